@@ -4,8 +4,22 @@ extern crate napi_build;
 #[cfg(any(feature = "py", feature = "py-noabi"))]
 extern crate pyo3_build_config;
 
-/// The main method of the buildscript, required by some glue modules.
+/// The main method of the buildscript, compiling protocol extensions and setting up glue modules.
 fn main() {
+	tonic_build::configure()
+		.compile_protos(
+			&[
+				"proto/members.proto",
+				"proto/presence.proto",
+				"proto/chat.proto",
+				"proto/annotations.proto",
+				"proto/decorations.proto",
+				"proto/metadata.proto",
+			],
+			&["proto"],
+		)
+		.expect("could not compile protocol extensions");
+
 	#[cfg(feature = "js")]
 	{
 		napi_build::setup();
@@ -1,6 +1,9 @@
 package mp.code;
 
+import mp.code.data.Annotation;
+import mp.code.data.BufferMetadata;
 import mp.code.data.BufferUpdate;
+import mp.code.data.LineEnding;
 import mp.code.data.TextChange;
 import mp.code.exceptions.ControllerException;
 
@@ -43,6 +46,33 @@ public final class BufferController {
 		return get_content(this.ptr);
 	}
 
+	private static native boolean verify(long self, long hash) throws ControllerException;
+
+	/**
+	 * Checks whether the given hash matches the content the editor should be displaying,
+	 * that is, the result of all {@link BufferUpdate}s received and {@link TextChange}s sent.
+	 * @param hash the hash of the editor content, see {@link Extensions#hash(String)}
+	 * @return true if the editor content is in sync
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public boolean verify(long hash) throws ControllerException {
+		return verify(this.ptr, hash);
+	}
+
+	private static native TextChange resync(long self, String content) throws ControllerException;
+
+	/**
+	 * Compares the given editor content with the content it should be displaying, and
+	 * returns the {@link TextChange} needed to bring it back in sync.
+	 * The change should only be applied locally: do not {@link #send(TextChange)} it.
+	 * @param content the current content of the editor
+	 * @return the corrective change, or an empty optional if already in sync
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Optional<TextChange> resync(String content) throws ControllerException {
+		return Optional.ofNullable(resync(this.ptr, content));
+	}
+
 	private static native BufferUpdate try_recv(long self) throws ControllerException;
 
 	/**
@@ -66,12 +96,22 @@ public final class BufferController {
 		return recv(this.ptr);
 	}
 
+	private static native boolean is_read_only(long self);
+
+	/**
+	 * Checks whether the current user may only read this buffer.
+	 * @return true if changes sent through this controller will be refused
+	 */
+	public boolean isReadOnly() {
+		return is_read_only(this.ptr);
+	}
+
 	private static native void send(long self, TextChange change) throws ControllerException;
 
 	/**
 	 * Tries to send a {@link TextChange} update.
 	 * @param change the update to send
-	 * @throws ControllerException if the controller was stopped
+	 * @throws ControllerException if the controller was stopped, or the buffer is read-only
 	 */
 	public void send(TextChange change) throws ControllerException {
 		send(this.ptr, change);
@@ -121,6 +161,162 @@ public final class BufferController {
 		ack(this.ptr, version);
 	}
 
+	private static native String annotate(long self, long startIdx, long endIdx, String text) throws ControllerException;
+
+	/**
+	 * Annotates a range of the buffer, as currently displayed by the editor.
+	 * The annotation becomes visible in {@link #annotations()} once the server relays it back.
+	 * @param startIdx the index of the first character to annotate
+	 * @param endIdx the index after the last character to annotate
+	 * @param text the text of the annotation
+	 * @return the identifier of the new annotation
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public String annotate(long startIdx, long endIdx, String text) throws ControllerException {
+		return annotate(this.ptr, startIdx, endIdx, text);
+	}
+
+	private static native void resolve_annotation(long self, String id) throws ControllerException;
+
+	/**
+	 * Marks an annotation as resolved.
+	 * @param id the identifier of the annotation
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public void resolveAnnotation(String id) throws ControllerException {
+		resolve_annotation(this.ptr, id);
+	}
+
+	private static native void delete_annotation(long self, String id) throws ControllerException;
+
+	/**
+	 * Deletes an annotation.
+	 * @param id the identifier of the annotation
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public void deleteAnnotation(String id) throws ControllerException {
+		delete_annotation(this.ptr, id);
+	}
+
+	private static native Annotation[] annotations(long self) throws ControllerException;
+
+	/**
+	 * Gets all the {@link Annotation}s of the buffer, in order of creation, with their ranges
+	 * moved to the content the editor should be displaying.
+	 * @return the annotations of the buffer
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Annotation[] annotations() throws ControllerException {
+		return annotations(this.ptr);
+	}
+
+	private static native void annotation_callback(long self, Consumer<BufferController> cb);
+
+	/**
+	 * Registers a callback to be invoked whenever an {@link Annotation} is created, resolved
+	 * or deleted. It is independent of the one set with {@link #callback(Consumer)}.
+	 * This will not work unless a Java thread has been dedicated to the event loop.
+	 * @param cb a {@link Consumer} that receives the controller when the change occurs;
+	 *           you should probably spawn a new thread in here, to avoid deadlocking
+	 * @see Extensions#drive(boolean)
+	 */
+	public void annotationCallback(Consumer<BufferController> cb) {
+		annotation_callback(this.ptr, cb);
+	}
+
+	private static native void clear_annotation_callback(long self);
+
+	/**
+	 * Clears the registered annotation callback.
+	 * @see #annotationCallback(Consumer)
+	 */
+	public void clearAnnotationCallback() {
+		clear_annotation_callback(this.ptr);
+	}
+
+	private static native BufferMetadata get_metadata(long self);
+
+	/**
+	 * Gets the latest {@link BufferMetadata} announced for this buffer.
+	 * @return the metadata of the buffer, with every value unset if none was announced
+	 */
+	public BufferMetadata getMetadata() {
+		return get_metadata(this.ptr);
+	}
+
+	private static native void metadata_callback(long self, Consumer<BufferController> cb);
+
+	/**
+	 * Registers a callback to be invoked whenever new {@link BufferMetadata} is announced.
+	 * It is independent of the one set with {@link #callback(Consumer)}.
+	 * This will not work unless a Java thread has been dedicated to the event loop.
+	 * @param cb a {@link Consumer} that receives the controller when the change occurs;
+	 *           you should probably spawn a new thread in here, to avoid deadlocking
+	 * @see Extensions#drive(boolean)
+	 */
+	public void metadataCallback(Consumer<BufferController> cb) {
+		metadata_callback(this.ptr, cb);
+	}
+
+	private static native void clear_metadata_callback(long self);
+
+	/**
+	 * Clears the registered metadata callback.
+	 * @see #metadataCallback(Consumer)
+	 */
+	public void clearMetadataCallback() {
+		clear_metadata_callback(this.ptr);
+	}
+
+	private static native void normalize_line_endings(long self, boolean enabled);
+
+	/**
+	 * Toggles replacing line endings of sent changes with the ones of the {@link BufferMetadata}.
+	 * The editor then receives the replaced line endings like any other change.
+	 * @param enabled whether to normalize line endings
+	 */
+	public void normalizeLineEndings(boolean enabled) {
+		normalize_line_endings(this.ptr, enabled);
+	}
+
+	private static native boolean is_normalizing_line_endings(long self);
+
+	/**
+	 * Checks whether line endings of sent changes are being normalized.
+	 * @return true if they are
+	 * @see #normalizeLineEndings(boolean)
+	 */
+	public boolean isNormalizingLineEndings() {
+		return is_normalizing_line_endings(this.ptr);
+	}
+
+	private static native void translate_line_endings(long self, LineEnding local);
+
+	/**
+	 * Translates line endings between the buffer and an editor using the given one.
+	 * While translating, every line break is shown with the local line ending: content, received
+	 * changes, hashes and indices all refer to the buffer as the editor shows it. Sent changes are
+	 * expected in the same form, and their line endings are replaced with the one of the
+	 * {@link BufferMetadata}, if set.
+	 * Since this changes how the buffer is shown, set it before fetching the content, or
+	 * {@link #resync(String)} afterwards.
+	 * @param local the line ending of the editor, or null to stop translating
+	 */
+	public void translateLineEndings(LineEnding local) {
+		translate_line_endings(this.ptr, local);
+	}
+
+	private static native LineEnding local_line_ending(long self);
+
+	/**
+	 * Gets the line ending of the editor, if line endings are being translated.
+	 * @return the local line ending, or an empty optional if not translating
+	 * @see #translateLineEndings(LineEnding)
+	 */
+	public Optional<LineEnding> getLocalLineEnding() {
+		return Optional.ofNullable(local_line_ending(this.ptr));
+	}
+
 	private static native void free(long self);
 
 	static {
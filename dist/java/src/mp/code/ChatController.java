@@ -0,0 +1,116 @@
+package mp.code;
+
+import mp.code.data.Draft;
+import mp.code.data.Message;
+import mp.code.exceptions.ControllerException;
+
+import java.util.Optional;
+import java.util.function.Consumer;
+
+/**
+ * Allows interaction with the CodeMP chat of a workspace.
+ * <p>
+ * It is generally safer to avoid storing this directly, see the api notes for {@link Workspace}.
+ */
+public final class ChatController {
+	private final long ptr;
+
+	ChatController(long ptr) {
+		this.ptr = ptr;
+		Extensions.CLEANER.register(this, () -> free(ptr));
+	}
+
+	private static native Message try_recv(long self) throws ControllerException;
+
+	/**
+	 * Tries to get a {@link Message} from the queue if any were present, and returns
+	 * an empty optional otherwise.
+	 * @return the first message in queue, if any are present
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Optional<Message> tryRecv() throws ControllerException {
+		return Optional.ofNullable(try_recv(this.ptr));
+	}
+
+	private static native Message recv(long self) throws ControllerException;
+
+	/**
+	 * Blocks until a {@link Message} is available and returns it.
+	 * @return the message that was received
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Message recv() throws ControllerException {
+		return recv(this.ptr);
+	}
+
+	private static native void send(long self, Draft draft) throws ControllerException;
+
+	/**
+	 * Tries to send a {@link Draft} to everyone in the workspace.
+	 * @param draft the message to send
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public void send(Draft draft) throws ControllerException {
+		send(this.ptr, draft);
+	}
+
+	private static native Message[] history(long self);
+
+	/**
+	 * Gets the last messages received, oldest first, including those already consumed.
+	 * @return an array of the last {@link Message}s
+	 */
+	public Message[] history() {
+		return history(this.ptr);
+	}
+
+	private static native Message[] thread_history(long self, String buffer);
+
+	/**
+	 * Gets the last messages received in threads about the given buffer, oldest first.
+	 * @param buffer the path of the buffer
+	 * @return an array of the last {@link Message}s about that buffer
+	 */
+	public Message[] threadHistory(String buffer) {
+		return thread_history(this.ptr, buffer);
+	}
+
+	private static native void callback(long self, Consumer<ChatController> cb);
+
+	/**
+	 * Registers a callback to be invoked whenever a {@link Message} is received.
+	 * This will not work unless a Java thread has been dedicated to the event loop.
+	 * @param cb a {@link Consumer} that receives the controller when a message arrives;
+	 *           you should probably spawn a new thread in here, to avoid deadlocking
+	 * @see Extensions#drive(boolean)
+	 */
+	public void callback(Consumer<ChatController> cb) {
+		callback(this.ptr, cb);
+	}
+
+	private static native void clear_callback(long self);
+
+	/**
+	 * Clears the registered callback.
+	 * @see #callback(Consumer)
+	 */
+	public void clearCallback() {
+		clear_callback(this.ptr);
+	}
+
+	private static native void poll(long self) throws ControllerException;
+
+	/**
+	 * Blocks until a {@link Message} is available.
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public void poll() throws ControllerException {
+		poll(this.ptr);
+	}
+
+	private static native void free(long self);
+
+	static {
+		NativeUtils.loadLibraryIfNeeded();
+	}
+}
@@ -2,6 +2,8 @@ package mp.code;
 
 import lombok.Getter;
 import mp.code.data.Config;
+import mp.code.data.Member;
+import mp.code.data.Role;
 import mp.code.data.User;
 import mp.code.exceptions.ConnectionException;
 import mp.code.exceptions.ConnectionRemoteException;
@@ -34,6 +36,26 @@ public final class Client {
 	 */
 	public static native Client connect(Config config) throws ConnectionException;
 
+	/**
+	 * Loads a {@link Config} from a configuration file and CODEMP_* environment variables,
+	 * letting the given explicit values take precedence. Empty optionals and credentials
+	 * in the explicit {@link Config} are ignored.
+	 * @param path the configuration file to read, null to look for it in the default locations
+	 * @param explicit a {@link Config} holding values which override everything else
+	 * @return the merged configuration
+	 * @throws ConnectionException if the configuration file or environment variables are invalid
+	 */
+	public static native Config loadConfig(String path, Config explicit) throws ConnectionException;
+
+	/**
+	 * Loads a {@link Config} from the default configuration file and CODEMP_* environment variables.
+	 * @return the loaded configuration
+	 * @throws ConnectionException if the configuration file or environment variables are invalid
+	 */
+	public static Config loadConfig() throws ConnectionException {
+		return loadConfig(null, new Config("", ""));
+	}
+
 	private static native User current_user(long self);
 
 	/**
@@ -91,6 +113,55 @@ public final class Client {
 		invite_to_workspace(this.ptr, workspaceId, user);
 	}
 
+	private static native Member[] fetch_workspace_members(long self, String workspaceId) throws ConnectionRemoteException;
+
+	/**
+	 * Lists the members of a workspace, including pending invites.
+	 * @param workspaceId the id of the workspace
+	 * @return an array of {@link Member}s
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public Member[] fetchWorkspaceMembers(String workspaceId) throws ConnectionRemoteException {
+		return fetch_workspace_members(this.ptr, workspaceId);
+	}
+
+	private static native void remove_from_workspace(long self, String workspaceId, String user) throws ConnectionRemoteException;
+
+	/**
+	 * Removes a member from an owned workspace, or revokes their pending invite.
+	 * @param workspaceId the id of the workspace
+	 * @param user the name of the user to remove
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public void removeFromWorkspace(String workspaceId, String user) throws ConnectionRemoteException {
+		remove_from_workspace(this.ptr, workspaceId, user);
+	}
+
+	private static native void set_workspace_role(long self, String workspaceId, String user, Role role) throws ConnectionRemoteException;
+
+	/**
+	 * Changes the {@link Role} of a member of an owned workspace.
+	 * @param workspaceId the id of the workspace
+	 * @param user the name of the member
+	 * @param role the new role
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public void setWorkspaceRole(String workspaceId, String user, Role role) throws ConnectionRemoteException {
+		set_workspace_role(this.ptr, workspaceId, user, role);
+	}
+
+	private static native void set_profile(long self, String displayName, String avatar) throws ConnectionRemoteException;
+
+	/**
+	 * Changes how the current user presents themselves to others.
+	 * @param displayName the name to show instead of the user name, if any
+	 * @param avatar the URL of an avatar picture, if any
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public void setProfile(Optional<String> displayName, Optional<String> avatar) throws ConnectionRemoteException {
+		set_profile(this.ptr, displayName.orElse(null), avatar.orElse(null));
+	}
+
 	private static native String[] fetch_owned_workspaces(long self) throws ConnectionRemoteException;
 
 	/**
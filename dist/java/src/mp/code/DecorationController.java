@@ -0,0 +1,117 @@
+package mp.code;
+
+import mp.code.data.Decoration;
+import mp.code.exceptions.ControllerException;
+
+import java.util.Optional;
+import java.util.function.Consumer;
+
+/**
+ * Allows interaction with the decorations of a CodeMP buffer, short-lived marks
+ * shared with the other users attached to it.
+ * <p>
+ * It is generally safer to avoid storing this directly, see the api notes for {@link Workspace}.
+ */
+public final class DecorationController {
+	private final long ptr;
+
+	DecorationController(long ptr) {
+		this.ptr = ptr;
+		Extensions.CLEANER.register(this, () -> free(ptr));
+	}
+
+	private static native String get_path(long self);
+
+	/**
+	 * Gets the path of the buffer decorated.
+	 * @return the path of the buffer
+	 */
+	public String getPath() {
+		return get_path(this.ptr);
+	}
+
+	private static native Decoration try_recv(long self) throws ControllerException;
+
+	/**
+	 * Tries to get a {@link Decoration} from the queue if any were present, and returns
+	 * an empty optional otherwise.
+	 * @return the first decoration in queue, if any are present
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Optional<Decoration> tryRecv() throws ControllerException {
+		return Optional.ofNullable(try_recv(this.ptr));
+	}
+
+	private static native Decoration recv(long self) throws ControllerException;
+
+	/**
+	 * Blocks until a {@link Decoration} is available and returns it.
+	 * @return the decoration that was received
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Decoration recv() throws ControllerException {
+		return recv(this.ptr);
+	}
+
+	private static native void send(long self, Decoration decoration) throws ControllerException;
+
+	/**
+	 * Tries to send a {@link Decoration} to everyone attached to the buffer.
+	 * @param decoration the decoration to send, its author is ignored
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public void send(Decoration decoration) throws ControllerException {
+		send(this.ptr, decoration);
+	}
+
+	private static native Decoration[] active(long self) throws ControllerException;
+
+	/**
+	 * Gets the decorations received which didn't expire yet, oldest first, including those
+	 * already consumed.
+	 * @return an array of the active {@link Decoration}s
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public Decoration[] active() throws ControllerException {
+		return active(this.ptr);
+	}
+
+	private static native void callback(long self, Consumer<DecorationController> cb);
+
+	/**
+	 * Registers a callback to be invoked whenever a {@link Decoration} is received.
+	 * This will not work unless a Java thread has been dedicated to the event loop.
+	 * @param cb a {@link Consumer} that receives the controller when a decoration arrives;
+	 *           you should probably spawn a new thread in here, to avoid deadlocking
+	 * @see Extensions#drive(boolean)
+	 */
+	public void callback(Consumer<DecorationController> cb) {
+		callback(this.ptr, cb);
+	}
+
+	private static native void clear_callback(long self);
+
+	/**
+	 * Clears the registered callback.
+	 * @see #callback(Consumer)
+	 */
+	public void clearCallback() {
+		clear_callback(this.ptr);
+	}
+
+	private static native void poll(long self) throws ControllerException;
+
+	/**
+	 * Blocks until a {@link Decoration} is available.
+	 * @throws ControllerException if the controller was stopped
+	 */
+	public void poll() throws ControllerException {
+		poll(this.ptr);
+	}
+
+	private static native void free(long self);
+
+	static {
+		NativeUtils.loadLibraryIfNeeded();
+	}
+}
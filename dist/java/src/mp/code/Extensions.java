@@ -1,6 +1,9 @@
 package mp.code;
 
+import mp.code.data.Theme;
+
 import java.lang.ref.Cleaner;
+import java.util.UUID;
 
 /**
  * A class holding utility functions, as well as functions which are specific
@@ -24,6 +27,14 @@ public final class Extensions {
 	 */
 	public static native long hash(String input);
 
+	/**
+	 * Gets the color assigned to a user, the same on every client.
+	 * @param id the {@link UUID} of the user
+	 * @param theme the {@link Theme} of the background the color is drawn on, null for a neutral one
+	 * @return the color, as <code>#rrggbb</code>
+	 */
+	public static native String userColor(UUID id, Theme theme);
+
 	/**
 	 * Drive the underlying library's asynchronous event loop. In other words, tells
 	 * it what thread to use. You usually want to call this during initialisation.
@@ -4,6 +4,10 @@ import java.util.Optional;
 import java.util.function.Consumer;
 
 import lombok.Getter;
+import mp.code.data.BufferMetadata;
+import mp.code.data.Member;
+import mp.code.data.Presence;
+import mp.code.data.Role;
 import mp.code.data.User;
 import mp.code.exceptions.ConnectionException;
 import mp.code.exceptions.ConnectionRemoteException;
@@ -113,6 +117,64 @@ public final class Workspace {
 		return attach_buffer(ptr, path);
 	}
 
+	private static native BufferController attach_buffer_read_only(long self, String path) throws ConnectionException;
+
+	/**
+	 * Attaches to an existing buffer with the given path as a spectator, if present.
+	 * The returned controller receives changes like any other, but refuses to send them.
+	 * @param path the path of the buffer to attach to
+	 * @return the read-only {@link BufferController} associated with that path
+	 * @throws ConnectionException if an error occurs in communicating with the server, or if the buffer did not exist
+	 */
+	public BufferController attachBufferReadOnly(String path) throws ConnectionException {
+		return attach_buffer_read_only(ptr, path);
+	}
+
+	private static native ChatController attach_chat(long self) throws ConnectionException;
+
+	/**
+	 * Attaches to the chat of this workspace, or gets it if it was already attached and is still running.
+	 * @return the {@link ChatController} of this workspace
+	 * @throws ConnectionException if an error occurs in communicating with the server, or if it has no chat
+	 */
+	public ChatController attachChat() throws ConnectionException {
+		return attach_chat(this.ptr);
+	}
+
+	private static native ChatController get_chat(long self);
+
+	/**
+	 * Gets the {@link ChatController} of this workspace, if the chat was attached and is still running.
+	 * @return the {@link ChatController}, if the chat was attached and the server didn't close it
+	 */
+	public Optional<ChatController> getChat() {
+		return Optional.ofNullable(get_chat(this.ptr));
+	}
+
+	private static native DecorationController attach_decorations(long self, String path) throws ConnectionException;
+
+	/**
+	 * Attaches to the decorations of a buffer, attaching to the buffer first if needed,
+	 * or gets them if they were already attached.
+	 * @param path the path of the buffer
+	 * @return the {@link DecorationController} of that buffer
+	 * @throws ConnectionException if an error occurs in communicating with the server, or if it has no decorations
+	 */
+	public DecorationController attachDecorations(String path) throws ConnectionException {
+		return attach_decorations(this.ptr, path);
+	}
+
+	private static native DecorationController get_decorations(long self, String path);
+
+	/**
+	 * Gets the {@link DecorationController} of a buffer, if its decorations were attached.
+	 * @param path the path of the buffer
+	 * @return the {@link DecorationController}, if the decorations were attached
+	 */
+	public Optional<DecorationController> getDecorations(String path) {
+		return Optional.ofNullable(get_decorations(this.ptr, path));
+	}
+
 	private static native boolean detach_buffer(long self, String path);
 
 	/**
@@ -146,6 +208,97 @@ public final class Workspace {
 		return fetch_users(this.ptr);
 	}
 
+	private static native Member[] fetch_members(long self) throws ConnectionRemoteException;
+
+	/**
+	 * Fetches the members of this workspace, including pending invites.
+	 * @return an array of {@link Member}s
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public Member[] fetchMembers() throws ConnectionRemoteException {
+		return fetch_members(this.ptr);
+	}
+
+	private static native BufferMetadata fetch_buffer_metadata(long self, String path) throws ConnectionRemoteException;
+
+	/**
+	 * Fetches the {@link BufferMetadata} of a buffer from the server.
+	 * @param path the path of the buffer
+	 * @return the metadata of the buffer
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public BufferMetadata fetchBufferMetadata(String path) throws ConnectionRemoteException {
+		return fetch_buffer_metadata(this.ptr, path);
+	}
+
+	private static native void set_buffer_metadata(long self, String path, BufferMetadata metadata) throws ConnectionRemoteException;
+
+	/**
+	 * Replaces the {@link BufferMetadata} of a buffer, announcing it to everyone attached to it.
+	 * Unset values are cleared.
+	 * @param path the path of the buffer
+	 * @param metadata the new metadata of the buffer
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public void setBufferMetadata(String path, BufferMetadata metadata) throws ConnectionRemoteException {
+		set_buffer_metadata(this.ptr, path, metadata);
+	}
+
+	private static native Presence[] presence(long self);
+
+	/**
+	 * Gets the {@link Presence} of all users currently in this workspace, as last known.
+	 * Changes are notified with {@link Event.Type#PRESENCE_CHANGED} events.
+	 * @return an array of {@link Presence}s
+	 */
+	public Presence[] presence() {
+		return presence(this.ptr);
+	}
+
+	private static native Presence user_presence(long self, String name);
+
+	/**
+	 * Gets the {@link Presence} of the user with the given name.
+	 * @param name the name of the user
+	 * @return the {@link Presence} of that user, if they are in this workspace
+	 */
+	public Optional<Presence> userPresence(String name) {
+		return Optional.ofNullable(user_presence(this.ptr, name));
+	}
+
+	private static native Presence[] fetch_presence(long self) throws ConnectionRemoteException;
+
+	/**
+	 * Updates the users in this workspace and their profiles, and fetches their {@link Presence}.
+	 * @return the updated array of {@link Presence}s
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public Presence[] fetchPresence() throws ConnectionRemoteException {
+		return fetch_presence(this.ptr);
+	}
+
+	private static native Role role(long self);
+
+	/**
+	 * Gets the {@link Role} of the current user in this workspace, as last fetched.
+	 * @return the role of the current user
+	 */
+	public Role role() {
+		return role(this.ptr);
+	}
+
+	private static native Role fetch_role(long self) throws ConnectionRemoteException;
+
+	/**
+	 * Updates and fetches the {@link Role} of the current user in this workspace.
+	 * Attached buffers will honour the new role.
+	 * @return the updated role
+	 * @throws ConnectionRemoteException if an error occurs in communicating with the server
+	 */
+	public Role fetchRole() throws ConnectionRemoteException {
+		return fetch_role(this.ptr);
+	}
+
 	private static native User[] fetch_buffer_users(long self, String path) throws ConnectionRemoteException;
 
 	/**
@@ -275,6 +428,17 @@ public final class Workspace {
 			} else return Optional.empty();
 		}
 
+		/**
+		 * Gets the user whose presence changed, if any did.
+		 * @return the user whose presence changed, if any did
+		 * @see Workspace#userPresence(String) to get the updated presence
+		 */
+		public Optional<String> getPresenceChanged() {
+			if(this.type == Type.PRESENCE_CHANGED) {
+				return Optional.of(this.argument);
+			} else return Optional.empty();
+		}
+
 		/**
 		 * The type of workspace event.
 		 */
@@ -293,7 +457,12 @@ public final class Workspace {
 			 * The filetree was updated.
 			 * @see #getChangedBuffer() to see the buffer that changed
 			 */
-			FILE_TREE_UPDATED
+			FILE_TREE_UPDATED,
+			/**
+			 * The presence of somebody changed.
+			 * @see #getPresenceChanged() to get the name
+			 */
+			PRESENCE_CHANGED
 		}
 	}
 }
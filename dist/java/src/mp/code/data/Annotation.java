@@ -0,0 +1,44 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+/**
+ * A data class holding a review comment about a range of a buffer.
+ * The range moves along as the buffer is edited, to keep covering the same text.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+public class Annotation {
+	/**
+	 * The unique identifier of the annotation.
+	 */
+	public final String id;
+
+	/**
+	 * The user who created the annotation, empty if they left the workspace.
+	 */
+	public final String author;
+
+	/**
+	 * The text of the annotation.
+	 */
+	public final String text;
+
+	/**
+	 * The index of the first character covered, in the buffer as the editor should display it.
+	 */
+	public final long startIdx;
+
+	/**
+	 * The index after the last character covered, in the buffer as the editor should display it.
+	 */
+	public final long endIdx;
+
+	/**
+	 * Whether the annotation was marked as resolved.
+	 */
+	public final boolean resolved;
+}
@@ -0,0 +1,41 @@
+package mp.code.data;
+
+import lombok.AccessLevel;
+import lombok.AllArgsConstructor;
+import lombok.EqualsAndHashCode;
+import lombok.ToString;
+import lombok.With;
+
+import java.util.Optional;
+
+/**
+ * A data class holding properties of a buffer which every editor should agree on.
+ * Unset values are left for each editor to guess.
+ */
+@With
+@ToString
+@EqualsAndHashCode
+@AllArgsConstructor(access = AccessLevel.PRIVATE)
+@SuppressWarnings("OptionalUsedAsFieldOrParameterType")
+public class BufferMetadata {
+	/** Identifier of the language of the buffer, such as {@code rust}, if any. */
+	public final Optional<String> language;
+	/** How lines of the buffer are terminated, if known. */
+	public final Optional<LineEnding> lineEnding;
+	/** How lines of the buffer are indented, if known. */
+	public final Optional<Indentation> indentation;
+	/** Encoding of the buffer once saved to a file, such as {@code utf-8}, if any. */
+	public final Optional<String> encoding;
+
+	/**
+	 * Provides metadata where every value is unset.
+	 */
+	public BufferMetadata() {
+		this(
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty()
+		);
+	}
+}
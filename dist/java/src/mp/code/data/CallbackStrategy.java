@@ -0,0 +1,15 @@
+package mp.code.data;
+
+/**
+ * How registered callbacks are run.
+ */
+public enum CallbackStrategy {
+	/** Directly on the network worker, which waits for them to return. */
+	INLINE,
+	/** In order on a dedicated task, without ever skipping an invocation. */
+	TASK,
+	/** Like {@link #TASK}, but skipping invocations while the callback queue is full. */
+	DROP,
+	/** Like {@link #TASK}, but skipping invocations while one is already pending. */
+	COALESCE
+}
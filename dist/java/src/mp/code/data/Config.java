@@ -4,6 +4,7 @@ import lombok.AccessLevel;
 import lombok.EqualsAndHashCode;
 import lombok.RequiredArgsConstructor;
 import lombok.ToString;
+import lombok.With;
 
 import java.util.Optional;
 import java.util.OptionalInt;
@@ -20,12 +21,26 @@ public class Config {
 	public final String username;
 	/** The password to connect with. */
 	public final String password;
-	/** The host to connect to, if custom. */
+	/** A pre-issued session token to use instead of logging in, if any. */
+	@With public final Optional<String> token;
+	/** An environment variable holding a session token to use instead of logging in, if any. */
+	@With public final Optional<String> tokenEnv;
+	/** A shell command printing a session token to use instead of logging in, if any. */
+	@With public final Optional<String> tokenCommand;
+	/** The full URL of the server, like "https://code.example:50053/prefix", if custom. */
+	public final Optional<String> endpoint;
+	/** The host to connect to, if custom; may also be "unix:///path/to/socket". */
 	public final Optional<String> host;
 	/** The port to connect to, if custom. */
 	public final OptionalInt port;
 	/** Whether to use TLS, if custom. */
 	public final Optional<Boolean> tls;
+	/** Certificates and verification settings for TLS connections, if custom. */
+	@With public final Optional<TlsSettings> tlsSettings;
+	/** The proxy to connect through, as "http://host:port" or "socks5://host:port", if any. */
+	@With public final Optional<String> proxy;
+	/** Fine-tuning for internal workers and connections, if custom. */
+	@With public final Optional<Tuning> tuning;
 
 	/**
 	 * Provides the given username and password on the default server.
@@ -37,7 +52,38 @@ public class Config {
 			username,
 			password,
 			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
 			OptionalInt.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty()
+		);
+	}
+
+	/**
+	 * Provides the given username and password on the server at the given URL.
+	 * Host, port and TLS are taken from the URL; it is validated upon connecting.
+	 * @param username the username
+	 * @param password the password
+	 * @param endpoint the full URL of the server, like "https://code.example:50053/prefix"
+	 */
+	public Config(String username, String password, String endpoint) {
+		this(
+			username,
+			password,
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.of(endpoint),
+			Optional.empty(),
+			OptionalInt.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
 			Optional.empty()
 		);
 	}
@@ -54,9 +100,16 @@ public class Config {
 		this(
 			username,
 			password,
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
 			Optional.of(host),
 			OptionalInt.of(port),
-			Optional.of(tls)
+			Optional.of(tls),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty()
 		);
 	}
 }
@@ -0,0 +1,60 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+/**
+ * A data class holding a short-lived mark on a range of a buffer, shared with other users.
+ * The range moves along as the buffer is edited, to keep covering the same text.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+public class Decoration {
+	/**
+	 * How long decorations are shown by default, in milliseconds.
+	 */
+	public static final long DEFAULT_TTL_MS = 30_000;
+
+	/**
+	 * What the decoration represents.
+	 */
+	public final DecorationKind kind;
+
+	/**
+	 * The message explaining the decoration, possibly empty.
+	 */
+	public final String message;
+
+	/**
+	 * The user who sent the decoration, ignored when sending.
+	 */
+	public final String author;
+
+	/**
+	 * The index of the first character covered, in the buffer as the editor should display it.
+	 */
+	public final long startIdx;
+
+	/**
+	 * The index after the last character covered, in the buffer as the editor should display it.
+	 */
+	public final long endIdx;
+
+	/**
+	 * How long the decoration should be shown once received, in milliseconds.
+	 */
+	public final long ttlMs;
+
+	/**
+	 * Creates a decoration to send, shown for {@link #DEFAULT_TTL_MS}.
+	 * @param kind what the decoration represents
+	 * @param startIdx the index of the first character covered
+	 * @param endIdx the index after the last character covered
+	 * @param message the message explaining the decoration
+	 */
+	public Decoration(DecorationKind kind, long startIdx, long endIdx, String message) {
+		this(kind, message, "", startIdx, endIdx, DEFAULT_TTL_MS);
+	}
+}
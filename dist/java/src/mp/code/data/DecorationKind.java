@@ -0,0 +1,19 @@
+package mp.code.data;
+
+/**
+ * What a {@link Decoration} represents, letting editors pick how to draw it.
+ */
+public enum DecorationKind {
+	/** A highlighted range, such as a search result. */
+	HIGHLIGHT,
+	/** A range the author wants others to look at. */
+	MARK,
+	/** An error diagnostic. */
+	ERROR,
+	/** A warning diagnostic. */
+	WARNING,
+	/** An informational diagnostic. */
+	INFO,
+	/** A hint diagnostic. */
+	HINT
+}
@@ -0,0 +1,34 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+import java.util.Optional;
+
+/**
+ * A data class holding a chat message about to be sent.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+@SuppressWarnings("OptionalUsedAsFieldOrParameterType")
+public class Draft {
+	/**
+	 * The text of the message.
+	 */
+	public final String text;
+
+	/**
+	 * The buffer range this message is about, if it belongs to a thread.
+	 */
+	public final Optional<Selection> thread;
+
+	/**
+	 * Creates a message for the whole workspace.
+	 * @param text the text of the message
+	 */
+	public Draft(String text) {
+		this(text, Optional.empty());
+	}
+}
@@ -0,0 +1,23 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+/**
+ * A data class holding how lines of a buffer are indented.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+public class Indentation {
+	/**
+	 * Whether lines are indented with tabs rather than spaces.
+	 */
+	public final boolean tabs;
+
+	/**
+	 * How many columns each indentation level spans.
+	 */
+	public final int width;
+}
@@ -0,0 +1,13 @@
+package mp.code.data;
+
+/**
+ * How lines of a buffer are terminated.
+ */
+public enum LineEnding {
+	/** A line feed, as on Unix systems. */
+	LF,
+	/** A carriage return followed by a line feed, as on Windows systems. */
+	CRLF,
+	/** A carriage return alone, as on classic Mac OS systems. */
+	CR
+}
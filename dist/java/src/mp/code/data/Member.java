@@ -0,0 +1,28 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+/**
+ * A data class holding information about a member of a workspace.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+public class Member {
+	/**
+	 * The {@link User} this membership refers to.
+	 */
+	public final User user;
+
+	/**
+	 * The {@link Role} of the user within the workspace.
+	 */
+	public final Role role;
+
+	/**
+	 * Whether the user was invited but has not joined yet.
+	 */
+	public final boolean pending;
+}
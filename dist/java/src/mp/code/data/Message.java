@@ -0,0 +1,36 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+import java.util.Optional;
+
+/**
+ * A data class holding a chat message relayed by the server.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+@SuppressWarnings("OptionalUsedAsFieldOrParameterType")
+public class Message {
+	/**
+	 * The user who sent the message, empty if they left the workspace.
+	 */
+	public final String user;
+
+	/**
+	 * The text of the message.
+	 */
+	public final String text;
+
+	/**
+	 * When the server received the message, in milliseconds since the UNIX epoch.
+	 */
+	public final long timestamp;
+
+	/**
+	 * The buffer range this message is about, if it belongs to a thread.
+	 */
+	public final Optional<Selection> thread;
+}
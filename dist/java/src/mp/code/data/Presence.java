@@ -0,0 +1,54 @@
+package mp.code.data;
+
+import lombok.EqualsAndHashCode;
+import lombok.RequiredArgsConstructor;
+import lombok.ToString;
+
+import java.util.Optional;
+
+/**
+ * A data class holding what a user in a workspace is up to.
+ */
+@ToString
+@EqualsAndHashCode
+@RequiredArgsConstructor
+@SuppressWarnings("OptionalUsedAsFieldOrParameterType")
+public class Presence {
+	/**
+	 * The {@link User} this presence refers to.
+	 */
+	public final User user;
+
+	/**
+	 * The name to show instead of the user name, if the user chose one.
+	 */
+	public final Optional<String> displayName;
+
+	/**
+	 * The URL of an avatar picture, if the user chose one.
+	 */
+	public final Optional<String> avatar;
+
+	/**
+	 * The color assigned to the user, as "#rrggbb", the same on every client.
+	 */
+	public final String color;
+
+	/**
+	 * How recently the user was active.
+	 */
+	public final Status status;
+
+	/**
+	 * The path of the buffer the user last moved their cursor in, if any.
+	 */
+	public final Optional<String> buffer;
+
+	/**
+	 * Gets the name to show for this user.
+	 * @return the display name if set, the user name otherwise
+	 */
+	public String label() {
+		return this.displayName.orElse(this.user.name);
+	}
+}
@@ -0,0 +1,13 @@
+package mp.code.data;
+
+/**
+ * The role a user holds within a workspace.
+ */
+public enum Role {
+	/** The user can read and edit buffers. */
+	READ_WRITE,
+	/** The user can read buffers but not edit them. */
+	READ_ONLY,
+	/** The user owns the workspace and can manage its members. */
+	OWNER
+}
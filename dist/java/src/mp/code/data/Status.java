@@ -0,0 +1,13 @@
+package mp.code.data;
+
+/**
+ * How recently a user was seen doing something in a workspace.
+ */
+public enum Status {
+	/** The user recently moved their cursor. */
+	ACTIVE,
+	/** The user didn't move their cursor for a while. */
+	IDLE,
+	/** The user didn't move their cursor for a long time. */
+	AWAY
+}
@@ -0,0 +1,11 @@
+package mp.code.data;
+
+/**
+ * The background a color is going to be drawn on.
+ */
+public enum Theme {
+	/** A light background: colors are darkened. */
+	LIGHT,
+	/** A dark background: colors are brightened. */
+	DARK
+}
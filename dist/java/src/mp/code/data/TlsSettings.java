@@ -0,0 +1,50 @@
+package mp.code.data;
+
+import lombok.AccessLevel;
+import lombok.AllArgsConstructor;
+import lombok.EqualsAndHashCode;
+import lombok.ToString;
+import lombok.With;
+
+import java.util.Optional;
+
+/**
+ * A data class holding settings for TLS connections.
+ * Unset values fall back to verifying the server against the system trust store.
+ */
+@With
+@ToString
+@EqualsAndHashCode
+@AllArgsConstructor(access = AccessLevel.PRIVATE)
+@SuppressWarnings("OptionalUsedAsFieldOrParameterType")
+public class TlsSettings {
+	/** Path to a PEM bundle of additional certificate authorities to trust, if any. */
+	public final Optional<String> caBundle;
+	/** Whether to also trust the system certificate authorities, if custom. */
+	public final Optional<Boolean> nativeRoots;
+	/** Path to a PEM client certificate chain to present, if any; requires {@link #clientKey}. */
+	public final Optional<String> clientCert;
+	/** Path to the PEM private key of {@link #clientCert}, if any. */
+	public final Optional<String> clientKey;
+	/** Name to verify the server certificate against, if different from the host. */
+	public final Optional<String> domain;
+	/**
+	 * SHA-256 fingerprint the server certificate must match, as hex digits, if any.
+	 * The certificate must still chain to a trusted authority.
+	 */
+	public final Optional<String> pinnedSha256;
+
+	/**
+	 * Provides settings where every parameter uses its default value.
+	 */
+	public TlsSettings() {
+		this(
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty(),
+			Optional.empty()
+		);
+	}
+}
@@ -0,0 +1,81 @@
+package mp.code.data;
+
+import lombok.AccessLevel;
+import lombok.AllArgsConstructor;
+import lombok.EqualsAndHashCode;
+import lombok.ToString;
+import lombok.With;
+
+import java.util.Optional;
+import java.util.OptionalInt;
+
+/**
+ * A data class holding fine-tuning parameters for internal workers and connections.
+ * Unset values fall back to sensible defaults; all durations are in milliseconds.
+ */
+@With
+@ToString
+@EqualsAndHashCode
+@AllArgsConstructor(access = AccessLevel.PRIVATE)
+@SuppressWarnings("OptionalUsedAsFieldOrParameterType")
+public class Tuning {
+	/** Capacity of the outgoing operations queue of each buffer, if custom. */
+	public final OptionalInt bufferQueue;
+	/** Capacity of the outgoing cursor movements queue, if custom. */
+	public final OptionalInt cursorQueue;
+	/** How many buffer updates between content hashes, if custom (zero disables them). */
+	public final OptionalInt hashPeriod;
+	/** How often idle workspace workers wake up to refresh presence and maybe stop, if custom. */
+	public final OptionalInt workerTickMs;
+	/** How long to wait before giving up on a connection attempt, if any. */
+	public final OptionalInt connectTimeoutMs;
+	/** How long to wait for a response to a request before giving up, if any. */
+	public final OptionalInt requestTimeoutMs;
+	/** How often to send HTTP/2 pings to keep connections alive, if any. */
+	public final OptionalInt http2KeepaliveMs;
+	/** How long to wait for a ping to be answered before considering a connection dead, if custom. */
+	public final OptionalInt http2KeepaliveTimeoutMs;
+	/** After how long of idling to start sending TCP keepalive probes, if any. */
+	public final OptionalInt tcpKeepaliveMs;
+	/** How many times to retry a failed connection attempt, if custom. */
+	public final OptionalInt connectRetries;
+	/** Delay before retrying a failed connection or token renewal, doubled each time, if custom. */
+	public final OptionalInt retryBackoffMs;
+	/** How long before they expire access tokens should be renewed, if custom. */
+	public final OptionalInt refreshMarginMs;
+	/** How registered callbacks are run, if not {@link CallbackStrategy#INLINE}. */
+	public final Optional<CallbackStrategy> callbackStrategy;
+	/** How many invocations may be pending with {@link CallbackStrategy#DROP}, if custom. */
+	public final OptionalInt callbackQueue;
+	/** After how long without cursor movements users should be considered idle, if custom. */
+	public final OptionalInt idleAfterMs;
+	/** After how long without cursor movements users should be considered away, if custom. */
+	public final OptionalInt awayAfterMs;
+	/** How many chat messages to keep in the local history, if custom. */
+	public final OptionalInt chatHistory;
+
+	/**
+	 * Provides a tuning where every parameter uses its default value.
+	 */
+	public Tuning() {
+		this(
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			Optional.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty(),
+			OptionalInt.empty()
+		);
+	}
+}
@@ -3,6 +3,7 @@ package mp.code.data;
 import lombok.EqualsAndHashCode;
 import lombok.RequiredArgsConstructor;
 import lombok.ToString;
+import mp.code.Extensions;
 
 import java.util.UUID;
 
@@ -22,4 +23,13 @@ public class User {
 	 * The human-readable name of the user.
 	 */
 	public final String name;
+
+	/**
+	 * Gets the color assigned to this user, the same on every client.
+	 * @param theme the {@link Theme} of the background the color is drawn on, null for a neutral one
+	 * @return the color, as <code>#rrggbb</code>
+	 */
+	public String color(Theme theme) {
+		return Extensions.userColor(this.id, theme);
+	}
 }
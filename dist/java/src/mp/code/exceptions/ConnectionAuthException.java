@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned when credentials to authenticate with could not be obtained.
+ */
+public class ConnectionAuthException extends ConnectionException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public ConnectionAuthException(String message) {
+		super(message);
+	}
+}
@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned when the given {@link mp.code.data.Config} can't be used to connect.
+ */
+public class ConnectionConfigException extends ConnectionException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public ConnectionConfigException(String message) {
+		super(message);
+	}
+}
@@ -12,4 +12,12 @@ public abstract class ConnectionException extends Exception {
 	protected ConnectionException(String message) {
 		super(message);
 	}
+
+	/**
+	 * Whether trying the same operation again later may succeed.
+	 * @return true if the failure is temporary, such as the server being unreachable
+	 */
+	public boolean isRetryable() {
+		return false;
+	}
 }
@@ -2,6 +2,7 @@ package mp.code.exceptions;
 
 /**
  * An exception returned by the server as a response.
+ * Concrete subclasses tell why the request was rejected.
  */
 public abstract class ConnectionRemoteException extends ConnectionException {
 
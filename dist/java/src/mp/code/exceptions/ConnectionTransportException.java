@@ -3,7 +3,7 @@ package mp.code.exceptions;
 /**
  * An exception that occurred from the underlying tonic layer.
  */
-public abstract class ConnectionTransportException extends Exception {
+public class ConnectionTransportException extends ConnectionException {
 
 	/**
 	 * Creates a new exception with the given message.
@@ -12,4 +12,9 @@ public abstract class ConnectionTransportException extends Exception {
 	public ConnectionTransportException(String message) {
 		super(message);
 	}
+
+	@Override
+	public boolean isRetryable() {
+		return true;
+	}
 }
@@ -0,0 +1,16 @@
+package mp.code.exceptions;
+
+/**
+ * An exception that occurs when attempting to send changes to a buffer
+ * the current user is only allowed to read.
+ */
+public class ControllerReadOnlyException extends ControllerException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public ControllerReadOnlyException(String message) {
+		super(message);
+	}
+}
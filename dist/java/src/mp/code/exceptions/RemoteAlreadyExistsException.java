@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the resource to create already exists.
+ */
+public class RemoteAlreadyExistsException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteAlreadyExistsException(String message) {
+		super(message);
+	}
+}
@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the request conflicts with the current state of the resource.
+ */
+public class RemoteConflictException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteConflictException(String message) {
+		super(message);
+	}
+}
@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when credentials are valid, but don't allow the requested operation.
+ */
+public class RemoteForbiddenException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteForbiddenException(String message) {
+		super(message);
+	}
+}
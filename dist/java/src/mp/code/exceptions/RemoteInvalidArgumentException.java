@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the request was malformed.
+ */
+public class RemoteInvalidArgumentException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteInvalidArgumentException(String message) {
+		super(message);
+	}
+}
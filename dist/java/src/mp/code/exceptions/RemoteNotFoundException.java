@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the requested resource (workspace, buffer, user...) does not exist.
+ */
+public class RemoteNotFoundException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteNotFoundException(String message) {
+		super(message);
+	}
+}
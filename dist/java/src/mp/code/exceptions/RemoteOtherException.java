@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the server failed for any other reason, such as an internal error.
+ */
+public class RemoteOtherException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteOtherException(String message) {
+		super(message);
+	}
+}
@@ -0,0 +1,20 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when too many requests were made, or some quota is exhausted.
+ */
+public class RemoteRateLimitedException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteRateLimitedException(String message) {
+		super(message);
+	}
+
+	@Override
+	public boolean isRetryable() {
+		return true;
+	}
+}
@@ -0,0 +1,20 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the request did not complete in time.
+ */
+public class RemoteTimedOutException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteTimedOutException(String message) {
+		super(message);
+	}
+
+	@Override
+	public boolean isRetryable() {
+		return true;
+	}
+}
@@ -0,0 +1,15 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when credentials are missing, wrong or expired.
+ */
+public class RemoteUnauthorizedException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteUnauthorizedException(String message) {
+		super(message);
+	}
+}
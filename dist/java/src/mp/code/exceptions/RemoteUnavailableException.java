@@ -0,0 +1,20 @@
+package mp.code.exceptions;
+
+/**
+ * An exception returned by the server when the server is temporarily unable to serve requests.
+ */
+public class RemoteUnavailableException extends ConnectionRemoteException {
+
+	/**
+	 * Creates a new exception with the given message.
+	 * @param message the message
+	 */
+	public RemoteUnavailableException(String message) {
+		super(message);
+	}
+
+	@Override
+	public boolean isRetryable() {
+		return true;
+	}
+}
@@ -135,6 +135,114 @@ function MaybeCursorPromise:cancel() end
 function MaybeCursorPromise:and_then(cb) end
 
 
+---@class (exact) ChatControllerPromise : Promise
+local ChatControllerPromise = {}
+--- block until promise is ready and return value
+--- @return ChatController
+function ChatControllerPromise:await() end
+--- cancel promise execution
+function ChatControllerPromise:cancel() end
+---@param cb fun(x: ChatController) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function ChatControllerPromise:and_then(cb) end
+
+
+---@class (exact) MessagePromise : Promise
+local MessagePromise = {}
+--- block until promise is ready and return value
+--- @return Message
+function MessagePromise:await() end
+--- cancel promise execution
+function MessagePromise:cancel() end
+---@param cb fun(x: Message) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function MessagePromise:and_then(cb) end
+
+
+---@class (exact) MaybeMessagePromise : Promise
+local MaybeMessagePromise = {}
+--- block until promise is ready and return value
+--- @return Message | nil
+function MaybeMessagePromise:await() end
+--- cancel promise execution
+function MaybeMessagePromise:cancel() end
+---@param cb fun(x: Message | nil) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function MaybeMessagePromise:and_then(cb) end
+
+
+---@class (exact) AnnotationListPromise : Promise
+local AnnotationListPromise = {}
+--- block until promise is ready and return value
+--- @return Annotation[]
+function AnnotationListPromise:await() end
+--- cancel promise execution
+function AnnotationListPromise:cancel() end
+---@param cb fun(x: Annotation[]) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function AnnotationListPromise:and_then(cb) end
+
+
+---@class (exact) BufferMetadataPromise : Promise
+local BufferMetadataPromise = {}
+--- block until promise is ready and return value
+--- @return BufferMetadata
+function BufferMetadataPromise:await() end
+--- cancel promise execution
+function BufferMetadataPromise:cancel() end
+---@param cb fun(x: BufferMetadata) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function BufferMetadataPromise:and_then(cb) end
+
+
+---@class (exact) DecorationControllerPromise : Promise
+local DecorationControllerPromise = {}
+--- block until promise is ready and return value
+--- @return DecorationController
+function DecorationControllerPromise:await() end
+--- cancel promise execution
+function DecorationControllerPromise:cancel() end
+---@param cb fun(x: DecorationController) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function DecorationControllerPromise:and_then(cb) end
+
+
+---@class (exact) DecorationPromise : Promise
+local DecorationPromise = {}
+--- block until promise is ready and return value
+--- @return Decoration
+function DecorationPromise:await() end
+--- cancel promise execution
+function DecorationPromise:cancel() end
+---@param cb fun(x: Decoration) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function DecorationPromise:and_then(cb) end
+
+
+---@class (exact) MaybeDecorationPromise : Promise
+local MaybeDecorationPromise = {}
+--- block until promise is ready and return value
+--- @return Decoration | nil
+function MaybeDecorationPromise:await() end
+--- cancel promise execution
+function MaybeDecorationPromise:cancel() end
+---@param cb fun(x: Decoration | nil) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function MaybeDecorationPromise:and_then(cb) end
+
+
+---@class (exact) DecorationListPromise : Promise
+local DecorationListPromise = {}
+--- block until promise is ready and return value
+--- @return Decoration[]
+function DecorationListPromise:await() end
+--- cancel promise execution
+function DecorationListPromise:cancel() end
+---@param cb fun(x: Decoration[]) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function DecorationListPromise:and_then(cb) end
+
+
 ---@class (exact) BufferUpdatePromise : Promise
 local BufferUpdatePromise = {}
 --- block until promise is ready and return value
@@ -158,6 +266,29 @@ function MaybeBufferUpdatePromise:cancel() end
 ---invoke callback asynchronously as soon as promise is ready
 function MaybeBufferUpdatePromise:and_then(cb) end
 
+---@class (exact) BooleanPromise : Promise
+local BooleanPromise = {}
+--- block until promise is ready and return value
+--- @return boolean
+function BooleanPromise:await() end
+--- cancel promise execution
+function BooleanPromise:cancel() end
+---@param cb fun(x: boolean) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function BooleanPromise:and_then(cb) end
+
+
+---@class (exact) MaybeTextChangePromise : Promise
+local MaybeTextChangePromise = {}
+--- block until promise is ready and return value
+--- @return TextChange | nil
+function MaybeTextChangePromise:await() end
+--- cancel promise execution
+function MaybeTextChangePromise:cancel() end
+---@param cb fun(x: TextChange | nil) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function MaybeTextChangePromise:and_then(cb) end
+
 ---@class (exact) UserListPromise : Promise
 local UserListPromise = {}
 --- block until promise is ready and return value
@@ -169,6 +300,39 @@ function UserListPromise:cancel() end
 ---invoke callback asynchronously as soon as promise is ready
 function UserListPromise:and_then(cb) end
 
+---@class (exact) MemberListPromise : Promise
+local MemberListPromise = {}
+--- block until promise is ready and return value
+--- @return Member[]
+function MemberListPromise:await() end
+--- cancel promise execution
+function MemberListPromise:cancel() end
+---@param cb fun(x: Member[]) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function MemberListPromise:and_then(cb) end
+
+---@class (exact) RolePromise : Promise
+local RolePromise = {}
+--- block until promise is ready and return value
+--- @return Role
+function RolePromise:await() end
+--- cancel promise execution
+function RolePromise:cancel() end
+---@param cb fun(x: Role) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function RolePromise:and_then(cb) end
+
+---@class (exact) PresenceListPromise : Promise
+local PresenceListPromise = {}
+--- block until promise is ready and return value
+--- @return Presence[]
+function PresenceListPromise:await() end
+--- cancel promise execution
+function PresenceListPromise:cancel() end
+---@param cb fun(x: Presence[]) callback to invoke
+---invoke callback asynchronously as soon as promise is ready
+function PresenceListPromise:and_then(cb) end
+
 -- [[ END ASYNC STUFF ]]
 
 
@@ -223,6 +387,38 @@ function Client:delete_workspace(ws) end
 ---grant user acccess to workspace
 function Client:invite_to_workspace(ws, user) end
 
+---@param ws string workspace id to list members of
+---@return MemberListPromise
+---@async
+---@nodiscard
+---fetch members of workspace with their roles, including pending invites
+function Client:fetch_workspace_members(ws) end
+
+---@param ws string workspace id to remove user from
+---@param user string user name to remove from given workspace
+---@return NilPromise
+---@async
+---@nodiscard
+---revoke user access to workspace, or their pending invite
+function Client:remove_from_workspace(ws, user) end
+
+---@param ws string workspace id to change role in
+---@param user string user name whose role should change
+---@param role Role new role of user
+---@return NilPromise
+---@async
+---@nodiscard
+---change role of user in workspace
+function Client:set_workspace_role(ws, user, role) end
+
+---@param display_name? string name to show instead of user name, cleared if nil
+---@param avatar? string url of avatar picture, cleared if nil
+---@return NilPromise
+---@async
+---@nodiscard
+---change how current user presents themselves to others
+function Client:set_profile(display_name, avatar) end
+
 ---@return StringArrayPromise
 ---@async
 ---@nodiscard
@@ -246,6 +442,23 @@ function Client:get_workspace(ws) end
 ---@field id string user uuid
 ---@field name string user display name
 
+---@alias Role "read_write" | "read_only" | "owner"
+
+---@class Member
+---@field user User workspace member
+---@field role Role what member may do in the workspace
+---@field pending boolean true if member was invited but didn't join yet
+
+---@alias Status "active" | "idle" | "away"
+
+---@class Presence
+---@field user User user this presence refers to
+---@field display_name string? name to show instead of user name, if set
+---@field avatar string? url of avatar picture, if set
+---@field color string color assigned to user, as "#rrggbb"
+---@field status Status how recently user moved their cursor
+---@field buffer string? buffer user last moved their cursor in, if any
+
 
 
 ---@class (exact) Workspace
@@ -290,6 +503,35 @@ function Workspace:get_buffer(path) end
 ---attach to a remote buffer, synching content and changes and returning its controller
 function Workspace:attach_buffer(path) end
 
+---@param path string relative path ("name") of buffer to spectate
+---@return BufferControllerPromise
+---@async
+---@nodiscard
+---attach to a remote buffer as a spectator: content and changes are received, but any change sent is refused
+function Workspace:attach_buffer_read_only(path) end
+
+---@return ChatControllerPromise
+---@async
+---@nodiscard
+---attach to the workspace chat and return its controller, or the existing one if still running
+function Workspace:attach_chat() end
+
+---@return ChatController?
+---get the chat controller, if the chat was attached and the server didn't close it
+function Workspace:get_chat() end
+
+---@param path string relative path ("name") of buffer to decorate
+---@return DecorationControllerPromise
+---@async
+---@nodiscard
+---attach to the decorations of a buffer, attaching to the buffer first if needed, and return their controller, or the existing one if already attached
+function Workspace:attach_decorations(path) end
+
+---@param path string relative path ("name") of decorated buffer
+---@return DecorationController?
+---get the decoration controller of a buffer, if its decorations were attached
+function Workspace:get_decorations(path) end
+
 ---@param path string relative path ("name") of buffer to detach from
 ---@return boolean success
 ---detach from an active buffer, closing all streams. returns false if there are still dangling references
@@ -323,6 +565,52 @@ function Workspace:fetch_users(path) end
 ---fetch the list of users in the given buffer
 function Workspace:fetch_buffer_users(path) end
 
+---@param path string the relative path to the buffer
+---@return BufferMetadataPromise
+---@async
+---@nodiscard
+---fetch metadata of given buffer from the server
+function Workspace:fetch_buffer_metadata(path) end
+
+---@param path string the relative path to the buffer
+---@param metadata BufferMetadata the new metadata, unset fields are cleared
+---@return NilPromise
+---@async
+---@nodiscard
+---replace metadata of given buffer, announcing it to everyone attached to it
+function Workspace:set_buffer_metadata(path, metadata) end
+
+---@return MemberListPromise
+---@async
+---@nodiscard
+---fetch members of this workspace with their roles, including pending invites
+function Workspace:fetch_members() end
+
+---@return Role
+---role of current user in this workspace, as last fetched
+function Workspace:role() end
+
+---@return RolePromise
+---@async
+---@nodiscard
+---re-fetch role of current user in this workspace, deciding whether buffers accept changes
+function Workspace:fetch_role() end
+
+---@return Presence[]
+---presence of all users currently in this workspace, as last known
+function Workspace:presence() end
+
+---@param name string user name to look for
+---@return Presence?
+---presence of given user, if currently in this workspace
+function Workspace:user_presence(name) end
+
+---@return PresenceListPromise
+---@async
+---@nodiscard
+---re-fetch users in this workspace and their profiles, returning their presence
+function Workspace:fetch_presence() end
+
 ---@class (exact) WorkspaceEvent
 ---@field type string
 ---@field value string
@@ -380,8 +668,13 @@ function TextChange:apply(other) end
 ---@async
 ---@nodiscard
 ---update buffer with a text change; note that to delete content should be empty but not span, while to insert span should be empty but not content (can insert and delete at the same time)
+---fails if buffer is read-only for current user
 function BufferController:send(change) end
 
+---@return boolean
+---check if changes sent to this buffer are refused, because of current user's role in the workspace
+function BufferController:is_read_only() end
+
 ---@return MaybeBufferUpdatePromise
 ---@async
 ---@nodiscard
@@ -413,10 +706,102 @@ function BufferController:callback(cb) end
 ---get current content of buffer controller, marking all pending changes as seen
 function BufferController:content() end
 
+---@param hash integer hash of current editor content
+---@return BooleanPromise
+---@async
+---@nodiscard
+---check if editor content matches what this buffer controller delivered so far
+function BufferController:verify(hash) end
+
+---@param content string current editor content
+---@return MaybeTextChangePromise
+---@async
+---@nodiscard
+---compute the text change bringing given editor content back in sync, nil if already in sync; apply it locally without sending it
+function BufferController:resync(content) end
+
 ---@param version [integer] version to ack
 ---notify controller that this version's change has been correctly applied
 function BufferController:ack(version) end
 
+---@class (exact) Annotation
+---@field id string unique identifier of this annotation
+---@field author string name of the user who created it, empty if they left the workspace
+---@field text string text of this annotation
+---@field start_idx integer index of first character covered, as the editor should currently display it
+---@field end_idx integer index after last character covered, as the editor should currently display it
+---@field resolved boolean whether this annotation was marked as resolved
+local Annotation = {}
+
+---@param start_idx integer index of first character to annotate
+---@param end_idx integer index after last character to annotate
+---@param text string text of the annotation
+---@return string
+---annotate a range of the buffer as currently displayed, returning the id of the new annotation; the range moves along as the buffer is edited
+function BufferController:annotate(start_idx, end_idx, text) end
+
+---@param id string identifier of annotation to resolve
+---mark an annotation as resolved
+function BufferController:resolve_annotation(id) end
+
+---@param id string identifier of annotation to delete
+---delete an annotation
+function BufferController:delete_annotation(id) end
+
+---@return AnnotationListPromise
+---@async
+---@nodiscard
+---get all annotations of this buffer, in order of creation, with ranges as the editor should currently display them
+function BufferController:annotations() end
+
+---clears any previously registered annotation callback
+function BufferController:clear_annotation_callback() end
+
+---@param cb fun(c: BufferController) callback to invoke whenever an annotation is created, resolved or deleted
+---register a new callback to be called on annotation changes (replaces any previously registered one)
+function BufferController:annotation_callback(cb) end
+
+---@alias LineEnding "lf" | "crlf" | "cr"
+
+---@class (exact) Indentation
+---@field tabs boolean whether lines are indented with tabs rather than spaces
+---@field width integer how many columns each indentation level spans
+
+---@class (exact) BufferMetadata
+---@field language string? identifier of the language of the buffer, such as "rust"
+---@field line_ending LineEnding? how lines of the buffer are terminated
+---@field indentation Indentation? how lines of the buffer are indented
+---@field encoding string? encoding of the buffer once saved to a file, such as "utf-8"
+---properties of a buffer which every editor should agree on, unset ones are left for each editor to guess
+local BufferMetadata = {}
+
+---@return BufferMetadata
+---latest metadata announced for this buffer
+function BufferController:metadata() end
+
+---clears any previously registered metadata callback
+function BufferController:clear_metadata_callback() end
+
+---@param cb fun(c: BufferController) callback to invoke whenever new metadata is announced
+---register a new callback to be called on metadata changes (replaces any previously registered one)
+function BufferController:metadata_callback(cb) end
+
+---@param enabled boolean whether to normalize line endings
+---replace line endings of sent changes with the ones announced for this buffer; the editor receives the replacement like any other change
+function BufferController:normalize_line_endings(enabled) end
+
+---@return boolean
+---whether line endings of sent changes are being normalized
+function BufferController:is_normalizing_line_endings() end
+
+---@param line_ending LineEnding? line ending of the editor, nil to stop translating
+---show every line break of the buffer with given line ending: content, received changes, hashes and indices refer to the buffer as the editor shows it, and line endings of sent changes are replaced with the canonical one; set it before fetching content, or resync afterwards
+function BufferController:translate_line_endings(line_ending) end
+
+---@return LineEnding?
+---line ending of the editor, if line endings are being translated
+function BufferController:local_line_ending() end
+
 
 
 
@@ -472,12 +857,160 @@ function CursorController:callback(cb) end
 
 
 
+---@class (exact) ChatController
+---handle to a workspace's chat, allowing send/recv operations
+local ChatController = {}
+
+---@class Draft
+---@field text string text of message
+---@field thread Selection? buffer range this message is about, if it belongs to a thread
+---a chat message about to be sent
+
+---@class Message
+---@field user string name of user who sent this message, empty if they left
+---@field text string text of message
+---@field timestamp integer when server received this message, in milliseconds since unix epoch
+---@field thread Selection? buffer range this message is about, if it belongs to a thread
+
+---@param draft Draft message to send to everyone in workspace
+---send a chat message to server
+function ChatController:send(draft) end
+
+---@return Message[]
+---last messages received, oldest first, including already consumed ones
+function ChatController:history() end
+
+---@param buffer string relative path ("name") of buffer
+---@return Message[]
+---last messages received in threads about given buffer, oldest first
+function ChatController:thread_history(buffer) end
+
+---@return MaybeMessagePromise
+---@async
+---@nodiscard
+---try to receive chat messages, returning nil if none is available
+function ChatController:try_recv() end
+
+---@return MessagePromise
+---@async
+---@nodiscard
+---block until next chat message and return it
+function ChatController:recv() end
+
+---@return NilPromise
+---@async
+---@nodiscard
+---block until next chat message without returning it
+function ChatController:poll() end
+
+---clears any previously registered chat callback
+function ChatController:clear_callback() end
+
+---@param cb fun(c: ChatController) callback to invoke on each chat message from server
+---register a new callback to be called on chat messages (replaces any previously registered one)
+function ChatController:callback(cb) end
+
+
+
+
+---@class (exact) DecorationController
+---handle to the decorations of a buffer, short-lived marks shared with other users, allowing send/recv operations
+local DecorationController = {}
+
+---@alias DecorationKind "highlight" | "mark" | "error" | "warning" | "info" | "hint"
+
+---@class Decoration
+---@field kind DecorationKind? what this decoration represents, default "highlight"
+---@field message string? message explaining this decoration, default empty
+---@field author string? name of user who sent this decoration, ignored when sending
+---@field start_idx integer? index of first character covered, as the editor should currently display it
+---@field end_idx integer? index after last character covered, as the editor should currently display it
+---@field ttl_ms integer? how long this decoration should be shown once received, in milliseconds, default 30000
+
+---@return string
+---get the relative path ("name") of decorated buffer
+function DecorationController:path() end
+
+---@param decoration Decoration decoration to send to everyone attached to the buffer
+---send a decoration to server; its range moves along as the buffer is edited
+function DecorationController:send(decoration) end
+
+---@return DecorationListPromise
+---@async
+---@nodiscard
+---get decorations received which didn't expire yet, oldest first, including already consumed ones
+function DecorationController:active() end
+
+---@return MaybeDecorationPromise
+---@async
+---@nodiscard
+---try to receive decorations, returning nil if none is available
+function DecorationController:try_recv() end
+
+---@return DecorationPromise
+---@async
+---@nodiscard
+---block until next decoration and return it
+function DecorationController:recv() end
+
+---@return NilPromise
+---@async
+---@nodiscard
+---block until next decoration without returning it
+function DecorationController:poll() end
+
+---clears any previously registered decoration callback
+function DecorationController:clear_callback() end
+
+---@param cb fun(c: DecorationController) callback to invoke on each decoration from server
+---register a new callback to be called on decorations (replaces any previously registered one)
+function DecorationController:callback(cb) end
+
+
+
+
 ---@class Config
 ---@field username string user identifier used to register, possibly your email
 ---@field password string user password chosen upon registration
----@field host string | nil address of server to connect to, default api.code.mp
+---@field token string | nil pre-issued session token to use instead of logging in
+---@field token_env string | nil environment variable holding a session token to use instead of logging in
+---@field token_command string | nil shell command printing a session token to use instead of logging in
+---@field endpoint string | nil full url of server, like https://code.example:50053/prefix, overridden by host, port and tls
+---@field host string | nil address of server to connect to, default api.code.mp (or unix:///path/to/socket)
 ---@field port integer | nil port to connect to, default 50053
----@field tls boolean | nil enable or disable tls, default true
+---@field tls boolean | nil enable or disable tls, default true (false for unix sockets)
+---@field tls_settings TlsSettings | nil certificates and verification settings for tls connections
+---@field proxy string | nil proxy to connect through, "http://host:port" or "socks5://host:port", default none
+---@field tuning Tuning | nil fine-tuning for internal workers and connections
+
+---@class TlsSettings
+---@field ca_bundle string | nil path to a PEM bundle of additional certificate authorities to trust
+---@field native_roots boolean | nil also trust the system certificate authorities, default true
+---@field client_cert string | nil path to a PEM client certificate chain to present, requires client_key
+---@field client_key string | nil path to the PEM private key of client_cert
+---@field domain string | nil name to verify the server certificate against, default host
+---@field pinned_sha256 string | nil SHA-256 fingerprint the server certificate must match, as hex digits, on top of chaining to a trusted authority
+
+---@alias CallbackStrategy "inline" | "task" | "drop" | "coalesce"
+
+---@class Tuning
+---@field buffer_queue integer | nil capacity of each buffer outgoing operations queue, default 256
+---@field cursor_queue integer | nil capacity of the outgoing cursor movements queue, default 128
+---@field hash_period integer | nil attach a content hash every this many buffer updates, default 10 (0 disables)
+---@field worker_tick_ms integer | nil how often idle workspace workers wake up to update presence and check if they should stop, default 5000
+---@field connect_timeout_ms integer | nil give up establishing a connection after this long, default never
+---@field request_timeout_ms integer | nil give up waiting for a response after this long, default never
+---@field http2_keepalive_ms integer | nil send http2 pings this often to keep connections alive, default never
+---@field http2_keepalive_timeout_ms integer | nil consider a connection dead if a ping is unanswered this long, default 20000
+---@field tcp_keepalive_ms integer | nil send tcp keepalive probes after idling this long, default never
+---@field connect_retries integer | nil how many times to retry a failed connection attempt, default 0
+---@field retry_backoff_ms integer | nil delay before first retry of a failed connection or token renewal, doubled each attempt, default 500
+---@field refresh_margin_ms integer | nil renew access tokens this long before they expire, default 60000
+---@field callback_strategy CallbackStrategy | nil run callbacks "inline" on the network worker (default), in order on a separate "task", or on a task which will "drop" or "coalesce" invocations when busy
+---@field callback_queue integer | nil how many invocations may be pending with the "drop" strategy, default 64
+---@field idle_after_ms integer | nil consider users idle after not moving their cursor this long, default 60000
+---@field away_after_ms integer | nil consider users away after not moving their cursor this long, default 600000
+---@field chat_history integer | nil how many chat messages to keep in local history, default 256
 
 ---@class Codemp
 ---the codemp shared library
@@ -490,6 +1023,12 @@ local Codemp = {}
 ---connect to codemp server, authenticate and return client
 function Codemp.connect(config) end
 
+---@param path? string configuration file to read, default looks in standard locations
+---@param explicit? Config (possibly partial) configuration values taking precedence
+---@return Config
+---load configuration from file and CODEMP_* environment variables, merging explicit values on top
+function Codemp.load_config(path, explicit) end
+
 ---@return function, any | nil
 ---@nodiscard
 ---check if codemp thread sent a callback to be run on main thread
@@ -504,6 +1043,27 @@ function Codemp.hash(data) end
 ---get current library version as string, in semver format
 function Codemp.version() end
 
+---@alias Theme "light" | "dark"
+
+---@param id string uuid of user
+---@param theme Theme? background color will be drawn on, nil for a neutral one
+---@return string
+---get color assigned to given user as "#rrggbb", the same on every client
+function Codemp.user_color(id, theme) end
+
+---@alias ErrorKind "not_found" | "unauthorized" | "forbidden" | "already_exists" | "unavailable" | "invalid_argument" | "timed_out" | "rate_limited" | "conflict" | "closed" | "other"
+
+---@class (exact) CodempError
+---@field kind ErrorKind category of this error
+---@field message string description of what went wrong
+---@field retryable boolean whether the operation which failed may succeed if tried again later
+---error raised by codemp, also passed to failed promise callbacks instead of their message
+
+---@param err any error caught from a codemp call, for example with pcall
+---@return CodempError | nil
+---get the codemp error carried by a raised error, nil if it doesn't come from codemp
+function Codemp.error(err) end
+
 ---@class (exact) RuntimeDriver
 local RuntimeDriver = {}
 
@@ -1,5 +1,26 @@
 from typing import Tuple, Optional, Callable
 
+class CodempError(Exception):
+	"""
+	Base class of every error raised by codemp
+	"""
+
+class RetryableError(CodempError):
+	"""
+	Temporary failure: trying the same operation again later may succeed
+	"""
+
+class NotFoundError(CodempError): ...
+class UnauthorizedError(CodempError): ...
+class ForbiddenError(CodempError): ...
+class AlreadyExistsError(CodempError): ...
+class UnavailableError(RetryableError): ...
+class InvalidArgumentError(CodempError): ...
+class TimedOutError(RetryableError): ...
+class RateLimitedError(RetryableError): ...
+class ConflictError(CodempError): ...
+class ClosedError(CodempError): ...
+
 class Driver:
 	"""
 	this is akin to a big red button with a white "STOP" on top of it.
@@ -7,12 +28,56 @@ class Driver:
 	"""
 	def stop(self) -> None: ...
 
+class Theme:
+	"""
+	Background a color is going to be drawn on
+	"""
+	Light: Theme
+	Dark: Theme
+
 class User:
 	"""
 	A remote user, with uuid and username
 	"""
 	id: str
 	name: str
+	def color(self, theme: Optional[Theme] = None) -> str: ...
+
+class Role:
+	"""
+	What a member may do in a workspace
+	"""
+	ReadWrite: Role
+	ReadOnly: Role
+	Owner: Role
+
+class Member:
+	"""
+	A user allowed into a workspace, with their role
+	"""
+	user: User
+	role: Role
+	pending: bool
+
+class Status:
+	"""
+	How recently a user was seen doing something in a workspace
+	"""
+	Active: Status
+	Idle: Status
+	Away: Status
+
+class Presence:
+	"""
+	What a user in a workspace is up to: current buffer, status and profile
+	"""
+	user: User
+	display_name: Optional[str]
+	avatar: Optional[str]
+	color: str
+	status: Status
+	buffer: Optional[str]
+	def label(self) -> str: ...
 
 class Config:
 	"""
@@ -20,11 +85,66 @@ class Config:
 	"""
 	username: str
 	password: str
+	token: Optional[str]
+	token_env: Optional[str]
+	token_command: Optional[str]
+	endpoint: Optional[str]
 	host: Optional[str]
 	port: Optional[int]
 	tls: Optional[bool]
+	tls_settings: Optional[TlsSettings]
+	proxy: Optional[str]
+	tuning: Optional[Tuning]
 
 	def __new__(cls, *, username: str, password: str, **kwargs) -> Config: ...
+	@staticmethod
+	def load(path: Optional[str] = None, **kwargs) -> Config: ...
+
+class TlsSettings:
+	"""
+	Settings for TLS connections: trusted authorities, client certificates and pinning
+	"""
+	ca_bundle: Optional[str]
+	native_roots: Optional[bool]
+	client_cert: Optional[str]
+	client_key: Optional[str]
+	domain: Optional[str]
+	pinned_sha256: Optional[str]
+
+	def __new__(cls, **kwargs) -> TlsSettings: ...
+
+class CallbackStrategy:
+	"""
+	How registered callbacks are run
+	"""
+	Inline: CallbackStrategy
+	Task: CallbackStrategy
+	Drop: CallbackStrategy
+	Coalesce: CallbackStrategy
+
+class Tuning:
+	"""
+	Fine-tuning parameters for internal workers and connections, durations in milliseconds
+	"""
+	buffer_queue: Optional[int]
+	cursor_queue: Optional[int]
+	hash_period: Optional[int]
+	worker_tick_ms: Optional[int]
+	connect_timeout_ms: Optional[int]
+	request_timeout_ms: Optional[int]
+	http2_keepalive_ms: Optional[int]
+	http2_keepalive_timeout_ms: Optional[int]
+	tcp_keepalive_ms: Optional[int]
+	connect_retries: Optional[int]
+	retry_backoff_ms: Optional[int]
+	refresh_margin_ms: Optional[int]
+	callback_strategy: Optional[CallbackStrategy]
+	callback_queue: Optional[int]
+	idle_after_ms: Optional[int]
+	away_after_ms: Optional[int]
+	chat_history: Optional[int]
+
+	def __new__(cls, **kwargs) -> Tuning: ...
 
 def init() -> Driver: ...
 def set_logger(logger_cb: Callable[[str], None], debug: bool) -> bool: ...
@@ -51,6 +171,10 @@ class Client:
 	def create_workspace(self, workspace: str)  -> Promise[None]: ...
 	def delete_workspace(self, workspace: str)  -> Promise[None]: ...
 	def invite_to_workspace(self, workspace: str, username: str) -> Promise[None]: ...
+	def fetch_workspace_members(self, workspace: str) -> Promise[list[Member]]: ...
+	def remove_from_workspace(self, workspace: str, username: str) -> Promise[None]: ...
+	def set_workspace_role(self, workspace: str, username: str, role: Role) -> Promise[None]: ...
+	def set_profile(self, display_name: Optional[str] = None, avatar: Optional[str] = None) -> Promise[None]: ...
 	def fetch_owned_workspaces(self)            -> Promise[list[str]]: ...
 	def fetch_joined_workspaces(self)           -> Promise[list[str]]: ...
 	def leave_workspace(self, workspace: str)   -> bool: ...
@@ -69,15 +193,29 @@ class Workspace:
 	"""
 	def create_buffer(self, path: str)          -> Promise[None]: ...
 	def attach_buffer(self, path: str)          -> Promise[BufferController]: ...
+	def attach_buffer_read_only(self, path: str) -> Promise[BufferController]: ...
+	def attach_chat(self)                       -> Promise[ChatController]: ...
+	def attach_decorations(self, path: str)     -> Promise[DecorationController]: ...
 	def detach_buffer(self, path: str)          -> bool: ...
 	def fetch_buffers(self)                     -> Promise[list[str]]: ...
 	def fetch_users(self)                       -> Promise[list[User]]: ...
 	def fetch_buffer_users(self, path: str)     -> Promise[list[User]]: ...
+	def fetch_members(self)                     -> Promise[list[Member]]: ...
+	def fetch_role(self)                        -> Promise[Role]: ...
+	def fetch_presence(self)                    -> Promise[list[Presence]]: ...
+	def fetch_buffer_metadata(self, path: str)  -> Promise[BufferMetadata]: ...
+	def set_buffer_metadata(self, path: str,
+		metadata: BufferMetadata)               -> Promise[None]: ...
 	def delete_buffer(self, path: str)          -> Promise[None]: ...
 	def id(self)                                -> str: ...
 	def cursor(self)                            -> CursorController: ...
 	def get_buffer(self, path: str)             -> Optional[BufferController]: ...
+	def get_chat(self)                          -> Optional[ChatController]: ...
+	def get_decorations(self, path: str)        -> Optional[DecorationController]: ...
 	def user_list(self)                         -> list[User]: ...
+	def role(self)                              -> Role: ...
+	def presence(self)                          -> list[Presence]: ...
+	def user_presence(self, name: str)          -> Optional[Presence]: ...
 	def active_buffers(self)                    -> list[str]: ...
 	def search_buffers(self, filter: Optional[str]) -> list[str]: ...
 	def recv(self)                              -> Promise[Event]: ...
@@ -115,8 +253,11 @@ class BufferController:
 	of operations to and from other peers.
 	"""
 	def path(self)                              -> str: ...
+	def is_read_only(self)                      -> bool: ...
 	def content(self)                           -> Promise[str]: ...
 	def ack(self, v: list[int])                 -> None: ...
+	def verify(self, hash: int)                 -> Promise[bool]: ...
+	def resync(self, content: str)              -> Promise[Optional[TextChange]]: ...
 	def send(self, op: TextChange)              -> None: ...
 	def try_recv(self)                          -> Promise[Optional[TextChange]]: ...
 	def recv(self)                              -> Promise[TextChange]: ...
@@ -124,6 +265,104 @@ class BufferController:
 	def callback(self,
 		cb: Callable[[BufferController], None]) -> None: ...
 	def clear_callback(self)                    -> None: ...
+	def annotate(self, start_idx: int, end_idx: int,
+		text: str)                              -> str: ...
+	def resolve_annotation(self, id: str)       -> None: ...
+	def delete_annotation(self, id: str)        -> None: ...
+	def annotations(self)                       -> Promise[list[Annotation]]: ...
+	def annotation_callback(self,
+		cb: Callable[[BufferController], None]) -> None: ...
+	def clear_annotation_callback(self)         -> None: ...
+	def metadata(self)                          -> BufferMetadata: ...
+	def metadata_callback(self,
+		cb: Callable[[BufferController], None]) -> None: ...
+	def clear_metadata_callback(self)           -> None: ...
+	def normalize_line_endings(self, enabled: bool) -> None: ...
+	def is_normalizing_line_endings(self)       -> bool: ...
+	def translate_line_endings(self, local: Optional[LineEnding] = None) -> None: ...
+	def local_line_ending(self)                 -> Optional[LineEnding]: ...
+
+class Annotation:
+	"""
+	A review comment about a range of a buffer, which moves along as the buffer is edited
+	"""
+	id: str
+	author: str
+	text: str
+	start_idx: int
+	end_idx: int
+	resolved: bool
+
+class LineEnding:
+	"""
+	How lines of a buffer are terminated
+	"""
+	Lf: LineEnding
+	Crlf: LineEnding
+	Cr: LineEnding
+
+class Indentation:
+	"""
+	How lines of a buffer are indented
+	"""
+	tabs: bool
+	width: int
+
+	def __new__(cls, tabs: bool, width: int) -> Indentation: ...
+
+class BufferMetadata:
+	"""
+	Properties of a buffer which every editor should agree on, unset if left to guess
+	"""
+	language: Optional[str]
+	line_ending: Optional[LineEnding]
+	indentation: Optional[Indentation]
+	encoding: Optional[str]
+
+	def __new__(cls, language: Optional[str] = None,
+		line_ending: Optional[LineEnding] = None,
+		indentation: Optional[Indentation] = None,
+		encoding: Optional[str] = None) -> BufferMetadata: ...
+
+class DecorationKind:
+	"""
+	What a decoration represents
+	"""
+	Highlight: DecorationKind
+	Mark: DecorationKind
+	Error: DecorationKind
+	Warning: DecorationKind
+	Info: DecorationKind
+	Hint: DecorationKind
+
+class Decoration:
+	"""
+	A short-lived mark on a range of a buffer, shared with other peers
+	"""
+	kind: DecorationKind
+	message: str
+	author: str
+	start_idx: int
+	end_idx: int
+	ttl_ms: int
+
+	def __new__(cls, kind: DecorationKind, start_idx: int, end_idx: int,
+		message: str = "", ttl_ms: int = 30000) -> Decoration: ...
+
+class DecorationController:
+	"""
+	Handle to the decorations of a buffer, which manages the back and forth of
+	decorations to and from other peers
+	"""
+	def path(self)                              -> str: ...
+	def send(self, decoration: Decoration)      -> None: ...
+	def active(self)                            -> Promise[list[Decoration]]: ...
+	def try_recv(self)                          -> Promise[Optional[Decoration]]: ...
+	def recv(self)                              -> Promise[Decoration]: ...
+	def poll(self)                              -> Promise[None]: ...
+	def callback(self,
+		cb: Callable[[DecorationController], None]) -> None: ...
+	def clear_callback(self)                    -> None: ...
 
 
 
@@ -156,3 +395,36 @@ class CursorController:
 		cb: Callable[[CursorController], None]) -> None: ...
 	def clear_callback(self)                    -> None: ...
 
+
+class Draft:
+	"""
+	A chat message about to be sent, optionally in a thread about a buffer range
+	"""
+	text: str
+	thread: Optional[Selection]
+
+	def __new__(cls, text: str, thread: Optional[Selection] = None) -> Draft: ...
+
+class Message:
+	"""
+	A chat message relayed by the server
+	"""
+	user: str
+	text: str
+	timestamp: int
+	thread: Optional[Selection]
+
+class ChatController:
+	"""
+	Handle to the chat of a workspace, which manages the back and forth of
+	messages to and from other peers
+	"""
+	def send(self, draft: Draft)                -> None: ...
+	def history(self)                           -> list[Message]: ...
+	def thread_history(self, buffer: str)       -> list[Message]: ...
+	def try_recv(self)                          -> Promise[Optional[Message]]: ...
+	def recv(self)                              -> Promise[Message]: ...
+	def poll(self)                              -> Promise[None]: ...
+	def callback(self,
+		cb: Callable[[ChatController], None])   -> None: ...
+	def clear_callback(self)                    -> None: ...
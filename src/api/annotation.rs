@@ -0,0 +1,27 @@
+//! # Annotation
+//! Review comments attached to a range of a buffer, see [`crate::buffer::Controller::annotate`].
+//!
+//! Annotations are anchored to the CRDT of their buffer rather than to fixed indices: as the
+//! buffer is edited, locally or remotely, their range moves to keep covering the same text.
+
+/// A comment about a range of a buffer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "js", napi_derive::napi(object))]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(get_all))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+	/// Unique identifier of the annotation, a UUID.
+	pub id: String,
+	/// Name of the user who created the annotation, empty if they are not in the workspace anymore.
+	pub author: String,
+	/// Text of the annotation.
+	pub text: String,
+	/// Index of the first character covered, in the buffer as currently shown to the editor.
+	pub start_idx: u32,
+	/// Index after the last character covered, in the buffer as currently shown to the editor.
+	///
+	/// Equal to [`Annotation::start_idx`] if the text covered was entirely deleted.
+	pub end_idx: u32,
+	/// Whether the annotation was marked as resolved.
+	pub resolved: bool,
+}
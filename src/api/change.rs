@@ -67,6 +67,47 @@ impl TextChange {
 	pub fn span(&self) -> std::ops::Range<usize> {
 		self.start_idx as usize..self.end_idx as usize
 	}
+
+	/// Compute the smallest [`TextChange`] transforming `before` into `after`, if they differ.
+	///
+	/// The resulting change replaces everything between the common prefix and the common suffix
+	/// of the two strings. Indexes are expressed in characters, like every other [`TextChange`].
+	///
+	/// ```
+	/// let change = codemp::api::TextChange::diff("hello world!", "hello mom!").unwrap();
+	/// assert_eq!(change.span(), 6..11);
+	/// assert_eq!(change.content, "mom");
+	/// ```
+	pub fn diff(before: &str, after: &str) -> Option<Self> {
+		if before == after {
+			return None;
+		}
+
+		let before_len = before.chars().count();
+		let after_len = after.chars().count();
+		let prefix = before
+			.chars()
+			.zip(after.chars())
+			.take_while(|(a, b)| a == b)
+			.count();
+		let suffix = before
+			.chars()
+			.rev()
+			.zip(after.chars().rev())
+			.take(std::cmp::min(before_len, after_len) - prefix)
+			.take_while(|(a, b)| a == b)
+			.count();
+
+		Some(Self {
+			start_idx: prefix as u32,
+			end_idx: (before_len - suffix) as u32,
+			content: after
+				.chars()
+				.skip(prefix)
+				.take(after_len - suffix - prefix)
+				.collect(),
+		})
+	}
 }
 
 #[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pymethods)]
@@ -158,4 +199,25 @@ mod tests {
 		let result = change.apply("some important text");
 		assert_eq!(result, "some important text");
 	}
+
+	#[test]
+	fn textchange_diff_of_equal_strings_is_none() {
+		assert!(super::TextChange::diff("same text", "same text").is_none());
+	}
+
+	#[test]
+	fn textchange_diff_roundtrips_through_apply() {
+		let before = "hello cruel world!";
+		let after = "hello not very pleasant world!";
+		let change = super::TextChange::diff(before, after).expect("strings differ");
+		assert_eq!(change.span(), 6..11);
+		assert_eq!(change.apply(before), after);
+	}
+
+	#[test]
+	fn textchange_diff_handles_repeated_characters() {
+		let change = super::TextChange::diff("aaaa", "aa").expect("strings differ");
+		assert_eq!(change.span(), 2..4);
+		assert!(!change.is_insert());
+	}
 }
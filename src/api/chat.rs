@@ -0,0 +1,61 @@
+//! # Chat
+//! Short text messages exchanged by users of a workspace, see [`crate::chat::Controller`].
+//!
+//! Messages may optionally belong to a thread about a range of a buffer, identified by its
+//! [`Selection`].
+
+use super::Selection;
+
+/// A chat message about to be sent.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+	any(feature = "py", feature = "py-noabi"),
+	pyo3::pyclass(get_all, set_all)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Draft {
+	/// Text of the message.
+	pub text: String,
+	/// Range of a buffer this message is about, if it belongs to a thread.
+	pub thread: Option<Selection>,
+}
+
+impl Draft {
+	/// A message for the whole workspace.
+	pub fn new(text: impl ToString) -> Self {
+		Self {
+			text: text.to_string(),
+			thread: None,
+		}
+	}
+
+	/// A message in the thread about given range of a buffer.
+	pub fn in_thread(text: impl ToString, thread: Selection) -> Self {
+		Self {
+			text: text.to_string(),
+			thread: Some(thread),
+		}
+	}
+}
+
+/// A chat message as relayed by the server, including those sent by the current user.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(get_all))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message {
+	/// Name of the user who sent the message, empty if they are not in the workspace anymore.
+	pub user: String,
+	/// Text of the message.
+	pub text: String,
+	/// When the server received the message, in milliseconds since the UNIX epoch.
+	pub timestamp: u64,
+	/// Range of a buffer this message is about, if it belongs to a thread.
+	pub thread: Option<Selection>,
+}
+
+impl Message {
+	/// Whether this message belongs to a thread about given buffer.
+	pub fn is_about(&self, buffer: &str) -> bool {
+		self.thread.as_ref().is_some_and(|sel| sel.buffer == buffer)
+	}
+}
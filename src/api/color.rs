@@ -0,0 +1,136 @@
+//! # Color
+//! Stable colors for users, so that every client shows the same teammate in the same color.
+//!
+//! Colors are picked from a fixed palette of readable hues, from the UUID of each user: the
+//! index is the XOR of the two halves of the UUID (as big endian `u64`s), modulo the palette
+//! length. Each hue comes in three shades: a base one, with a contrast ratio of at least 3:1 on
+//! both black and white, and variants reaching 4.5:1 on light or dark backgrounds, see [`Theme`].
+
+use uuid::Uuid;
+
+/// Background a color is going to be drawn on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(eq, eq_int))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum Theme {
+	/// A light background: colors are darkened.
+	Light,
+	/// A dark background: colors are brightened.
+	Dark,
+}
+
+impl Theme {
+	/// Stable `snake_case` name of this theme, as exposed to bindings.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Light => "light",
+			Self::Dark => "dark",
+		}
+	}
+}
+
+impl std::fmt::Display for Theme {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl std::str::FromStr for Theme {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"light" => Ok(Self::Light),
+			"dark" => Ok(Self::Dark),
+			_ => Err(format!("unknown theme '{s}'")),
+		}
+	}
+}
+
+/// Base, light background and dark background shades of each hue, as `#rrggbb`.
+///
+/// Yellows are left out, as they are hard to read on light backgrounds whatever the shade.
+const PALETTE: [[&str; 3]; 16] = [
+	["#ef4444", "#b91c1c", "#fca5a5"], // red
+	["#ea580c", "#c2410c", "#fdba74"], // orange
+	["#d97706", "#b45309", "#fcd34d"], // amber
+	["#65a30d", "#4d7c0f", "#bef264"], // lime
+	["#16a34a", "#15803d", "#86efac"], // green
+	["#059669", "#047857", "#6ee7b7"], // emerald
+	["#0d9488", "#0f766e", "#5eead4"], // teal
+	["#0891b2", "#0e7490", "#67e8f9"], // cyan
+	["#0284c7", "#0369a1", "#7dd3fc"], // sky
+	["#3b82f6", "#1d4ed8", "#93c5fd"], // blue
+	["#6366f1", "#4338ca", "#a5b4fc"], // indigo
+	["#8b5cf6", "#6d28d9", "#c4b5fd"], // violet
+	["#a855f7", "#7e22ce", "#d8b4fe"], // purple
+	["#d946ef", "#a21caf", "#f0abfc"], // fuchsia
+	["#ec4899", "#be185d", "#f9a8d4"], // pink
+	["#f43f5e", "#be123c", "#fda4af"], // rose
+];
+
+/// Color assigned to the user with given id, as `#rrggbb`, optionally adapted to a [`Theme`].
+///
+/// The same id always gets the same color, on every client.
+pub fn user_color(id: Uuid, theme: Option<Theme>) -> &'static str {
+	let (hi, lo) = id.as_u64_pair();
+	let shades = PALETTE[((hi ^ lo) % PALETTE.len() as u64) as usize];
+	match theme {
+		None => shades[0],
+		Some(Theme::Light) => shades[1],
+		Some(Theme::Dark) => shades[2],
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{user_color, Theme, PALETTE};
+
+	#[test]
+	fn colors_are_stable_and_themed() {
+		let id = uuid::Uuid::from_u64_pair(0xdead_beef, 0xcafe);
+		let index = ((0xdead_beef_u64 ^ 0xcafe) % 16) as usize;
+		assert_eq!(user_color(id, None), PALETTE[index][0]);
+		assert_eq!(user_color(id, Some(Theme::Light)), PALETTE[index][1]);
+		assert_eq!(user_color(id, Some(Theme::Dark)), PALETTE[index][2]);
+		assert_eq!(user_color(id, None), user_color(id, None));
+	}
+
+	/// WCAG contrast ratio between given color and black or white.
+	fn contrast(color: &str, white: bool) -> f64 {
+		let channel = |i: usize| {
+			let c = u8::from_str_radix(&color[i..i + 2], 16).unwrap() as f64 / 255.0;
+			if c <= 0.03928 {
+				c / 12.92
+			} else {
+				((c + 0.055) / 1.055).powf(2.4)
+			}
+		};
+		let luminance = 0.2126 * channel(1) + 0.7152 * channel(3) + 0.0722 * channel(5);
+		if white {
+			1.05 / (luminance + 0.05)
+		} else {
+			(luminance + 0.05) / 0.05
+		}
+	}
+
+	#[test]
+	fn shades_are_readable() {
+		for [base, light, dark] in PALETTE {
+			assert!(contrast(base, true) >= 3.0, "{base} on white");
+			assert!(contrast(base, false) >= 3.0, "{base} on black");
+			assert!(contrast(light, true) >= 4.5, "{light} on white");
+			assert!(contrast(dark, false) >= 4.5, "{dark} on black");
+		}
+	}
+
+	#[test]
+	fn palette_is_made_of_hex_triplets() {
+		for color in PALETTE.iter().flatten() {
+			assert_eq!(color.len(), 7);
+			assert!(color.starts_with('#'));
+			assert!(color[1..].chars().all(|c| c.is_ascii_hexdigit()));
+		}
+	}
+}
@@ -5,9 +5,15 @@
 ///
 /// `username` and `password` are required fields, everything else is optional.
 ///
-/// `host`, `port` and `tls` affect all connections to all gRPC services; the
+/// Instead of logging in with `username` and `password`, a pre-issued session token may be given
+/// directly with `token`, or obtained from an environment variable with `token_env`, or printed by
+/// a shell command with `token_command`. See [`crate::auth`] for the precedence of these. Such
+/// tokens must be JWT-shaped, carrying the UUID of their user as `sub` claim.
+///
+/// The server may be given as a full URL with `endpoint` (see [`Config::from_url`]), while `host`,
+/// `port` and `tls` override its parts. They affect all connections to all gRPC services; the
 /// resulting endpoint is composed like this:
-///     http{tls?'s':''}://{host}:{port}
+///     http{tls?'s':''}://{host}:{port}{path of endpoint, if any}
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "js", napi_derive::napi(object))]
 #[cfg_attr(
@@ -15,17 +21,134 @@
 	pyo3::pyclass(get_all, set_all)
 )]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
 pub struct Config {
 	/// User identifier used to register, possibly your email.
 	pub username: String,
 	/// User password chosen upon registration.
 	pub password: String,
+	/// Pre-issued session token to use instead of logging in.
+	pub token: Option<String>,
+	/// Environment variable holding a session token to use instead of logging in.
+	pub token_env: Option<String>,
+	/// Shell command printing a session token to use instead of logging in.
+	pub token_command: Option<String>,
+	/// Full URL of the server, like `https://code.example:50053/prefix`, `http://[::1]:50053` or
+	/// `unix:///path/to/socket`, default none. Port defaults to 50053 when missing.
+	pub endpoint: Option<String>,
 	/// Address of server to connect to, default api.code.mp.
+	///
+	/// May also be `unix:///path/to/socket` to connect to a Unix domain socket instead, in which
+	/// case `port` and `proxy` are ignored and `tls` defaults to false.
 	pub host: Option<String>,
 	/// Port to connect to, default 50053.
 	pub port: Option<u16>,
-	/// Enable or disable tls, default true.
+	/// Enable or disable tls, default true (false for Unix sockets and custom transports).
 	pub tls: Option<bool>,
+	/// Certificates and verification settings used when `tls` is enabled, see [`TlsSettings`].
+	pub tls_settings: Option<TlsSettings>,
+	/// Proxy to connect through, either `http://host:port` (using CONNECT) or
+	/// `socks5://host:port`, optionally with `user:password@` credentials, default none.
+	pub proxy: Option<String>,
+	/// Fine-tuning for internal workers and connections, see [`Tuning`].
+	pub tuning: Option<Tuning>,
+}
+
+/// Settings for TLS connections: trusted authorities, client certificates and pinning.
+///
+/// Every field is optional: by default the server certificate is verified against the system
+/// trust store, and no client certificate is presented. All certificates and keys are read from
+/// PEM files upon connecting.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "js", napi_derive::napi(object))]
+#[cfg_attr(
+	any(feature = "py", feature = "py-noabi"),
+	pyo3::pyclass(get_all, set_all)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TlsSettings {
+	/// Path to a bundle of additional certificate authorities to trust, e.g. a private CA.
+	pub ca_bundle: Option<String>,
+	/// Also trust the system certificate authorities, default true.
+	pub native_roots: Option<bool>,
+	/// Path to a client certificate chain to present to the server, requires `client_key`.
+	pub client_cert: Option<String>,
+	/// Path to the private key of `client_cert`.
+	pub client_key: Option<String>,
+	/// Name to verify the server certificate against, default `host`.
+	pub domain: Option<String>,
+	/// Only accept a server certificate with this SHA-256 fingerprint, as hex digits. The
+	/// certificate must still chain to a trusted authority, see `ca_bundle` and `native_roots`.
+	pub pinned_sha256: Option<String>,
+}
+
+/// Fine-tuning parameters for internal workers and connections.
+///
+/// Every field is optional: unset values fall back to defaults which should be sensible for
+/// most editors. All durations are expressed in milliseconds.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "js", napi_derive::napi(object))]
+#[cfg_attr(
+	any(feature = "py", feature = "py-noabi"),
+	pyo3::pyclass(get_all, set_all)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tuning {
+	/// Capacity of the outgoing operations queue of each buffer, default 256.
+	pub buffer_queue: Option<u32>,
+	/// Capacity of the outgoing cursor movements queue, default 128.
+	pub cursor_queue: Option<u32>,
+	/// Attach a content hash to every Nth buffer update, default 10. Zero disables hashes.
+	pub hash_period: Option<u32>,
+	/// How often workspace workers wake up when idle, to update the presence of users and check
+	/// if they should stop, default 5000.
+	pub worker_tick_ms: Option<u32>,
+	/// Give up establishing a connection after this long, default never.
+	pub connect_timeout_ms: Option<u32>,
+	/// Give up waiting for a response to a request after this long, default never.
+	pub request_timeout_ms: Option<u32>,
+	/// Send HTTP/2 pings this often to keep connections alive, even when idle, default never.
+	pub http2_keepalive_ms: Option<u32>,
+	/// Consider a connection dead if a ping is not answered within this long, default 20000.
+	pub http2_keepalive_timeout_ms: Option<u32>,
+	/// Enable TCP keepalive probes after the connection was idle this long, default never.
+	pub tcp_keepalive_ms: Option<u32>,
+	/// How many times to retry a failed connection attempt, default 0.
+	pub connect_retries: Option<u32>,
+	/// Delay before the first retry of a failed connection attempt or token renewal, doubled on
+	/// each attempt, default 500. Token renewals are retried at most every minute.
+	pub retry_backoff_ms: Option<u32>,
+	/// Renew access tokens this long before they expire, default 60000.
+	pub refresh_margin_ms: Option<u32>,
+	/// How registered callbacks are run, default [`CallbackStrategy::Inline`].
+	pub callback_strategy: Option<CallbackStrategy>,
+	/// How many invocations may be pending with [`CallbackStrategy::Drop`], default 64.
+	pub callback_queue: Option<u32>,
+	/// Consider users idle after they didn't move their cursor for this long, default 60000.
+	pub idle_after_ms: Option<u32>,
+	/// Consider users away after they didn't move their cursor for this long, default 600000.
+	pub away_after_ms: Option<u32>,
+	/// How many chat messages to keep in the local history, default 256.
+	pub chat_history: Option<u32>,
+}
+
+/// How registered callbacks are run, see [`Tuning::callback_strategy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "js", napi_derive::napi(string_enum = "lowercase"))]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(eq, eq_int))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum CallbackStrategy {
+	/// Directly on the network worker, which waits for them to return.
+	#[default]
+	Inline,
+	/// In order on a dedicated task, without ever skipping an invocation.
+	Task,
+	/// Like [`CallbackStrategy::Task`], but skipping invocations while
+	/// [`Tuning::callback_queue`] are pending.
+	Drop,
+	/// Like [`CallbackStrategy::Task`], but skipping invocations while one is already pending.
+	Coalesce,
 }
 
 impl Config {
@@ -34,33 +157,471 @@ impl Config {
 		Self {
 			username: username.to_string(),
 			password: password.to_string(),
+			token: None,
+			token_env: None,
+			token_command: None,
+			endpoint: None,
 			host: None,
 			port: None,
 			tls: None,
+			tls_settings: None,
+			proxy: None,
+			tuning: None,
 		}
 	}
 
+	/// Construct a new Config object connecting to given full URL, with given username and
+	/// password, see `endpoint`.
+	#[allow(clippy::result_large_err)] // same error connecting with it would give
+	pub fn from_url(
+		url: impl ToString,
+		username: impl ToString,
+		password: impl ToString,
+	) -> crate::errors::ConnectionResult<Self> {
+		let url = url.to_string();
+		Url::parse(&url).map_err(crate::errors::ConnectionError::InvalidConfig)?;
+		Ok(Self {
+			endpoint: Some(url),
+			..Self::new(username, password)
+		})
+	}
+
+	#[inline]
+	fn url(&self) -> Option<Url<'_>> {
+		Url::parse(self.endpoint.as_deref()?).ok()
+	}
+
 	#[inline]
 	pub(crate) fn host(&self) -> &str {
-		self.host.as_deref().unwrap_or("api.code.mp")
+		match (&self.host, self.url()) {
+			(Some(host), _) => host.trim_start_matches('[').trim_end_matches(']'),
+			(None, Some(Url::Tcp { host, .. })) => host,
+			(None, _) => "api.code.mp",
+		}
 	}
 
 	#[inline]
 	pub(crate) fn port(&self) -> u16 {
-		self.port.unwrap_or(50053)
+		match (self.port, self.url()) {
+			(Some(port), _) => port,
+			(
+				None,
+				Some(Url::Tcp {
+					port: Some(port), ..
+				}),
+			) => port,
+			(None, _) => 50053,
+		}
 	}
 
 	#[inline]
 	pub(crate) fn tls(&self) -> bool {
-		self.tls.unwrap_or(true)
+		match (self.tls, self.url()) {
+			(Some(tls), _) => tls,
+			(None, Some(Url::Tcp { tls, .. })) if self.unix_socket().is_none() => tls,
+			(None, _) => self.unix_socket().is_none(),
+		}
+	}
+
+	/// Path prefix every request should be sent under, empty unless given with `endpoint`.
+	#[inline]
+	pub(crate) fn path_prefix(&self) -> &str {
+		match self.url() {
+			Some(Url::Tcp { prefix, .. }) if self.unix_socket().is_none() => prefix,
+			_ => "",
+		}
+	}
+
+	/// Path of the Unix domain socket to connect to, if `host` or `endpoint` points to one.
+	#[inline]
+	pub(crate) fn unix_socket(&self) -> Option<&str> {
+		match (&self.host, self.url()) {
+			(Some(host), _) => host.strip_prefix("unix://"),
+			(None, Some(Url::Unix(path))) => Some(path),
+			(None, _) => None,
+		}
+	}
+
+	#[inline]
+	pub(crate) fn tls_settings(&self) -> TlsSettings {
+		self.tls_settings.clone().unwrap_or_default()
+	}
+
+	#[inline]
+	pub(crate) fn tuning(&self) -> Tuning {
+		self.tuning.clone().unwrap_or_default()
+	}
+
+	/// Check that this configuration can be used to connect, describing the first problem found.
+	pub(crate) fn validate(&self) -> Result<(), String> {
+		if let Some(endpoint) = &self.endpoint {
+			Url::parse(endpoint)?;
+		}
+		if self.host().is_empty() {
+			return Err("host can't be empty".into());
+		}
+		let credentials = [
+			("token", &self.token),
+			("token_env", &self.token_env),
+			("token_command", &self.token_command),
+		];
+		for (name, value) in credentials {
+			if value.as_deref().is_some_and(|x| x.trim().is_empty()) {
+				return Err(format!("{name} can't be empty"));
+			}
+		}
+		if self.unix_socket().is_some_and(str::is_empty) {
+			return Err("unix socket path can't be empty".into());
+		}
+		if cfg!(not(unix)) && self.unix_socket().is_some() {
+			return Err("unix sockets are not supported on this platform".into());
+		}
+		if let Some(tls) = &self.tls_settings {
+			tls.validate()?;
+		}
+		if let Some(proxy) = &self.proxy {
+			crate::proxy::Proxy::parse(proxy)?;
+		}
+		if let Some(tuning) = &self.tuning {
+			tuning.validate()?;
+		}
+		Ok(())
+	}
+}
+
+/// Parts of a server URL, borrowed from the `endpoint` it was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Url<'a> {
+	Tcp {
+		tls: bool,
+		host: &'a str,
+		port: Option<u16>,
+		prefix: &'a str,
+	},
+	Unix(&'a str),
+}
+
+impl<'a> Url<'a> {
+	/// Parse an url like `https://host:port/prefix` or `unix:///path`, describing what's wrong.
+	fn parse(url: &'a str) -> Result<Self, String> {
+		let (scheme, rest) = url
+			.split_once("://")
+			.ok_or_else(|| format!("endpoint '{url}' has no scheme"))?;
+		let tls = match scheme {
+			"https" => true,
+			"http" => false,
+			"unix" if rest.is_empty() => return Err("unix socket path can't be empty".into()),
+			"unix" => return Ok(Self::Unix(rest)),
+			_ => return Err(format!("endpoint scheme '{scheme}' is not supported")),
+		};
+		let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+		if path.contains(['?', '#']) {
+			return Err(format!("endpoint '{url}' can't have a query or fragment"));
+		}
+		if authority.contains('@') {
+			return Err(format!("endpoint '{url}' can't carry credentials"));
+		}
+		let (host, port) = match authority.strip_prefix('[') {
+			Some(bracketed) => {
+				let (host, after) = bracketed
+					.split_once(']')
+					.ok_or_else(|| format!("endpoint '{url}' has an unclosed IPv6 address"))?;
+				match after {
+					"" => (host, None),
+					_ => (host, Some(after.strip_prefix(':').unwrap_or(after))),
+				}
+			}
+			None => match authority.split_once(':') {
+				Some((_, port)) if port.contains(':') => {
+					return Err(format!(
+						"endpoint '{url}' must enclose IPv6 addresses in brackets"
+					))
+				}
+				Some((host, port)) => (host, Some(port)),
+				None => (authority, None),
+			},
+		};
+		if host.is_empty() {
+			return Err(format!("endpoint '{url}' has no host"));
+		}
+		let port = port
+			.map(|port| {
+				port.parse()
+					.map_err(|_| format!("endpoint port '{port}' is invalid"))
+			})
+			.transpose()?;
+		Ok(Self::Tcp {
+			tls,
+			host,
+			port,
+			prefix: path.trim_end_matches('/'),
+		})
+	}
+}
+
+impl TlsSettings {
+	#[inline]
+	pub(crate) fn native_roots(&self) -> bool {
+		self.native_roots.unwrap_or(true)
+	}
+
+	/// Pinned fingerprint as raw bytes, ignoring case and `:` separators.
+	pub(crate) fn pinned_sha256(&self) -> Option<Result<Vec<u8>, String>> {
+		let hex = self.pinned_sha256.as_ref()?.replace(':', "");
+		let digest = (0..hex.len())
+			.step_by(2)
+			.map(|i| {
+				hex.get(i..i + 2)
+					.and_then(|x| u8::from_str_radix(x, 16).ok())
+			})
+			.collect::<Option<Vec<u8>>>()
+			.filter(|x| x.len() == 32);
+		Some(digest.ok_or_else(|| "tls_settings.pinned_sha256 must be 64 hex digits".to_string()))
+	}
+
+	fn validate(&self) -> Result<(), String> {
+		if self.client_cert.is_some() != self.client_key.is_some() {
+			return Err(
+				"tls_settings.client_cert and tls_settings.client_key must be set together".into(),
+			);
+		}
+		if !self.native_roots() && self.ca_bundle.is_none() {
+			return Err(
+				"tls_settings.ca_bundle is required when tls_settings.native_roots is disabled"
+					.into(),
+			);
+		}
+		if let Some(Err(e)) = self.pinned_sha256() {
+			return Err(e);
+		}
+		Ok(())
+	}
+}
+
+impl Tuning {
+	#[inline]
+	pub(crate) fn buffer_queue(&self) -> usize {
+		self.buffer_queue.unwrap_or(256) as usize
+	}
+
+	#[inline]
+	pub(crate) fn cursor_queue(&self) -> usize {
+		self.cursor_queue.unwrap_or(128) as usize
+	}
+
+	#[inline]
+	pub(crate) fn hash_period(&self) -> u32 {
+		self.hash_period.unwrap_or(10)
+	}
+
+	#[inline]
+	pub(crate) fn worker_tick(&self) -> std::time::Duration {
+		std::time::Duration::from_millis(self.worker_tick_ms.unwrap_or(5000).into())
+	}
+
+	#[inline]
+	pub(crate) fn connect_timeout(&self) -> Option<std::time::Duration> {
+		self.connect_timeout_ms
+			.map(|ms| std::time::Duration::from_millis(ms.into()))
+	}
+
+	#[inline]
+	pub(crate) fn request_timeout(&self) -> Option<std::time::Duration> {
+		self.request_timeout_ms
+			.map(|ms| std::time::Duration::from_millis(ms.into()))
+	}
+
+	#[inline]
+	pub(crate) fn http2_keepalive(&self) -> Option<std::time::Duration> {
+		self.http2_keepalive_ms
+			.map(|ms| std::time::Duration::from_millis(ms.into()))
+	}
+
+	#[inline]
+	pub(crate) fn http2_keepalive_timeout(&self) -> Option<std::time::Duration> {
+		self.http2_keepalive_timeout_ms
+			.map(|ms| std::time::Duration::from_millis(ms.into()))
+	}
+
+	#[inline]
+	pub(crate) fn tcp_keepalive(&self) -> Option<std::time::Duration> {
+		self.tcp_keepalive_ms
+			.map(|ms| std::time::Duration::from_millis(ms.into()))
+	}
+
+	#[inline]
+	pub(crate) fn connect_retries(&self) -> u32 {
+		self.connect_retries.unwrap_or(0)
+	}
+
+	#[inline]
+	pub(crate) fn retry_backoff(&self) -> std::time::Duration {
+		std::time::Duration::from_millis(self.retry_backoff_ms.unwrap_or(500).into())
+	}
+
+	#[inline]
+	pub(crate) fn refresh_margin(&self) -> std::time::Duration {
+		std::time::Duration::from_millis(self.refresh_margin_ms.unwrap_or(60_000).into())
+	}
+
+	#[inline]
+	pub(crate) fn idle_after(&self) -> std::time::Duration {
+		std::time::Duration::from_millis(self.idle_after_ms.unwrap_or(60_000).into())
+	}
+
+	#[inline]
+	pub(crate) fn away_after(&self) -> std::time::Duration {
+		std::time::Duration::from_millis(self.away_after_ms.unwrap_or(600_000).into())
+	}
+
+	#[inline]
+	pub(crate) fn chat_history(&self) -> usize {
+		self.chat_history.unwrap_or(256) as usize
+	}
+
+	pub(crate) fn callback_strategy(&self) -> crate::dispatch::Strategy {
+		use crate::dispatch::Strategy;
+		match self.callback_strategy.unwrap_or_default() {
+			CallbackStrategy::Inline => Strategy::Inline,
+			CallbackStrategy::Task => Strategy::Task,
+			CallbackStrategy::Drop => Strategy::Drop(self.callback_queue.unwrap_or(64) as usize),
+			CallbackStrategy::Coalesce => Strategy::Coalesce,
+		}
+	}
+
+	fn validate(&self) -> Result<(), String> {
+		let nonzero = [
+			("buffer_queue", self.buffer_queue),
+			("cursor_queue", self.cursor_queue),
+			("worker_tick_ms", self.worker_tick_ms),
+			("connect_timeout_ms", self.connect_timeout_ms),
+			("request_timeout_ms", self.request_timeout_ms),
+			("http2_keepalive_ms", self.http2_keepalive_ms),
+			(
+				"http2_keepalive_timeout_ms",
+				self.http2_keepalive_timeout_ms,
+			),
+			("tcp_keepalive_ms", self.tcp_keepalive_ms),
+			("callback_queue", self.callback_queue),
+			("idle_after_ms", self.idle_after_ms),
+			("away_after_ms", self.away_after_ms),
+			("chat_history", self.chat_history),
+		];
+		for (name, value) in nonzero {
+			if value == Some(0) {
+				return Err(format!("tuning.{name} must be greater than zero"));
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn default_config_is_valid() {
+		let config = super::Config::new("user", "password");
+		assert!(config.validate().is_ok());
+	}
+
+	#[test]
+	fn callbacks_run_inline_by_default() {
+		let tuning = super::Tuning::default();
+		assert_eq!(
+			tuning.callback_strategy(),
+			crate::dispatch::Strategy::Inline
+		);
+		let tuning = super::Tuning {
+			callback_strategy: Some(super::CallbackStrategy::Drop),
+			callback_queue: Some(8),
+			..Default::default()
+		};
+		assert_eq!(
+			tuning.callback_strategy(),
+			crate::dispatch::Strategy::Drop(8)
+		);
+	}
+
+	#[test]
+	fn empty_token_sources_are_rejected() {
+		let mut config = super::Config::new("user", "password");
+		config.token_command = Some(" ".into());
+		assert_eq!(
+			config.validate(),
+			Err("token_command can't be empty".to_string())
+		);
+	}
+
+	#[test]
+	fn endpoint_urls_are_parsed() {
+		use super::Url;
+		assert_eq!(
+			Url::parse("https://code.example/codemp/"),
+			Ok(Url::Tcp {
+				tls: true,
+				host: "code.example",
+				port: None,
+				prefix: "/codemp"
+			})
+		);
+		assert_eq!(
+			Url::parse("http://[::1]:8080"),
+			Ok(Url::Tcp {
+				tls: false,
+				host: "::1",
+				port: Some(8080),
+				prefix: ""
+			})
+		);
+		assert_eq!(
+			Url::parse("unix:///run/codemp.sock"),
+			Ok(Url::Unix("/run/codemp.sock"))
+		);
+		assert!(Url::parse("code.example:50053").is_err());
+		assert!(Url::parse("ftp://code.example").is_err());
+		assert!(Url::parse("http://::1:50053").is_err());
+		assert!(Url::parse("http://code.example:port").is_err());
+	}
+
+	#[test]
+	fn explicit_fields_override_endpoint() {
+		let mut config =
+			super::Config::from_url("http://[::1]:8080/codemp", "user", "password").unwrap();
+		assert_eq!(
+			(config.host(), config.port(), config.tls()),
+			("::1", 8080, false)
+		);
+		assert_eq!(config.path_prefix(), "/codemp");
+		config.host = Some("code.example".into());
+		config.tls = Some(true);
+		assert_eq!(
+			(config.host(), config.port(), config.tls()),
+			("code.example", 8080, true)
+		);
+		assert!(super::Config::from_url("http://", "user", "password").is_err());
+	}
+
+	#[test]
+	fn pinned_fingerprints_are_parsed() {
+		let mut tls = super::TlsSettings {
+			pinned_sha256: Some(format!("AB:{}", "01".repeat(31))),
+			..Default::default()
+		};
+		assert_eq!(tls.pinned_sha256().unwrap().unwrap()[0], 0xab);
+		tls.pinned_sha256 = Some("abc".into());
+		assert!(tls.validate().is_err());
 	}
 
-	pub(crate) fn endpoint(&self) -> String {
-		format!(
-			"{}://{}:{}",
-			if self.tls() { "https" } else { "http" },
-			self.host(),
-			self.port()
-		)
+	#[test]
+	fn zero_sized_queues_are_rejected() {
+		let mut config = super::Config::new("user", "password");
+		config.tuning = Some(super::Tuning {
+			buffer_queue: Some(0),
+			..Default::default()
+		});
+		assert_eq!(
+			config.validate(),
+			Err("tuning.buffer_queue must be greater than zero".to_string())
+		);
 	}
 }
@@ -23,7 +23,7 @@ use crate::errors::ControllerResult;
 /// Every [`Controller`]'s worker will stop cleanly when all references to its [`Controller`] have
 /// been dropped.
 ///
-/// [`crate::ext::select_buffer`] may provide a useful helper for managing multiple controllers.
+/// [`crate::ext::select`] may provide a useful helper for managing multiple controllers.
 #[allow(async_fn_in_trait)]
 #[cfg_attr(feature = "async-trait", async_trait::async_trait)]
 pub trait Controller<Tx, Rx = Tx>: AsyncSender<Tx> + AsyncReceiver<Rx>
@@ -0,0 +1,141 @@
+//! # Decoration
+//! Short-lived marks on a range of a buffer shared with other users, such as diagnostics from a
+//! language server or search highlights, see [`crate::decoration::Controller`].
+//!
+//! Unlike [`super::Annotation`]s, decorations are not stored by the server: they are only
+//! delivered to users attached at the time they are sent, and expire after a while.
+
+use crate::protocol::decorations as proto;
+
+/// What a [`Decoration`] represents, letting editors pick how to draw it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(eq, eq_int))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum DecorationKind {
+	/// A highlighted range, such as a search result.
+	#[default]
+	Highlight,
+	/// A range the author wants others to look at.
+	Mark,
+	/// An error diagnostic.
+	Error,
+	/// A warning diagnostic.
+	Warning,
+	/// An informational diagnostic.
+	Info,
+	/// A hint diagnostic.
+	Hint,
+}
+
+impl DecorationKind {
+	/// Stable `snake_case` name of this kind, as exposed to bindings.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Highlight => "highlight",
+			Self::Mark => "mark",
+			Self::Error => "error",
+			Self::Warning => "warning",
+			Self::Info => "info",
+			Self::Hint => "hint",
+		}
+	}
+}
+
+impl std::fmt::Display for DecorationKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl std::str::FromStr for DecorationKind {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"highlight" => Ok(Self::Highlight),
+			"mark" => Ok(Self::Mark),
+			"error" => Ok(Self::Error),
+			"warning" => Ok(Self::Warning),
+			"info" => Ok(Self::Info),
+			"hint" => Ok(Self::Hint),
+			_ => Err(format!("unknown decoration kind '{s}'")),
+		}
+	}
+}
+
+impl From<proto::Kind> for DecorationKind {
+	fn from(value: proto::Kind) -> Self {
+		match value {
+			proto::Kind::Highlight => Self::Highlight,
+			proto::Kind::Mark => Self::Mark,
+			proto::Kind::Error => Self::Error,
+			proto::Kind::Warning => Self::Warning,
+			proto::Kind::Info => Self::Info,
+			proto::Kind::Hint => Self::Hint,
+		}
+	}
+}
+
+impl From<DecorationKind> for proto::Kind {
+	fn from(value: DecorationKind) -> Self {
+		match value {
+			DecorationKind::Highlight => Self::Highlight,
+			DecorationKind::Mark => Self::Mark,
+			DecorationKind::Error => Self::Error,
+			DecorationKind::Warning => Self::Warning,
+			DecorationKind::Info => Self::Info,
+			DecorationKind::Hint => Self::Hint,
+		}
+	}
+}
+
+/// A short-lived mark on a range of a buffer.
+///
+/// Missing fields are filled in from [`Decoration::default`] when deserializing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+	any(feature = "py", feature = "py-noabi"),
+	pyo3::pyclass(get_all, set_all)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+pub struct Decoration {
+	/// What the decoration represents.
+	pub kind: DecorationKind,
+	/// Message explaining the decoration, possibly empty.
+	pub message: String,
+	/// Name of the user who sent the decoration, ignored when sending.
+	pub author: String,
+	/// Index of the first character covered, in the buffer as currently shown to the editor.
+	pub start_idx: u32,
+	/// Index after the last character covered, in the buffer as currently shown to the editor.
+	pub end_idx: u32,
+	/// How long the decoration should be shown once received, in milliseconds.
+	pub ttl_ms: u32,
+}
+
+impl Decoration {
+	/// How long decorations are shown by default, in milliseconds.
+	pub const DEFAULT_TTL_MS: u32 = 30_000;
+
+	/// A decoration on given range of the buffer, shown for [`Decoration::DEFAULT_TTL_MS`].
+	pub fn new(kind: DecorationKind, start_idx: u32, end_idx: u32, message: impl ToString) -> Self {
+		Self {
+			kind,
+			message: message.to_string(),
+			author: String::new(),
+			start_idx,
+			end_idx,
+			ttl_ms: Self::DEFAULT_TTL_MS,
+		}
+	}
+}
+
+impl Default for Decoration {
+	/// An empty [`DecorationKind::Highlight`] at the start of the buffer, shown for
+	/// [`Decoration::DEFAULT_TTL_MS`].
+	fn default() -> Self {
+		Self::new(DecorationKind::default(), 0, 0, "")
+	}
+}
@@ -17,6 +17,9 @@ pub enum Event {
 	UserJoin { name: String },
 	/// Fired when an user leaves the current workspace.
 	UserLeave { name: String },
+	/// Fired when the presence of an user changes: their buffer, status or profile.
+	/// See [`crate::Workspace::presence`].
+	PresenceChanged { name: String },
 }
 
 impl From<WorkspaceEventInner> for Event {
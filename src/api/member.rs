@@ -0,0 +1,102 @@
+//! # Member
+//! Users allowed into a workspace, each with a [`Role`] deciding what they may do there.
+
+use crate::protocol::members as proto;
+
+use super::User;
+
+/// What a member may do in a workspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(eq, eq_int))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum Role {
+	/// May edit and manage buffers.
+	#[default]
+	ReadWrite,
+	/// May only follow along: changes sent to buffers are refused.
+	ReadOnly,
+	/// May also manage the workspace and its members.
+	Owner,
+}
+
+impl Role {
+	/// Whether this role allows editing buffers.
+	pub fn can_write(self) -> bool {
+		!matches!(self, Self::ReadOnly)
+	}
+
+	/// Stable `snake_case` name of this role, as exposed to bindings.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::ReadWrite => "read_write",
+			Self::ReadOnly => "read_only",
+			Self::Owner => "owner",
+		}
+	}
+}
+
+impl std::fmt::Display for Role {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl std::str::FromStr for Role {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"read_write" => Ok(Self::ReadWrite),
+			"read_only" => Ok(Self::ReadOnly),
+			"owner" => Ok(Self::Owner),
+			_ => Err(format!("unknown role '{s}'")),
+		}
+	}
+}
+
+impl From<proto::Role> for Role {
+	fn from(value: proto::Role) -> Self {
+		match value {
+			proto::Role::ReadWrite => Self::ReadWrite,
+			proto::Role::ReadOnly => Self::ReadOnly,
+			proto::Role::Owner => Self::Owner,
+		}
+	}
+}
+
+impl From<Role> for proto::Role {
+	fn from(value: Role) -> Self {
+		match value {
+			Role::ReadWrite => Self::ReadWrite,
+			Role::ReadOnly => Self::ReadOnly,
+			Role::Owner => Self::Owner,
+		}
+	}
+}
+
+/// A user allowed into a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(get_all))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Member {
+	/// The member itself.
+	pub user: User,
+	/// What the member may do in the workspace.
+	pub role: Role,
+	/// Whether the member was invited but didn't join yet.
+	pub pending: bool,
+}
+
+impl From<proto::Member> for Member {
+	fn from(value: proto::Member) -> Self {
+		Self {
+			user: User {
+				id: value.uuid(),
+				name: value.name.clone(),
+			},
+			role: value.role().into(),
+			pending: value.pending,
+		}
+	}
+}
@@ -0,0 +1,208 @@
+//! # Metadata
+//! Properties of a buffer which every editor should agree on, such as its language and line
+//! endings, see [`crate::Workspace::set_buffer_metadata`].
+
+use std::borrow::Cow;
+
+use crate::protocol::metadata as proto;
+
+/// How lines of a buffer are terminated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(eq, eq_int))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum LineEnding {
+	/// A line feed, as on Unix systems.
+	#[default]
+	Lf,
+	/// A carriage return followed by a line feed, as on Windows systems.
+	Crlf,
+	/// A carriage return alone, as on classic Mac OS systems.
+	Cr,
+}
+
+impl LineEnding {
+	/// Stable `snake_case` name of this line ending, as exposed to bindings.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Lf => "lf",
+			Self::Crlf => "crlf",
+			Self::Cr => "cr",
+		}
+	}
+
+	/// Characters terminating each line.
+	pub fn sequence(self) -> &'static str {
+		match self {
+			Self::Lf => "\n",
+			Self::Crlf => "\r\n",
+			Self::Cr => "\r",
+		}
+	}
+
+	/// Terminate every line of given text with this line ending, whatever it was before.
+	///
+	/// Text already using only this line ending is returned as is.
+	pub fn normalize(self, text: &str) -> Cow<'_, str> {
+		let foreign = match self {
+			Self::Lf => text.contains('\r'),
+			Self::Cr => text.contains('\n'),
+			Self::Crlf => text.char_indices().any(|(i, c)| match c {
+				'\r' => !text[i + 1..].starts_with('\n'),
+				'\n' => !text[..i].ends_with('\r'),
+				_ => false,
+			}),
+		};
+		if !foreign {
+			return Cow::Borrowed(text);
+		}
+		let mut out = String::with_capacity(text.len());
+		let mut chars = text.chars().peekable();
+		while let Some(c) = chars.next() {
+			match c {
+				'\r' => {
+					chars.next_if_eq(&'\n');
+					out.push_str(self.sequence());
+				}
+				'\n' => out.push_str(self.sequence()),
+				c => out.push(c),
+			}
+		}
+		Cow::Owned(out)
+	}
+}
+
+impl std::fmt::Display for LineEnding {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl std::str::FromStr for LineEnding {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"lf" => Ok(Self::Lf),
+			"crlf" => Ok(Self::Crlf),
+			"cr" => Ok(Self::Cr),
+			_ => Err(format!("unknown line ending '{s}'")),
+		}
+	}
+}
+
+impl From<proto::LineEnding> for LineEnding {
+	fn from(value: proto::LineEnding) -> Self {
+		match value {
+			proto::LineEnding::Lf => Self::Lf,
+			proto::LineEnding::Crlf => Self::Crlf,
+			proto::LineEnding::Cr => Self::Cr,
+		}
+	}
+}
+
+impl From<LineEnding> for proto::LineEnding {
+	fn from(value: LineEnding) -> Self {
+		match value {
+			LineEnding::Lf => Self::Lf,
+			LineEnding::Crlf => Self::Crlf,
+			LineEnding::Cr => Self::Cr,
+		}
+	}
+}
+
+/// How lines of a buffer are indented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "js", napi_derive::napi(object))]
+#[cfg_attr(
+	any(feature = "py", feature = "py-noabi"),
+	pyo3::pyclass(get_all, set_all)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Indentation {
+	/// Whether lines are indented with tabs rather than spaces.
+	pub tabs: bool,
+	/// How many columns each indentation level spans.
+	pub width: u32,
+}
+
+/// Properties of a buffer which every editor should agree on.
+///
+/// Every field is optional: unset ones are left for each editor to guess.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+	any(feature = "py", feature = "py-noabi"),
+	pyo3::pyclass(get_all, set_all)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BufferMetadata {
+	/// Identifier of the language of the buffer, such as `rust`.
+	pub language: Option<String>,
+	/// How lines of the buffer are terminated.
+	pub line_ending: Option<LineEnding>,
+	/// How lines of the buffer are indented.
+	pub indentation: Option<Indentation>,
+	/// Encoding of the buffer once saved to a file, such as `utf-8`.
+	pub encoding: Option<String>,
+}
+
+impl BufferMetadata {
+	pub(crate) fn into_proto(self, path: &str) -> proto::BufferMetadata {
+		let mut metadata = proto::BufferMetadata {
+			path: path.to_string(),
+			language: self.language,
+			line_ending: None,
+			indentation: self.indentation.map(|i| proto::Indentation {
+				tabs: i.tabs,
+				width: i.width,
+			}),
+			encoding: self.encoding,
+		};
+		if let Some(line_ending) = self.line_ending {
+			metadata.set_line_ending(line_ending.into());
+		}
+		metadata
+	}
+}
+
+impl From<proto::BufferMetadata> for BufferMetadata {
+	fn from(value: proto::BufferMetadata) -> Self {
+		Self {
+			line_ending: value
+				.line_ending
+				.is_some()
+				.then(|| value.line_ending().into()),
+			language: value.language,
+			indentation: value.indentation.map(|i| Indentation {
+				tabs: i.tabs,
+				width: i.width,
+			}),
+			encoding: value.encoding,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::LineEnding;
+
+	#[test]
+	fn normalize_converts_every_line_ending() {
+		let mixed = "a\r\nb\nc\rd";
+		assert_eq!(LineEnding::Lf.normalize(mixed), "a\nb\nc\nd");
+		assert_eq!(LineEnding::Crlf.normalize(mixed), "a\r\nb\r\nc\r\nd");
+		assert_eq!(LineEnding::Cr.normalize(mixed), "a\rb\rc\rd");
+	}
+
+	#[test]
+	fn normalize_borrows_text_already_normalized() {
+		assert!(matches!(
+			LineEnding::Crlf.normalize("a\r\nb\r\n"),
+			std::borrow::Cow::Borrowed(_)
+		));
+		assert!(matches!(
+			LineEnding::Crlf.normalize("a\nb"),
+			std::borrow::Cow::Owned(_)
+		));
+	}
+}
@@ -19,9 +19,37 @@ pub mod event;
 /// data structure for remote users
 pub mod user;
 
+/// stable colors for users
+pub mod color;
+
+/// workspace members and their roles
+pub mod member;
+
+/// what users in a workspace are up to
+pub mod presence;
+
+/// text messages between users of a workspace
+pub mod chat;
+
+/// review comments anchored to buffer ranges
+pub mod annotation;
+
+/// short-lived marks on buffer ranges
+pub mod decoration;
+
+/// properties of buffers, such as their language and line endings
+pub mod metadata;
+
+pub use annotation::Annotation;
 pub use change::{BufferUpdate, TextChange};
-pub use config::Config;
+pub use chat::{Draft, Message};
+pub use color::Theme;
+pub use config::{CallbackStrategy, Config, TlsSettings, Tuning};
 pub use controller::{AsyncReceiver, AsyncSender, Controller};
 pub use cursor::{Cursor, Selection};
+pub use decoration::{Decoration, DecorationKind};
 pub use event::Event;
+pub use member::{Member, Role};
+pub use metadata::{BufferMetadata, Indentation, LineEnding};
+pub use presence::{Presence, Status};
 pub use user::User;
@@ -0,0 +1,161 @@
+//! # Presence
+//! What users in a workspace are up to: which buffer they are in, whether they are around, and
+//! how they present themselves to others.
+
+use std::time::{Duration, Instant};
+
+use crate::protocol::presence as proto;
+
+use super::User;
+
+/// How recently a user was seen doing something in a workspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(eq, eq_int))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum Status {
+	/// Recently moved their cursor.
+	#[default]
+	Active,
+	/// Didn't move their cursor for a while, see [`crate::api::Tuning::idle_after_ms`].
+	Idle,
+	/// Didn't move their cursor for a long time, see [`crate::api::Tuning::away_after_ms`].
+	Away,
+}
+
+impl Status {
+	/// Status of a user whose last activity happened this long ago.
+	fn since(elapsed: Duration, idle: Duration, away: Duration) -> Self {
+		if elapsed >= away {
+			Self::Away
+		} else if elapsed >= idle {
+			Self::Idle
+		} else {
+			Self::Active
+		}
+	}
+
+	/// Stable `snake_case` name of this status, as exposed to bindings.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Active => "active",
+			Self::Idle => "idle",
+			Self::Away => "away",
+		}
+	}
+}
+
+impl std::fmt::Display for Status {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// A snapshot of what a user in a workspace is up to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass(get_all))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Presence {
+	/// The user this presence refers to.
+	pub user: User,
+	/// Name to show instead of the user name, if the user chose one.
+	pub display_name: Option<String>,
+	/// URL of an avatar picture, if the user chose one.
+	pub avatar: Option<String>,
+	/// Color assigned to the user as `#rrggbb`, see [`User::color`].
+	pub color: String,
+	/// How recently the user was active.
+	pub status: Status,
+	/// Path of the buffer the user last moved their cursor in, if any.
+	pub buffer: Option<String>,
+}
+
+impl Presence {
+	/// Name to show for this user: their display name if set, their user name otherwise.
+	pub fn label(&self) -> &str {
+		self.display_name.as_deref().unwrap_or(&self.user.name)
+	}
+}
+
+/// Everything tracked about a user in a workspace, from which their [`Presence`] is derived.
+#[derive(Debug, Clone)]
+pub(crate) struct Tracked {
+	pub(crate) user: User,
+	display_name: Option<String>,
+	avatar: Option<String>,
+	buffer: Option<String>,
+	last_activity: Instant,
+	reported: Status,
+}
+
+impl Tracked {
+	/// Start tracking a user, considering them active from now.
+	pub(crate) fn new(user: User) -> Self {
+		Self {
+			user,
+			display_name: None,
+			avatar: None,
+			buffer: None,
+			last_activity: Instant::now(),
+			reported: Status::Active,
+		}
+	}
+
+	/// Record some activity in given buffer, returning whether the presence visibly changed.
+	pub(crate) fn touch(&mut self, buffer: String) -> bool {
+		self.last_activity = Instant::now();
+		let moved = self.buffer.as_deref() != Some(buffer.as_str());
+		self.buffer = Some(buffer);
+		let woke = self.reported != Status::Active;
+		self.reported = Status::Active;
+		moved || woke
+	}
+
+	/// Check whether the status changed since it was last reported, marking it as reported.
+	pub(crate) fn refresh(&mut self, idle: Duration, away: Duration) -> bool {
+		let status = Status::since(self.last_activity.elapsed(), idle, away);
+		let changed = status != self.reported;
+		self.reported = status;
+		changed
+	}
+
+	/// Update display name and avatar, returning whether they changed.
+	pub(crate) fn set_profile(&mut self, profile: proto::Profile) -> bool {
+		let changed = self.display_name != profile.display_name || self.avatar != profile.avatar;
+		self.display_name = profile.display_name;
+		self.avatar = profile.avatar;
+		changed
+	}
+
+	pub(crate) fn presence(&self, idle: Duration, away: Duration) -> Presence {
+		Presence {
+			user: self.user.clone(),
+			display_name: self.display_name.clone(),
+			avatar: self.avatar.clone(),
+			color: self.user.color(None).to_string(),
+			status: Status::since(self.last_activity.elapsed(), idle, away),
+			buffer: self.buffer.clone(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::Status;
+
+	#[test]
+	fn status_follows_last_activity() {
+		let (idle, away) = (Duration::from_secs(60), Duration::from_secs(600));
+		assert_eq!(Status::since(Duration::ZERO, idle, away), Status::Active);
+		assert_eq!(
+			Status::since(Duration::from_secs(61), idle, away),
+			Status::Idle
+		);
+		assert_eq!(
+			Status::since(Duration::from_secs(601), idle, away),
+			Status::Away
+		);
+	}
+}
@@ -15,6 +15,15 @@ pub struct User {
 	pub name: String,
 }
 
+impl User {
+	/// Color assigned to this user as `#rrggbb`, the same on every client.
+	///
+	/// See [`crate::api::color`] for how it is picked.
+	pub fn color(&self, theme: Option<super::Theme>) -> &'static str {
+		super::color::user_color(self.id, theme)
+	}
+}
+
 impl From<codemp_proto::common::User> for User {
 	fn from(value: codemp_proto::common::User) -> Self {
 		Self {
@@ -0,0 +1,213 @@
+//! ### Auth
+//! Providers of credentials used by a [`crate::Client`] to authenticate new sessions.
+//!
+//! Built-in providers can be selected with [`crate::api::Config`] fields, in order of precedence
+//! `token`, `token_env`, `token_command` and finally `username` and `password`. Custom ones can
+//! be used with [`crate::Client::connect_with`].
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::errors::{ConnectionError, ConnectionResult};
+
+/// Credentials which can be used to authenticate a new session.
+#[derive(Clone)]
+pub enum Credentials {
+	/// Log in with username and password.
+	Password { username: String, password: String },
+	/// Use a pre-issued session token, skipping login.
+	Token(String),
+}
+
+impl std::fmt::Debug for Credentials {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Password { username, .. } => f
+				.debug_struct("Password")
+				.field("username", username)
+				.finish_non_exhaustive(),
+			Self::Token(_) => f.write_str("Token(..)"),
+		}
+	}
+}
+
+/// Something able to provide [`Credentials`] for a new session.
+pub trait AuthProvider: Send + Sync {
+	/// Obtain credentials, failing with [`ConnectionError::Auth`] if that's not possible.
+	fn credentials(&self) -> impl Future<Output = ConnectionResult<Credentials>> + Send;
+}
+
+/// Type-erased [`AuthProvider`], kept by a session to authenticate again once its token can't be
+/// refreshed anymore.
+pub(crate) type DynProvider = Arc<
+	dyn Fn() -> Pin<Box<dyn Future<Output = ConnectionResult<Credentials>> + Send>> + Send + Sync,
+>;
+
+pub(crate) fn erase<A: AuthProvider + 'static>(provider: A) -> DynProvider {
+	let provider = Arc::new(provider);
+	Arc::new(move || {
+		let provider = provider.clone();
+		Box::pin(async move { provider.credentials().await })
+	})
+}
+
+/// Provides given username and password.
+#[derive(Debug, Clone)]
+pub struct Password {
+	pub username: String,
+	pub password: String,
+}
+
+impl AuthProvider for Password {
+	async fn credentials(&self) -> ConnectionResult<Credentials> {
+		Ok(Credentials::Password {
+			username: self.username.clone(),
+			password: self.password.clone(),
+		})
+	}
+}
+
+/// Provides a fixed, pre-issued session token.
+#[derive(Clone)]
+pub struct StaticToken(pub String);
+
+impl std::fmt::Debug for StaticToken {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("StaticToken(..)")
+	}
+}
+
+impl AuthProvider for StaticToken {
+	async fn credentials(&self) -> ConnectionResult<Credentials> {
+		Ok(Credentials::Token(self.0.clone()))
+	}
+}
+
+/// Provides the session token stored in an environment variable, read upon each request.
+#[derive(Debug, Clone)]
+pub struct Env(pub String);
+
+impl AuthProvider for Env {
+	async fn credentials(&self) -> ConnectionResult<Credentials> {
+		match std::env::var(&self.0) {
+			Ok(token) if !token.trim().is_empty() => {
+				Ok(Credentials::Token(token.trim().to_string()))
+			}
+			Ok(_) => Err(ConnectionError::Auth(format!(
+				"variable {} is empty",
+				self.0
+			))),
+			Err(e) => Err(ConnectionError::Auth(format!(
+				"could not read {}: {e}",
+				self.0
+			))),
+		}
+	}
+}
+
+/// Provides the session token printed by a shell command, run upon each request.
+///
+/// Useful to integrate with SSO helpers and keyrings, e.g. `secret-tool lookup service codemp`.
+#[derive(Debug, Clone)]
+pub struct Command(pub String);
+
+impl AuthProvider for Command {
+	async fn credentials(&self) -> ConnectionResult<Credentials> {
+		#[cfg(windows)]
+		let mut cmd = tokio::process::Command::new("cmd");
+		#[cfg(windows)]
+		cmd.arg("/C");
+		#[cfg(not(windows))]
+		let mut cmd = tokio::process::Command::new("sh");
+		#[cfg(not(windows))]
+		cmd.arg("-c");
+
+		let output = cmd
+			.arg(&self.0)
+			.stdin(std::process::Stdio::null())
+			.kill_on_drop(true)
+			.output()
+			.await
+			.map_err(|e| ConnectionError::Auth(format!("could not run token command: {e}")))?;
+
+		if !output.status.success() {
+			return Err(ConnectionError::Auth(format!(
+				"token command failed ({}): {}",
+				output.status,
+				String::from_utf8_lossy(&output.stderr).trim()
+			)));
+		}
+
+		match String::from_utf8(output.stdout) {
+			Ok(token) if !token.trim().is_empty() => {
+				Ok(Credentials::Token(token.trim().to_string()))
+			}
+			Ok(_) => Err(ConnectionError::Auth(
+				"token command printed nothing".into(),
+			)),
+			Err(_) => Err(ConnectionError::Auth(
+				"token command printed invalid utf8".into(),
+			)),
+		}
+	}
+}
+
+/// The built-in provider selected by a [`crate::api::Config`].
+///
+/// In order of precedence: `token`, `token_env`, `token_command`, and finally `username` and
+/// `password`.
+#[derive(Debug, Clone)]
+pub(crate) enum ConfigProvider {
+	Password(Password),
+	StaticToken(StaticToken),
+	Env(Env),
+	Command(Command),
+}
+
+impl From<&crate::api::Config> for ConfigProvider {
+	fn from(config: &crate::api::Config) -> Self {
+		if let Some(token) = &config.token {
+			Self::StaticToken(StaticToken(token.clone()))
+		} else if let Some(var) = &config.token_env {
+			Self::Env(Env(var.clone()))
+		} else if let Some(cmd) = &config.token_command {
+			Self::Command(Command(cmd.clone()))
+		} else {
+			Self::Password(Password {
+				username: config.username.clone(),
+				password: config.password.clone(),
+			})
+		}
+	}
+}
+
+impl AuthProvider for ConfigProvider {
+	async fn credentials(&self) -> ConnectionResult<Credentials> {
+		match self {
+			Self::Password(x) => x.credentials().await,
+			Self::StaticToken(x) => x.credentials().await,
+			Self::Env(x) => x.credentials().await,
+			Self::Command(x) => x.credentials().await,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{AuthProvider, Command, Credentials};
+
+	#[cfg(not(windows))]
+	#[tokio::test]
+	async fn command_provider_trims_output_and_reports_failures() {
+		let ok = Command("echo '  token-from-helper  '".into());
+		assert!(matches!(
+			ok.credentials().await,
+			Ok(Credentials::Token(t)) if t == "token-from-helper"
+		));
+
+		let failing = Command("echo nope >&2; exit 3".into());
+		assert!(matches!(
+			failing.credentials().await,
+			Err(crate::errors::ConnectionError::Auth(e)) if e.contains("nope")
+		));
+	}
+}
@@ -7,10 +7,15 @@ use diamond_types::LocalVersion;
 use tokio::sync::{mpsc, oneshot, watch};
 
 use crate::api::controller::{AsyncReceiver, AsyncSender, Controller, ControllerCallback};
+use crate::api::Annotation;
+use crate::api::BufferMetadata;
 use crate::api::BufferUpdate;
+use crate::api::LineEnding;
+use crate::api::Role;
 use crate::api::TextChange;
-use crate::errors::ControllerResult;
-use crate::ext::IgnorableError;
+use crate::errors::{ControllerError, ControllerResult};
+use crate::ext::{ControllerStream, IgnorableError};
+use crate::protocol::annotations::Anchor;
 
 /// A [Controller] to asynchronously interact with remote buffers.
 ///
@@ -27,6 +32,13 @@ impl BufferController {
 		&self.0.name
 	}
 
+	/// Whether changes sent to this buffer are refused, either because it was attached with
+	/// [`crate::Workspace::attach_buffer_read_only`] or because of the current user's [`Role`]
+	/// in the workspace.
+	pub fn is_read_only(&self) -> bool {
+		self.0.spectator || !self.0.role.borrow().can_write()
+	}
+
 	/// Return buffer whole content, updating internal acknowledgement tracker.
 	pub async fn content(&self) -> ControllerResult<String> {
 		let (tx, rx) = oneshot::channel();
@@ -35,6 +47,189 @@ impl BufferController {
 		Ok(content)
 	}
 
+	/// Check whether given content hash matches the buffer state the editor should be displaying.
+	///
+	/// The hash is compared against the content of the buffer as it was last delivered to the
+	/// editor (all received [`BufferUpdate`]s and sent [`TextChange`]s), calculated with
+	/// [`crate::ext::hash`]. Unlike [`BufferController::content`], this doesn't consume pending
+	/// changes.
+	pub async fn verify(&self, hash: i64) -> ControllerResult<bool> {
+		let content = self.checkout_branch().await?;
+		Ok(crate::ext::hash(content) == hash)
+	}
+
+	/// Compare given editor content with the buffer state the editor should be displaying,
+	/// returning the [`TextChange`] that brings it back in sync, if any is necessary.
+	///
+	/// The returned change is expressed over the given content and should be applied to the
+	/// editor only: it must **not** be sent back with [`AsyncSender::send`], as the CRDT is
+	/// already in the desired state.
+	pub async fn resync(&self, content: &str) -> ControllerResult<Option<TextChange>> {
+		let expected = self.checkout_branch().await?;
+		Ok(TextChange::diff(content, &expected))
+	}
+
+	/// Turn this controller into a [`tokio_stream::Stream`] of [`BufferUpdate`]s.
+	///
+	/// The stream ends once the controller worker stops, see [`ControllerStream`].
+	pub fn into_stream(self) -> ControllerStream<BufferUpdate> {
+		ControllerStream::new(move || {
+			let controller = self.clone();
+			Box::pin(async move { controller.recv().await })
+		})
+	}
+
+	async fn checkout_branch(&self) -> ControllerResult<String> {
+		let (tx, rx) = oneshot::channel();
+		self.0.branch_request.send(tx).await?;
+		Ok(rx.await?)
+	}
+
+	/// Annotate a range of the buffer, as currently shown to the editor, returning the identifier
+	/// of the new annotation.
+	///
+	/// The annotation is anchored to the buffer CRDT: as the buffer changes, its range moves to
+	/// keep covering the same text. It becomes visible in [`BufferController::annotations`] once
+	/// the server relays it back. Servers not supporting annotations silently ignore it.
+	pub fn annotate(&self, start_idx: u32, end_idx: u32, text: &str) -> ControllerResult<String> {
+		let id = uuid::Uuid::new_v4().to_string();
+		self.0
+			.ops_in
+			.send(EditorOp::Annotation(AnnotationOp::Create {
+				id: id.clone(),
+				start_idx,
+				end_idx,
+				text: text.to_string(),
+			}))?;
+		Ok(id)
+	}
+
+	/// Mark the annotation with given identifier as resolved.
+	pub fn resolve_annotation(&self, id: &str) -> ControllerResult<()> {
+		Ok(self
+			.0
+			.ops_in
+			.send(EditorOp::Annotation(AnnotationOp::Resolve(id.to_string())))?)
+	}
+
+	/// Delete the annotation with given identifier.
+	pub fn delete_annotation(&self, id: &str) -> ControllerResult<()> {
+		Ok(self
+			.0
+			.ops_in
+			.send(EditorOp::Annotation(AnnotationOp::Delete(id.to_string())))?)
+	}
+
+	/// Get all annotations of this buffer, in order of creation, with their ranges moved to the
+	/// buffer as currently shown to the editor.
+	///
+	/// Annotations made on changes the editor hasn't received yet are left out until it does.
+	pub async fn annotations(&self) -> ControllerResult<Vec<Annotation>> {
+		let (tx, rx) = oneshot::channel();
+		self.0.annotations_request.send(tx).await?;
+		Ok(rx.await?)
+	}
+
+	/// Register a callback to be invoked every time an annotation of this buffer is created,
+	/// resolved or deleted. There can only be one annotation callback registered at any given
+	/// time, independently from the one set with [`AsyncReceiver::callback`].
+	pub fn annotation_callback(&self, cb: impl Into<ControllerCallback<BufferController>>) {
+		self.0.annotation_callback.send_replace(Some(cb.into()));
+	}
+
+	/// Clear the currently registered annotation callback.
+	pub fn clear_annotation_callback(&self) {
+		if self.0.annotation_callback.send(None).is_err() {
+			tracing::warn!("no active buffer worker to clear annotation callback");
+		}
+	}
+
+	/// Get the last [`BufferMetadata`] announced by the server for this buffer.
+	///
+	/// Every field is unset until the server announces it, and forever on servers without
+	/// metadata support.
+	pub fn metadata(&self) -> BufferMetadata {
+		self.0.metadata.borrow().clone()
+	}
+
+	/// Register a callback to be invoked every time the server announces new metadata for this
+	/// buffer. There can only be one metadata callback registered at any given time,
+	/// independently from the one set with [`AsyncReceiver::callback`].
+	pub fn metadata_callback(&self, cb: impl Into<ControllerCallback<BufferController>>) {
+		self.0.metadata_callback.send_replace(Some(cb.into()));
+	}
+
+	/// Clear the currently registered metadata callback.
+	pub fn clear_metadata_callback(&self) {
+		if self.0.metadata_callback.send(None).is_err() {
+			tracing::warn!("no active buffer worker to clear metadata callback");
+		}
+	}
+
+	/// Enable or disable normalization of line endings in sent changes, disabled by default.
+	///
+	/// While enabled and the buffer [`BufferMetadata::line_ending`] is set, line endings of
+	/// inserted text are replaced with it right after sending. The replacement is delivered back
+	/// to the editor like any other change, so that it ends up with the canonical line endings too.
+	pub fn normalize_line_endings(&self, enabled: bool) {
+		self.0.normalize.send_replace(enabled);
+	}
+
+	/// Whether line endings of sent changes are normalized, see
+	/// [`BufferController::normalize_line_endings`].
+	pub fn is_normalizing_line_endings(&self) -> bool {
+		*self.0.normalize.borrow()
+	}
+
+	/// Translate line endings between the buffer and an editor using given one, or stop
+	/// translating them if `None`, as by default.
+	///
+	/// While translating, the editor is shown every line break of the buffer with its own line
+	/// ending: [`BufferController::content`], received [`BufferUpdate`]s, hashes and every index
+	/// refer to the buffer as the editor shows it. Sent [`TextChange`]s are expected in the same
+	/// form, and their line endings are replaced with the [`BufferMetadata::line_ending`] of the
+	/// buffer, if set. Any other line ending sent by the editor is replaced too, leaving it out of
+	/// sync until [`BufferController::resync`].
+	///
+	/// Since this changes how the buffer is shown, it should be set before fetching its content
+	/// or the editor should [`BufferController::resync`] afterwards.
+	pub fn translate_line_endings(&self, local: Option<LineEnding>) {
+		self.0.translate.send_replace(local);
+	}
+
+	/// Line ending of the editor, if line endings are translated, see
+	/// [`BufferController::translate_line_endings`].
+	pub fn local_line_ending(&self) -> Option<LineEnding> {
+		*self.0.translate.borrow()
+	}
+
+	/// Anchor given range of the buffer, as currently shown to the editor, to its CRDT.
+	///
+	/// The request is queued along with changes sent by the editor, so that the range refers to
+	/// the buffer as it was when this was called.
+	pub(crate) fn anchor(
+		&self,
+		start_idx: u32,
+		end_idx: u32,
+	) -> ControllerResult<oneshot::Receiver<Anchor>> {
+		let (tx, rx) = oneshot::channel();
+		self.0
+			.ops_in
+			.send(EditorOp::Anchor(start_idx, end_idx, tx))?;
+		Ok(rx)
+	}
+
+	/// Find where given anchored ranges lie in the buffer as currently shown to the editor, if
+	/// the editor already received the changes they refer to.
+	pub(crate) async fn locate(
+		&self,
+		anchors: Vec<Anchor>,
+	) -> ControllerResult<Vec<Option<(u32, u32)>>> {
+		let (tx, rx) = oneshot::channel();
+		self.0.locate_request.send((anchors, tx)).await?;
+		Ok(rx.await?)
+	}
+
 	/// Notify CRDT that changes up to the given version have been merged succesfully.
 	pub fn ack(&self, version: Vec<i64>) {
 		let version = version
@@ -53,20 +248,57 @@ pub(crate) struct BufferControllerInner {
 	pub(crate) name: String,
 	pub(crate) latest_version: watch::Receiver<diamond_types::LocalVersion>,
 	pub(crate) local_version: watch::Receiver<diamond_types::LocalVersion>,
-	pub(crate) ops_in: mpsc::UnboundedSender<TextChange>,
+	pub(crate) ops_in: mpsc::UnboundedSender<EditorOp>,
 	pub(crate) poller: mpsc::UnboundedSender<oneshot::Sender<()>>,
 	pub(crate) content_request: mpsc::Sender<oneshot::Sender<String>>,
+	pub(crate) branch_request: mpsc::Sender<oneshot::Sender<String>>,
 	pub(crate) delta_request: mpsc::Sender<(LocalVersion, oneshot::Sender<Option<BufferUpdate>>)>,
 	pub(crate) callback: watch::Sender<Option<ControllerCallback<BufferController>>>,
 	pub(crate) ack_tx: mpsc::UnboundedSender<LocalVersion>,
+	pub(crate) role: watch::Receiver<Role>,
+	pub(crate) spectator: bool,
+	pub(crate) annotations_request: mpsc::Sender<oneshot::Sender<Vec<Annotation>>>,
+	pub(crate) annotation_callback: watch::Sender<Option<ControllerCallback<BufferController>>>,
+	pub(crate) locate_request:
+		mpsc::Sender<(Vec<Anchor>, oneshot::Sender<Vec<Option<(u32, u32)>>>)>,
+	pub(crate) metadata: watch::Receiver<BufferMetadata>,
+	pub(crate) metadata_callback: watch::Sender<Option<ControllerCallback<BufferController>>>,
+	pub(crate) normalize: watch::Sender<bool>,
+	pub(crate) translate: watch::Sender<Option<LineEnding>>,
+}
+
+/// Requests from the editor, carried out by the worker in the order they were made.
+#[derive(Debug)]
+pub(crate) enum EditorOp {
+	Change(TextChange),
+	Annotation(AnnotationOp),
+	Anchor(u32, u32, oneshot::Sender<Anchor>),
+}
+
+/// Changes to annotations requested by the editor, carried out by the worker.
+#[derive(Debug)]
+pub(crate) enum AnnotationOp {
+	Create {
+		id: String,
+		start_idx: u32,
+		end_idx: u32,
+		text: String,
+	},
+	Resolve(String),
+	Delete(String),
 }
 
 #[cfg_attr(feature = "async-trait", async_trait::async_trait)]
 impl Controller<TextChange, BufferUpdate> for BufferController {}
 
 impl AsyncSender<TextChange> for BufferController {
+	/// Send a change, failing with [`ControllerError::ReadOnly`] if the current user may not
+	/// edit this buffer.
 	fn send(&self, op: TextChange) -> ControllerResult<()> {
-		self.0.ops_in.send(op)?;
+		if self.is_read_only() {
+			return Err(ControllerError::ReadOnly);
+		}
+		self.0.ops_in.send(EditorOp::Change(op))?;
 		Ok(())
 	}
 }
@@ -6,6 +6,9 @@
 /// controller worker implementation
 pub(crate) mod worker;
 
+/// line ending translation between buffer and editor
+pub(crate) mod translation;
+
 /// buffer controller implementation
 pub mod controller;
 pub use controller::BufferController as Controller;
@@ -0,0 +1,137 @@
+//! Translation between a buffer and an editor showing every line break of it with the same line
+//! ending, see [`super::Controller::translate_line_endings`].
+//!
+//! Positions are counted in characters, like [`TextChange`] indices.
+
+use crate::api::{LineEnding, TextChange};
+
+/// Length in characters of every item of given text, grouping each line break in a single one,
+/// along with whether the item is a line break.
+fn items(text: &str) -> impl Iterator<Item = (usize, bool)> + '_ {
+	let mut chars = text.chars().peekable();
+	std::iter::from_fn(move || match chars.next()? {
+		'\r' if chars.next_if_eq(&'\n').is_some() => Some((2, true)),
+		'\r' | '\n' => Some((1, true)),
+		_ => Some((1, false)),
+	})
+}
+
+/// Position shown by the editor for given position in the buffer, whose content is `text`.
+///
+/// Positions within a line break are moved to its start.
+pub(crate) fn to_editor(local: LineEnding, text: &str, pos: usize) -> usize {
+	let width = local.sequence().len();
+	let (mut buffer, mut editor) = (0, 0);
+	for (len, line_break) in items(text) {
+		if buffer + len > pos {
+			break;
+		}
+		buffer += len;
+		editor += if line_break { width } else { 1 };
+	}
+	editor
+}
+
+/// Position in the buffer, whose content is `text`, for given position shown by the editor.
+///
+/// Positions within a line break are moved to its start, or to its end if `after`.
+pub(crate) fn to_buffer(local: LineEnding, text: &str, pos: usize, after: bool) -> usize {
+	let width = local.sequence().len();
+	let (mut buffer, mut editor) = (0, 0);
+	for (len, line_break) in items(text) {
+		if editor >= pos {
+			break;
+		}
+		let shown = if line_break { width } else { 1 };
+		if editor + shown > pos {
+			return if after { buffer + len } else { buffer };
+		}
+		buffer += len;
+		editor += shown;
+	}
+	buffer
+}
+
+/// Range in the buffer, whose content is `text`, for given range shown by the editor.
+///
+/// Line breaks the range starts or ends within are included, unless it's empty.
+pub(crate) fn range_to_buffer(
+	local: LineEnding,
+	text: &str,
+	start: usize,
+	end: usize,
+) -> (usize, usize) {
+	let buffer_start = to_buffer(local, text, start, false);
+	if end <= start {
+		return (buffer_start, buffer_start);
+	}
+	(buffer_start, to_buffer(local, text, end, true))
+}
+
+/// Change turning `before` into `after`, both as shown by the editor, starting at `pos` unless
+/// the texts differ earlier.
+///
+/// Unlike [`TextChange::diff`], an empty change at `pos` is returned if they are the same.
+pub(crate) fn change(before: &str, after: &str, pos: usize) -> TextChange {
+	let before_len = before.chars().count();
+	let after_len = after.chars().count();
+	let prefix = before
+		.chars()
+		.zip(after.chars())
+		.take(pos)
+		.take_while(|(a, b)| a == b)
+		.count();
+	let suffix = before
+		.chars()
+		.rev()
+		.zip(after.chars().rev())
+		.take(std::cmp::min(before_len, after_len) - prefix)
+		.take_while(|(a, b)| a == b)
+		.count();
+	TextChange {
+		start_idx: prefix as u32,
+		end_idx: (before_len - suffix) as u32,
+		content: after
+			.chars()
+			.skip(prefix)
+			.take(after_len - suffix - prefix)
+			.collect(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn positions_skip_over_translated_line_breaks() {
+		let text = "a\nb\r\nc\rd";
+		// shown as "a\r\nb\r\nc\r\nd"
+		assert_eq!(to_editor(LineEnding::Crlf, text, 2), 3);
+		assert_eq!(to_editor(LineEnding::Crlf, text, 6), 7);
+		assert_eq!(to_editor(LineEnding::Crlf, text, 8), 10);
+		assert_eq!(to_buffer(LineEnding::Crlf, text, 3, false), 2);
+		assert_eq!(to_buffer(LineEnding::Crlf, text, 7, false), 6);
+		assert_eq!(to_buffer(LineEnding::Crlf, text, 10, false), 8);
+		// shown as "a\nb\nc\nd"
+		assert_eq!(to_editor(LineEnding::Lf, text, 5), 4);
+		assert_eq!(to_buffer(LineEnding::Lf, text, 4, false), 5);
+	}
+
+	#[test]
+	fn positions_within_line_breaks_move_to_their_boundaries() {
+		assert_eq!(to_editor(LineEnding::Lf, "a\r\nb", 2), 1);
+		assert_eq!(to_buffer(LineEnding::Crlf, "a\nb", 2, false), 1);
+		assert_eq!(to_buffer(LineEnding::Crlf, "a\nb", 2, true), 2);
+		assert_eq!(range_to_buffer(LineEnding::Crlf, "a\nb", 2, 2), (1, 1));
+		assert_eq!(range_to_buffer(LineEnding::Crlf, "a\nb", 2, 4), (1, 3));
+	}
+
+	#[test]
+	fn change_starts_at_given_position_when_ambiguous() {
+		let change = change("aa", "aaa", 1);
+		assert_eq!(change.span(), 1..1);
+		assert_eq!(change.content, "a");
+		assert_eq!(super::change("ab", "ab", 1).span(), 1..1);
+	}
+}
@@ -1,44 +1,82 @@
 use std::sync::Arc;
 
+use diamond_types::causalgraph::agent_assignment::remote_ids::RemoteVersion;
+use diamond_types::list::operation::OpKind;
 use diamond_types::list::{Branch, OpLog};
 use diamond_types::LocalVersion;
 use tokio::sync::{mpsc, oneshot, watch};
 use tonic::Streaming;
 use uuid::Uuid;
 
-use crate::api::controller::ControllerCallback;
+use crate::api::presence::Tracked;
+use crate::api::Annotation;
+use crate::api::BufferMetadata;
 use crate::api::BufferUpdate;
+use crate::api::LineEnding;
+use crate::api::Role;
 use crate::api::TextChange;
+use crate::dispatch::{Dispatcher, Strategy};
 use crate::ext::IgnorableError;
+use crate::protocol::annotations as proto;
 
 use codemp_proto::buffer::{BufferEvent, Operation};
 
-use super::controller::{BufferController, BufferControllerInner};
+use super::controller::{AnnotationOp, BufferController, BufferControllerInner, EditorOp};
+use super::translation;
+
+/// Connection to the annotations of a buffer, see [`crate::protocol::annotations`].
+pub(crate) struct AnnotationLink {
+	/// Users of the workspace, to tell who authored each annotation.
+	pub(crate) users: Arc<dashmap::DashMap<Uuid, Tracked>>,
+	pub(crate) tx: mpsc::Sender<proto::Change>,
+	pub(crate) rx: Streaming<proto::Event>,
+}
 
 struct BufferWorker {
-	agent_id: u32,
+	agent_id: Option<u32>,
 	path: String,
 	latest_version: watch::Sender<diamond_types::LocalVersion>,
 	local_version: watch::Sender<diamond_types::LocalVersion>,
 	ack_rx: mpsc::UnboundedReceiver<LocalVersion>,
-	ops_in: mpsc::UnboundedReceiver<TextChange>,
+	ops_in: mpsc::UnboundedReceiver<EditorOp>,
 	poller: mpsc::UnboundedReceiver<oneshot::Sender<()>>,
 	pollers: Vec<oneshot::Sender<()>>,
 	content_checkout: mpsc::Receiver<oneshot::Sender<String>>,
+	branch_checkout: mpsc::Receiver<oneshot::Sender<String>>,
 	delta_req: mpsc::Receiver<(LocalVersion, oneshot::Sender<Option<BufferUpdate>>)>,
 	controller: std::sync::Weak<BufferControllerInner>,
-	callback: watch::Receiver<Option<ControllerCallback<BufferController>>>,
+	callback: Dispatcher<BufferController>,
 	oplog: OpLog,
 	branch: Branch,
 	timer: Timer,
+	annotations_req: mpsc::Receiver<oneshot::Sender<Vec<Annotation>>>,
+	annotation_callback: Dispatcher<BufferController>,
+	annotation_tx: Option<mpsc::Sender<proto::Change>>,
+	annotations: Vec<proto::Annotation>,
+	users: Arc<dashmap::DashMap<Uuid, Tracked>>,
+	locate_req: mpsc::Receiver<(Vec<proto::Anchor>, oneshot::Sender<Vec<Option<(u32, u32)>>>)>,
+	metadata: watch::Sender<BufferMetadata>,
+	metadata_callback: Dispatcher<BufferController>,
+	normalize: watch::Receiver<bool>,
+	translate: watch::Receiver<Option<LineEnding>>,
 }
 
 impl BufferController {
+	/// Spawn a worker for a buffer, editing it as the given user or only following it if none.
+	///
+	/// Annotations are unavailable without an [`AnnotationLink`], and metadata is never announced
+	/// without its stream, for servers not supporting them.
+	#[allow(clippy::too_many_arguments)] // internal, called from a single place
 	pub(crate) fn spawn(
-		user_id: Uuid,
+		user_id: Option<Uuid>,
 		path: &str,
+		hash_period: u32,
+		callbacks: Strategy,
+		role: watch::Receiver<Role>,
 		tx: mpsc::Sender<Operation>,
 		rx: Streaming<BufferEvent>,
+		annotations: Option<AnnotationLink>,
+		metadata: Option<Streaming<crate::protocol::metadata::BufferMetadata>>,
 	) -> Self {
 		let init = diamond_types::LocalVersion::default();
 
@@ -48,12 +86,20 @@ impl BufferController {
 		let (ack_tx, ack_rx) = mpsc::unbounded_channel();
 
 		let (req_tx, req_rx) = mpsc::channel(1);
+		let (branch_tx, branch_rx) = mpsc::channel(1);
 		let (recv_tx, recv_rx) = mpsc::channel(1);
 		let (cb_tx, cb_rx) = watch::channel(None);
+		let (ann_req_tx, ann_req_rx) = mpsc::channel(1);
+		let (ann_cb_tx, ann_cb_rx) = watch::channel(None);
+		let (locate_tx, locate_rx) = mpsc::channel(1);
+		let (metadata_tx, metadata_rx) = watch::channel(BufferMetadata::default());
+		let (meta_cb_tx, meta_cb_rx) = watch::channel(None);
+		let (normalize_tx, normalize_rx) = watch::channel(false);
+		let (translate_tx, translate_rx) = watch::channel(None);
 
 		let (poller_tx, poller_rx) = mpsc::unbounded_channel();
 		let mut oplog = OpLog::new();
-		let agent_id = oplog.get_or_create_agent_id(&user_id.to_string());
+		let agent_id = user_id.map(|id| oplog.get_or_create_agent_id(&id.to_string()));
 
 		let controller = Arc::new(BufferControllerInner {
 			name: path.to_string(),
@@ -62,12 +108,26 @@ impl BufferController {
 			ops_in: opin_tx,
 			poller: poller_tx,
 			content_request: req_tx,
+			branch_request: branch_tx,
 			delta_request: recv_tx,
 			callback: cb_tx,
 			ack_tx,
+			role,
+			spectator: agent_id.is_none(),
+			annotations_request: ann_req_tx,
+			annotation_callback: ann_cb_tx,
+			locate_request: locate_tx,
+			metadata: metadata_rx,
+			metadata_callback: meta_cb_tx,
+			normalize: normalize_tx,
+			translate: translate_tx,
 		});
 
 		let weak = Arc::downgrade(&controller);
+		let (users, annotation_tx, annotation_rx) = match annotations {
+			Some(link) => (link.users, Some(link.tx), Some(link.rx)),
+			None => (Default::default(), None, None),
+		};
 
 		let worker = BufferWorker {
 			agent_id,
@@ -80,14 +140,27 @@ impl BufferController {
 			pollers: Vec::new(),
 			controller: weak,
 			content_checkout: req_rx,
+			branch_checkout: branch_rx,
 			delta_req: recv_rx,
-			callback: cb_rx,
-			oplog: OpLog::new(),
+			callback: Dispatcher::new(callbacks, cb_rx),
+			oplog,
 			branch: Branch::new(),
-			timer: Timer::new(10), // TODO configurable!
+			timer: Timer::new(hash_period),
+			annotations_req: ann_req_rx,
+			annotation_callback: Dispatcher::new(callbacks, ann_cb_rx),
+			annotation_tx,
+			annotations: Vec::new(),
+			users,
+			locate_req: locate_rx,
+			metadata: metadata_tx,
+			metadata_callback: Dispatcher::new(callbacks, meta_cb_rx),
+			normalize: normalize_rx,
+			translate: translate_rx,
 		};
 
-		tokio::spawn(async move { BufferController::work(worker, tx, rx).await });
+		tokio::spawn(async move {
+			BufferController::work(worker, tx, rx, annotation_rx, metadata).await
+		});
 
 		BufferController(controller)
 	}
@@ -96,6 +169,8 @@ impl BufferController {
 		mut worker: BufferWorker,
 		tx: mpsc::Sender<Operation>,
 		mut rx: Streaming<BufferEvent>,
+		mut annotation_rx: Option<Streaming<proto::Event>>,
+		mut metadata_rx: Option<Streaming<crate::protocol::metadata::BufferMetadata>>,
 	) {
 		tracing::debug!("controller worker started");
 		loop {
@@ -121,10 +196,15 @@ impl BufferController {
 					},
 				},
 
-				// received a text change from editor
+				// received a text change or another request from editor
 				res = worker.ops_in.recv() => match res {
 					None => break tracing::debug!("stopping: editor closed channel"),
-					Some(change) => worker.handle_editor_change(change, &tx).await,
+					Some(EditorOp::Change(change)) => worker.handle_editor_change(change, &tx).await,
+					Some(EditorOp::Annotation(op)) => worker.handle_annotation_op(op).await,
+					Some(EditorOp::Anchor(start, end, tx)) => {
+						let anchor = worker.anchor(start, end);
+						tx.send(anchor).unwrap_or_warn("anchor request dropped");
+					},
 				},
 
 				// received a message from server: add to oplog and update latest version (+unlock pollers)
@@ -134,6 +214,69 @@ impl BufferController {
 					Ok(Some(change)) => if worker.handle_server_change(change).await { break },
 				},
 
+				// received an annotation change from the server
+				res = async {
+					match annotation_rx.as_mut() {
+						Some(rx) => rx.message().await,
+						None => std::future::pending().await,
+					}
+				} => match res {
+					Ok(Some(event)) => worker.handle_annotation_event(event),
+					Ok(None) => {
+						tracing::info!("annotations of buffer {} closed", worker.path);
+						annotation_rx = None;
+					},
+					Err(e) => {
+						tracing::warn!("error receiving annotations for buffer {}: {e}", worker.path);
+						annotation_rx = None;
+					},
+				},
+
+				// received new metadata from the server
+				res = async {
+					match metadata_rx.as_mut() {
+						Some(rx) => rx.message().await,
+						None => std::future::pending().await,
+					}
+				} => match res {
+					Ok(Some(metadata)) => worker.handle_metadata(metadata),
+					Ok(None) => {
+						tracing::info!("metadata of buffer {} closed", worker.path);
+						metadata_rx = None;
+					},
+					Err(e) => {
+						tracing::warn!("error receiving metadata for buffer {}: {e}", worker.path);
+						metadata_rx = None;
+					},
+				},
+
+				// received a request for the current annotations
+				res = worker.annotations_req.recv() => match res {
+					None => break tracing::error!("no more active controllers: can't list annotations"),
+					Some(tx) => {
+						let annotations = worker.annotations();
+						tx.send(annotations).unwrap_or_warn("annotations request dropped");
+					},
+				},
+
+				// another controller needs to know where anchored ranges currently lie
+				res = worker.locate_req.recv() => match res {
+					None => break tracing::error!("no more active controllers: can't locate anchors"),
+					Some((anchors, tx)) => {
+						let current = worker.branch.local_version();
+						let shown = worker.shown_position();
+						let ranges = anchors
+							.iter()
+							.map(|anchor| {
+								worker
+									.move_anchor(anchor, &current)
+									.map(|(start, end)| (shown(start) as u32, shown(end) as u32))
+							})
+							.collect();
+						tx.send(ranges).unwrap_or_warn("locate request dropped");
+					},
+				},
+
 				// controller is ready to apply change and recv(), calculate it and send it back
 				res = worker.delta_req.recv() => match res {
 					None => break tracing::error!("no more active controllers: can't send changes"),
@@ -145,9 +288,18 @@ impl BufferController {
 					None => break tracing::error!("no more active controllers: can't update content"),
 					Some(tx) => {
 						worker.branch.merge(&worker.oplog, worker.oplog.local_version_ref());
-						let content = worker.branch.content().to_string();
+						let content = worker.shown_content();
 						tx.send(content).unwrap_or_warn("checkout request dropped");
 					},
+				},
+
+				// received a request for the content the editor should currently have
+				res = worker.branch_checkout.recv() => match res {
+					None => break tracing::error!("no more active controllers: can't verify content"),
+					Some(tx) => {
+						let content = worker.shown_content();
+						tx.send(content).unwrap_or_warn("branch checkout request dropped");
+					},
 				}
 			}
 		}
@@ -158,6 +310,24 @@ impl BufferController {
 
 impl BufferWorker {
 	async fn handle_editor_change(&mut self, change: TextChange, tx: &mpsc::Sender<Operation>) {
+		let Some(agent_id) = self.agent_id else {
+			return tracing::warn!("dropping change sent to spectated buffer {}", self.path);
+		};
+		let change = match self.local_line_ending() {
+			None => change,
+			Some(_) => {
+				let (start, end) =
+					self.branch_range(change.start_idx as usize, change.end_idx as usize);
+				TextChange {
+					start_idx: start as u32,
+					end_idx: end as u32,
+					content: match self.metadata.borrow().line_ending {
+						Some(canonical) => canonical.normalize(&change.content).into_owned(),
+						None => change.content,
+					},
+				}
+			}
+		};
 		let last_ver = self.oplog.local_version();
 		// clip to buffer extents
 		let clip_start = change.start_idx as usize;
@@ -170,16 +340,15 @@ impl BufferWorker {
 
 		// in case we have a "replace" span
 		if change.is_delete() {
-			self.branch.delete_without_content(
-				&mut self.oplog,
-				self.agent_id,
-				clip_start..clip_end,
-			);
+			self.branch
+				.delete_without_content(&mut self.oplog, agent_id, clip_start..clip_end);
 		}
 
+		let mut normalized = false;
 		if change.is_insert() {
 			self.branch
-				.insert(&mut self.oplog, self.agent_id, clip_start, &change.content);
+				.insert(&mut self.oplog, agent_id, clip_start, &change.content);
+			normalized = self.normalize_inserted(agent_id, clip_start, &change.content);
 		}
 
 		if change.is_delete() || change.is_insert() {
@@ -195,6 +364,82 @@ impl BufferWorker {
 				.send(self.branch.local_version())
 				.unwrap_or_warn("failed to update local version!");
 		}
+
+		// the editor has yet to receive the replaced line endings
+		if normalized {
+			for tx in self.pollers.drain(..) {
+				tx.send(()).unwrap_or_warn("could not wake up poller");
+			}
+			if let Some(controller) = self.controller.upgrade() {
+				self.callback.dispatch(BufferController(controller));
+			}
+		}
+	}
+
+	/// Replace foreign line endings of text just inserted at given position, if requested,
+	/// returning whether any was replaced.
+	///
+	/// The replacement is made on a copy of the editor branch, so that the editor receives it
+	/// later like any other change it didn't make.
+	fn normalize_inserted(&mut self, agent_id: u32, pos: usize, content: &str) -> bool {
+		if !*self.normalize.borrow() {
+			return false;
+		}
+		let Some(line_ending) = self.metadata.borrow().line_ending else {
+			return false;
+		};
+		let std::borrow::Cow::Owned(normalized) = line_ending.normalize(content) else {
+			return false;
+		};
+		let mut branch = self.branch.clone();
+		let len = content.chars().count();
+		branch.delete_without_content(&mut self.oplog, agent_id, pos..pos + len);
+		branch.insert(&mut self.oplog, agent_id, pos, &normalized);
+		true
+	}
+
+	/// Line ending of the editor, if line endings are translated for it.
+	fn local_line_ending(&self) -> Option<LineEnding> {
+		*self.translate.borrow()
+	}
+
+	/// Content of the editor branch, as shown by the editor.
+	fn shown_content(&self) -> String {
+		let content = self.branch.content().to_string();
+		match self.local_line_ending() {
+			Some(local) => local.normalize(&content).into_owned(),
+			None => content,
+		}
+	}
+
+	/// Range of the editor branch for given range shown by the editor.
+	fn branch_range(&self, start: usize, end: usize) -> (usize, usize) {
+		match self.local_line_ending() {
+			Some(local) => {
+				let content = self.branch.content().to_string();
+				translation::range_to_buffer(local, &content, start, end)
+			}
+			None => (start, end),
+		}
+	}
+
+	/// Moves positions of the editor branch, as it is now, to where the editor shows them.
+	fn shown_position(&self) -> impl Fn(usize) -> usize {
+		let translated = self
+			.local_line_ending()
+			.map(|local| (local, self.branch.content().to_string()));
+		move |pos| match &translated {
+			Some((local, content)) => translation::to_editor(*local, content, pos),
+			None => pos,
+		}
+	}
+
+	fn handle_metadata(&mut self, metadata: crate::protocol::metadata::BufferMetadata) {
+		self.metadata.send_replace(metadata.into());
+		if let Some(controller) = self.controller.upgrade() {
+			self.metadata_callback
+				.dispatch(BufferController(controller));
+		}
 	}
 
 	async fn handle_server_change(&mut self, change: BufferEvent) -> bool {
@@ -208,9 +453,7 @@ impl BufferWorker {
 					for tx in self.pollers.drain(..) {
 						tx.send(()).unwrap_or_warn("could not wake up poller");
 					}
-					if let Some(cb) = self.callback.borrow().as_ref() {
-						cb.call(BufferController(controller)); // TODO should we run this on another task/thread?
-					}
+					self.callback.dispatch(BufferController(controller));
 					false
 				}
 				Err(e) => {
@@ -231,6 +474,9 @@ impl BufferWorker {
 			.iter_xf_operations_from(&last_ver, self.oplog.local_version_ref())
 			.next()
 		{
+			let local = self.local_line_ending();
+			let before = local.map(|_| self.branch.content().to_string());
+
 			// x.0.start should always be after lastver!
 			// this step_ver will be the version after we apply the operation
 			// we give it to the controller so that he knows where it's at.
@@ -239,12 +485,12 @@ impl BufferWorker {
 			let new_local_v = self.branch.local_version();
 
 			let hash = if self.timer.step() {
-				Some(crate::ext::hash(self.branch.content().to_string()))
+				Some(crate::ext::hash(self.shown_content()))
 			} else {
 				None
 			};
 
-			let tc = match dtop.kind {
+			let mut tc = match dtop.kind {
 				diamond_types::list::operation::OpKind::Ins => {
 					if dtop.end() - dtop.start() != dtop.content_as_str().unwrap_or_default().len()
 					{
@@ -282,6 +528,15 @@ impl BufferWorker {
 					},
 				},
 			};
+
+			// line breaks may change length or merge once shown, compare what the editor sees
+			if let (Some(local), Some(before)) = (local, before) {
+				let after = self.branch.content().to_string();
+				let pos = translation::to_editor(local, &before, dtop.start());
+				tc.change =
+					translation::change(&local.normalize(&before), &local.normalize(&after), pos);
+			}
+
 			self.local_version
 				.send(new_local_v)
 				.unwrap_or_warn("could not update local version");
@@ -292,14 +547,168 @@ impl BufferWorker {
 				.unwrap_or_warn("could not update ops channel -- is controller dead?");
 		}
 	}
+
+	async fn handle_annotation_op(&mut self, op: AnnotationOp) {
+		let Some(ref tx) = self.annotation_tx else {
+			return tracing::warn!(
+				"dropping annotation change: unsupported for buffer {}",
+				self.path
+			);
+		};
+		let change = match op {
+			AnnotationOp::Create {
+				id,
+				start_idx,
+				end_idx,
+				text,
+			} => proto::change::Change::Create(proto::Note {
+				id,
+				text,
+				anchor: self.anchor(start_idx, end_idx),
+			}),
+			AnnotationOp::Resolve(id) => proto::change::Change::Resolve(id),
+			AnnotationOp::Delete(id) => proto::change::Change::Delete(id),
+		};
+		tx.send(proto::Change {
+			change: Some(change),
+		})
+		.await
+		.unwrap_or_warn("failed to send annotation change");
+	}
+
+	/// Anchor given range of what the editor is currently showing.
+	fn anchor(&self, start_idx: u32, end_idx: u32) -> proto::Anchor {
+		let (start, end) = self.branch_range(start_idx as usize, end_idx as usize);
+		let version = self
+			.branch
+			.local_version()
+			.iter()
+			.map(|lv| {
+				let RemoteVersion(agent, seq) =
+					self.oplog.cg.agent_assignment.local_to_remote_version(*lv);
+				proto::RemoteVersion {
+					agent: agent.to_string(),
+					seq: seq as u64,
+				}
+			})
+			.collect();
+		proto::Anchor {
+			version,
+			start: start as u64,
+			end: end.max(start) as u64,
+		}
+	}
+
+	fn handle_annotation_event(&mut self, event: proto::Event) {
+		match event.event {
+			None => return tracing::warn!("received empty annotation event"),
+			Some(proto::event::Event::Update(annotation)) => {
+				match self.annotations.iter_mut().find(|a| a.id == annotation.id) {
+					Some(known) => *known = annotation,
+					None => self.annotations.push(annotation),
+				}
+			}
+			Some(proto::event::Event::Delete(id)) => self.annotations.retain(|a| a.id != id),
+		}
+		if let Some(controller) = self.controller.upgrade() {
+			self.annotation_callback
+				.dispatch(BufferController(controller));
+		}
+	}
+
+	/// Known annotations, with their anchors moved to the version of the editor branch.
+	fn annotations(&self) -> Vec<Annotation> {
+		let current = self.branch.local_version();
+		let shown = self.shown_position();
+		self.annotations
+			.iter()
+			.filter_map(|annotation| {
+				let (start, end) = self.move_anchor(&annotation.anchor, &current)?;
+				let author = self
+					.users
+					.get(&annotation.author())
+					.map(|tracked| tracked.user.name.clone())
+					.unwrap_or_default();
+				Some(Annotation {
+					id: annotation.id.clone(),
+					author,
+					text: annotation.text.clone(),
+					start_idx: shown(start) as u32,
+					end_idx: shown(end) as u32,
+					resolved: annotation.resolved,
+				})
+			})
+			.collect()
+	}
+
+	/// Find where an anchored range lies at given version, by moving it through every operation
+	/// since the version it refers to. Text inserted at its boundaries stays outside of it.
+	///
+	/// Returns `None` if the anchor refers to operations not merged into given version yet.
+	fn move_anchor(
+		&self,
+		anchor: &proto::Anchor,
+		current: &LocalVersion,
+	) -> Option<(usize, usize)> {
+		let mut version = Vec::with_capacity(anchor.version.len());
+		for rv in &anchor.version {
+			let lv = self
+				.oplog
+				.cg
+				.agent_assignment
+				.try_remote_to_local_version(RemoteVersion(&rv.agent, rv.seq as usize))
+				.ok()?;
+			version.push(lv);
+		}
+		let version: LocalVersion = version.into_iter().collect();
+		if self.oplog.version_union(&version, current) != *current {
+			return None;
+		}
+
+		let (mut start, mut end) = (anchor.start as usize, anchor.end as usize);
+		for (_, op) in self.oplog.iter_xf_operations_from(&version, current) {
+			let Some(op) = op else { continue }; // concurrent deletes of the same text
+			let (op_start, op_end) = (op.start(), op.end());
+			match op.kind {
+				OpKind::Ins => {
+					let len = op_end - op_start;
+					if op_start <= start {
+						start += len;
+					}
+					if op_start < end {
+						end += len;
+					}
+				}
+				OpKind::Del => {
+					let shift = |pos: usize| {
+						if pos <= op_start {
+							pos
+						} else if pos >= op_end {
+							pos - (op_end - op_start)
+						} else {
+							op_start
+						}
+					};
+					start = shift(start);
+					end = shift(end);
+				}
+			}
+			end = end.max(start);
+		}
+		Some((start, end))
+	}
 }
 
+/// Fires once every `period` steps, never if `period` is zero.
 struct Timer(u32, u32);
 impl Timer {
 	fn new(period: u32) -> Self {
 		Timer(0, period)
 	}
 	fn step(&mut self) -> bool {
+		if self.1 == 0 {
+			return false;
+		}
 		self.0 += 1;
 		if self.0 >= self.1 {
 			self.0 = 0;
@@ -0,0 +1,116 @@
+//! ### Chat Controller
+//! A [Controller] implementation for [crate::api::Message]s exchanged in a [crate::Workspace]
+
+use std::{collections::VecDeque, sync::Arc};
+
+use tokio::sync::{mpsc, oneshot, watch};
+
+use crate::{
+	api::{
+		controller::{AsyncReceiver, AsyncSender, ControllerCallback},
+		Controller, Draft, Message,
+	},
+	errors::ControllerResult,
+	ext::ControllerStream,
+	protocol::chat as proto,
+};
+
+/// A [Controller] for asynchronously sending [Draft]s and receiving [Message]s.
+///
+/// An unique [ChatController] exists for each [crate::Workspace], obtained with
+/// [crate::Workspace::attach_chat]. Besides delivering new messages, it keeps a bounded history
+/// of the last ones received, see [crate::api::Tuning::chat_history].
+#[derive(Debug, Clone)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass)]
+#[cfg_attr(feature = "js", napi_derive::napi)]
+pub struct ChatController(pub(crate) Arc<ChatControllerInner>);
+
+impl ChatController {
+	/// Get the last messages received, oldest first, including those already consumed.
+	pub fn history(&self) -> Vec<Message> {
+		self.0
+			.history
+			.lock()
+			.expect("mutex poisoned")
+			.iter()
+			.cloned()
+			.collect()
+	}
+
+	/// Get the last messages received in threads about given buffer, oldest first.
+	pub fn thread_history(&self, buffer: &str) -> Vec<Message> {
+		self.0
+			.history
+			.lock()
+			.expect("mutex poisoned")
+			.iter()
+			.filter(|msg| msg.is_about(buffer))
+			.cloned()
+			.collect()
+	}
+
+	/// Whether the controller worker stopped, for example because the server closed the chat.
+	pub(crate) fn is_stopped(&self) -> bool {
+		self.0.op.is_closed()
+	}
+
+	/// Turn this controller into a [`tokio_stream::Stream`] of [`Message`]s.
+	///
+	/// The stream ends once the controller worker stops, see [`ControllerStream`].
+	pub fn into_stream(self) -> ControllerStream<Message> {
+		ControllerStream::new(move || {
+			let controller = self.clone();
+			Box::pin(async move { controller.recv().await })
+		})
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct ChatControllerInner {
+	pub(crate) op: mpsc::UnboundedSender<proto::Draft>,
+	pub(crate) stream: mpsc::Sender<oneshot::Sender<Option<Message>>>,
+	pub(crate) poll: mpsc::UnboundedSender<oneshot::Sender<()>>,
+	pub(crate) callback: watch::Sender<Option<ControllerCallback<ChatController>>>,
+	pub(crate) history: std::sync::Mutex<VecDeque<Message>>,
+}
+
+#[cfg_attr(feature = "async-trait", async_trait::async_trait)]
+impl Controller<Draft, Message> for ChatController {}
+
+#[cfg_attr(feature = "async-trait", async_trait::async_trait)]
+impl AsyncSender<Draft> for ChatController {
+	fn send(&self, draft: Draft) -> ControllerResult<()> {
+		Ok(self.0.op.send(proto::Draft {
+			text: draft.text,
+			anchor: draft.thread.map(proto::Anchor::from),
+		})?)
+	}
+}
+
+#[cfg_attr(feature = "async-trait", async_trait::async_trait)]
+impl AsyncReceiver<Message> for ChatController {
+	async fn try_recv(&self) -> ControllerResult<Option<Message>> {
+		let (tx, rx) = oneshot::channel();
+		self.0.stream.send(tx).await?;
+		Ok(rx.await?)
+	}
+
+	async fn poll(&self) -> ControllerResult<()> {
+		let (tx, rx) = oneshot::channel();
+		self.0.poll.send(tx)?;
+		rx.await?;
+		Ok(())
+	}
+
+	fn callback(&self, cb: impl Into<ControllerCallback<ChatController>>) {
+		if self.0.callback.send(Some(cb.into())).is_err() {
+			tracing::error!("no active chat worker to run registered callback!");
+		}
+	}
+
+	fn clear_callback(&self) {
+		if self.0.callback.send(None).is_err() {
+			tracing::warn!("no active chat worker to clear callback");
+		}
+	}
+}
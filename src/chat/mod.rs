@@ -0,0 +1,10 @@
+//! ### Chat
+//! Users in a [crate::Workspace] can exchange short text messages, either with everyone or in
+//! threads about a range of a buffer.
+
+/// chat worker implementation
+pub(crate) mod worker;
+
+/// chat controller implementation
+pub mod controller;
+pub use controller::ChatController as Controller;
@@ -0,0 +1,139 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use tokio::sync::{mpsc, oneshot, watch};
+use tonic::Streaming;
+use uuid::Uuid;
+
+use crate::{
+	api::{presence::Tracked, Message},
+	dispatch::{Dispatcher, Strategy},
+	ext::IgnorableError,
+	protocol::chat as proto,
+};
+
+use super::controller::{ChatController, ChatControllerInner};
+
+struct ChatWorker {
+	op: mpsc::UnboundedReceiver<proto::Draft>,
+	map: Arc<dashmap::DashMap<Uuid, Tracked>>,
+	stream: mpsc::Receiver<oneshot::Sender<Option<Message>>>,
+	poll: mpsc::UnboundedReceiver<oneshot::Sender<()>>,
+	pollers: Vec<oneshot::Sender<()>>,
+	store: VecDeque<Message>,
+	capacity: usize,
+	controller: std::sync::Weak<ChatControllerInner>,
+	callback: Dispatcher<ChatController>,
+}
+
+impl ChatController {
+	pub(crate) fn spawn(
+		user_map: Arc<dashmap::DashMap<Uuid, Tracked>>,
+		capacity: usize,
+		callbacks: Strategy,
+		tx: mpsc::Sender<proto::Draft>,
+		rx: Streaming<proto::Message>,
+	) -> Self {
+		let (op_tx, op_rx) = mpsc::unbounded_channel();
+		let (stream_tx, stream_rx) = mpsc::channel(1);
+		let (cb_tx, cb_rx) = watch::channel(None);
+		let (poll_tx, poll_rx) = mpsc::unbounded_channel();
+		let controller = Arc::new(ChatControllerInner {
+			op: op_tx,
+			stream: stream_tx,
+			callback: cb_tx,
+			poll: poll_tx,
+			history: std::sync::Mutex::new(VecDeque::with_capacity(capacity)),
+		});
+
+		let weak = Arc::downgrade(&controller);
+
+		let worker = ChatWorker {
+			op: op_rx,
+			map: user_map,
+			stream: stream_rx,
+			store: VecDeque::default(),
+			capacity,
+			controller: weak,
+			callback: Dispatcher::new(callbacks, cb_rx),
+			poll: poll_rx,
+			pollers: Vec::new(),
+		};
+
+		tokio::spawn(async move { ChatController::work(worker, tx, rx).await });
+
+		ChatController(controller)
+	}
+
+	async fn work(
+		mut worker: ChatWorker,
+		tx: mpsc::Sender<proto::Draft>,
+		mut rx: Streaming<proto::Message>,
+	) {
+		loop {
+			tracing::debug!("chat worker polling");
+			if worker.controller.upgrade().is_none() {
+				break;
+			}; // clean exit: all controllers dropped
+			tokio::select! {
+				biased;
+
+				// new poller
+				Some(poller) = worker.poll.recv() => worker.pollers.push(poller),
+
+				// client wrote a message
+				Some(op) = worker.op.recv() => {
+					tracing::debug!("received chat message from editor");
+					tx.send(op).await.unwrap_or_warn("could not send chat message");
+				},
+
+				// server relayed a message
+				res = rx.message() => match res {
+					Err(e) => break tracing::warn!("error receiving chat messages: {e}"),
+					Ok(None) => break tracing::info!("chat stream closed by server"),
+					Ok(Some(msg)) => match worker.controller.upgrade() {
+						None => break, // clean exit, just weird that we got it here
+						Some(controller) => worker.handle_message(controller, msg),
+					},
+				},
+
+				// client wants to get next chat message
+				Some(tx) = worker.stream.recv() => tx.send(worker.store.pop_front())
+					.unwrap_or_warn("client gave up receiving"),
+
+				else => break,
+			}
+		}
+		tracing::debug!("chat worker stopping");
+	}
+}
+
+impl ChatWorker {
+	fn handle_message(&mut self, controller: Arc<ChatControllerInner>, msg: proto::Message) {
+		tracing::debug!("received chat message from server");
+		let user = self
+			.map
+			.get(&msg.uuid())
+			.map(|tracked| tracked.user.name.clone())
+			.unwrap_or_default();
+		let message = Message {
+			user,
+			text: msg.text,
+			timestamp: msg.timestamp,
+			thread: msg.anchor.map(Into::into),
+		};
+
+		{
+			let mut history = controller.history.lock().expect("mutex poisoned");
+			if history.len() >= self.capacity {
+				history.pop_front();
+			}
+			history.push_back(message.clone());
+		}
+		self.store.push_back(message);
+		for tx in self.pollers.drain(..) {
+			tx.send(())
+				.unwrap_or_warn("poller dropped before unblocking");
+		}
+		self.callback.dispatch(ChatController(controller));
+	}
+}
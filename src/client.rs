@@ -4,22 +4,23 @@
 use std::sync::Arc;
 
 use dashmap::DashMap;
-use tonic::{
-	service::interceptor::InterceptedService,
-	transport::{Channel, Endpoint},
-};
 
 use crate::{
-	api::User,
+	api::{Member, Role, User},
+	auth::{AuthProvider, ConfigProvider},
 	errors::{ConnectionResult, RemoteResult},
-	ext::InternallyMutable,
 	network,
+	protocol::{
+		members::{MemberRequest, MembersRequest, RoleRequest},
+		presence::ProfileUpdate,
+	},
+	transport::Transport,
 	workspace::Workspace,
 };
 use codemp_proto::{
-	auth::{auth_client::AuthClient, LoginRequest},
-	common::{Empty, Token},
-	session::{session_client::SessionClient, InviteRequest, WorkspaceRequest},
+	auth::auth_client::AuthClient,
+	common::Empty,
+	session::{InviteRequest, WorkspaceRequest},
 };
 
 #[cfg(any(feature = "py", feature = "py-noabi"))]
@@ -29,7 +30,8 @@ use pyo3::prelude::*;
 ///
 /// It generates a new UUID and stores user credentials upon connecting.
 ///
-/// A new [`Client`] can be obtained with [`Client::connect`].
+/// A new [`Client`] can be obtained with [`Client::connect`], or with [`Client::connect_with`]
+/// to authenticate using a custom [`AuthProvider`].
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "js", napi_derive::napi)]
 #[cfg_attr(any(feature = "py", feature = "py-noabi"), pyclass)]
@@ -40,62 +42,108 @@ struct ClientInner {
 	user: User,
 	config: crate::api::Config,
 	workspaces: DashMap<String, Workspace>,
-	auth: AuthClient<Channel>,
-	session: SessionClient<InterceptedService<Channel, network::SessionInterceptor>>,
-	claims: InternallyMutable<Token>,
+	session: Arc<network::Session>,
 }
 
 impl Client {
 	/// Connect to the server, authenticate and instantiate a new [`Client`].
+	///
+	/// Credentials are taken from the given [`crate::api::Config`], see [`crate::auth`].
 	pub async fn connect(config: crate::api::Config) -> ConnectionResult<Self> {
-		// TODO move these two into network.rs
-		let channel = Endpoint::from_shared(config.endpoint())?.connect().await?;
-		let mut auth = AuthClient::new(channel.clone());
-
-		let resp = auth
-			.login(LoginRequest {
-				username: config.username.clone(),
-				password: config.password.clone(),
-			})
-			.await?
-			.into_inner();
+		let provider = ConfigProvider::from(&config);
+		Self::connect_with(config, provider).await
+	}
+
+	/// Connect to the server, authenticate with given [`AuthProvider`] and instantiate a new
+	/// [`Client`].
+	///
+	/// Credentials in the given [`crate::api::Config`] are ignored, except for `username` which is
+	/// used as display name of the current user if the provider hands out a pre-issued token
+	/// whose claims carry no `name`. The provider is kept around and asked again for credentials
+	/// whenever the session token can't be refreshed.
+	pub async fn connect_with(
+		config: crate::api::Config,
+		auth: impl AuthProvider + 'static,
+	) -> ConnectionResult<Self> {
+		Self::open(config, crate::auth::erase(auth), None).await
+	}
+
+	/// Connect to the server over given [`Transport`], authenticate and instantiate a new
+	/// [`Client`].
+	///
+	/// Every connection is opened with the transport instead of dialing `host` and `port`, and
+	/// `proxy` is ignored. TLS is layered on top only if `tls` is explicitly enabled.
+	pub async fn connect_with_transport(
+		config: crate::api::Config,
+		transport: impl Transport + 'static,
+	) -> ConnectionResult<Self> {
+		let provider = crate::auth::erase(ConfigProvider::from(&config));
+		Self::open(config, provider, Some(crate::transport::erase(transport))).await
+	}
 
-		let claims = InternallyMutable::new(resp.token);
+	async fn open(
+		config: crate::api::Config,
+		provider: crate::auth::DynProvider,
+		transport: Option<crate::transport::DynTransport>,
+	) -> ConnectionResult<Self> {
+		config
+			.validate()
+			.map_err(crate::errors::ConnectionError::InvalidConfig)?;
+		let link = network::connect(&config, transport).await?;
 
-		// TODO move this one into network.rs
-		let session =
-			SessionClient::with_interceptor(channel, network::SessionInterceptor(claims.channel()));
+		let (token, user) = network::authenticate(
+			&AuthClient::with_origin(link.channel.clone(), link.origin.clone()),
+			&provider,
+		)
+		.await?;
+		let user = match user {
+			Some(user) => user.into(),
+			// pre-issued tokens don't tell who we are, unless they carry claims about it
+			None => match network::identity(&token) {
+				Some((id, name)) => User {
+					id,
+					name: name.unwrap_or_else(|| config.username.clone()),
+				},
+				None => {
+					return Err(crate::errors::ConnectionError::Auth(
+						"pre-issued token doesn't carry the identity of its user".to_string(),
+					))
+				}
+			},
+		};
+
+		let tuning = config.tuning();
+		let session = Arc::new(network::Session::new(link, token, provider));
+		session.keep_fresh(tuning.refresh_margin(), tuning.retry_backoff());
 
 		Ok(Client(Arc::new(ClientInner {
-			user: resp.user.into(),
+			user,
 			workspaces: DashMap::default(),
-			claims,
-			auth,
 			session,
 			config,
 		})))
 	}
 
 	/// Refresh session token.
+	///
+	/// This is also done automatically some time before the token expires (when its expiration
+	/// is known, see [`crate::api::Tuning::refresh_margin_ms`]) and whenever the server rejects it.
 	pub async fn refresh(&self) -> RemoteResult<()> {
-		let new_token = self
-			.0
-			.auth
-			.clone()
-			.refresh(self.0.claims.get())
-			.await?
-			.into_inner();
-		self.0.claims.set(new_token);
-		Ok(())
+		self.0.session.refresh().await
 	}
 
 	/// Attempt to create a new workspace with given name.
 	pub async fn create_workspace(&self, name: impl AsRef<str>) -> RemoteResult<()> {
+		let workspace = name.as_ref().to_string();
 		self.0
 			.session
-			.clone()
-			.create_workspace(WorkspaceRequest {
-				workspace: name.as_ref().to_string(),
+			.call(|mut session| {
+				let workspace = workspace.clone();
+				async move {
+					session
+						.create_workspace(WorkspaceRequest { workspace })
+						.await
+				}
 			})
 			.await?;
 		Ok(())
@@ -103,11 +151,16 @@ impl Client {
 
 	/// Delete an existing workspace if possible.
 	pub async fn delete_workspace(&self, name: impl AsRef<str>) -> RemoteResult<()> {
+		let workspace = name.as_ref().to_string();
 		self.0
 			.session
-			.clone()
-			.delete_workspace(WorkspaceRequest {
-				workspace: name.as_ref().to_string(),
+			.call(|mut session| {
+				let workspace = workspace.clone();
+				async move {
+					session
+						.delete_workspace(WorkspaceRequest { workspace })
+						.await
+				}
 			})
 			.await?;
 		Ok(())
@@ -119,12 +172,103 @@ impl Client {
 		workspace_name: impl AsRef<str>,
 		user_name: impl AsRef<str>,
 	) -> RemoteResult<()> {
+		let request = InviteRequest {
+			workspace: workspace_name.as_ref().to_string(),
+			user: user_name.as_ref().to_string(),
+		};
+		self.0
+			.session
+			.call(|mut session| {
+				let request = request.clone();
+				async move { session.invite_to_workspace(request).await }
+			})
+			.await?;
+		Ok(())
+	}
+
+	/// Fetch all members of the given workspace with their roles, including pending invites.
+	pub async fn fetch_workspace_members(
+		&self,
+		workspace_name: impl AsRef<str>,
+	) -> RemoteResult<Vec<Member>> {
+		let workspace = workspace_name.as_ref().to_string();
+		let members = self
+			.0
+			.session
+			.call_members(|mut members| {
+				let workspace = workspace.clone();
+				async move { members.list_members(MembersRequest { workspace }).await }
+			})
+			.await?;
+		Ok(members.members.into_iter().map(Member::from).collect())
+	}
+
+	/// Revoke access of the user with given username to the given workspace, or their pending
+	/// invite, if possible.
+	pub async fn remove_from_workspace(
+		&self,
+		workspace_name: impl AsRef<str>,
+		user_name: impl AsRef<str>,
+	) -> RemoteResult<()> {
+		let request = MemberRequest {
+			workspace: workspace_name.as_ref().to_string(),
+			user: user_name.as_ref().to_string(),
+		};
+		self.0
+			.session
+			.call_members(|mut members| {
+				let request = request.clone();
+				async move { members.remove_member(request).await }
+			})
+			.await?;
+		Ok(())
+	}
+
+	/// Change the [`Role`] of the user with given username in the given workspace, if possible.
+	///
+	/// Workspaces attached by that user only notice the change after
+	/// [`Workspace::fetch_role`].
+	pub async fn set_workspace_role(
+		&self,
+		workspace_name: impl AsRef<str>,
+		user_name: impl AsRef<str>,
+		role: Role,
+	) -> RemoteResult<()> {
+		let mut request = RoleRequest {
+			workspace: workspace_name.as_ref().to_string(),
+			user: user_name.as_ref().to_string(),
+			role: 0,
+		};
+		request.set_role(role.into());
 		self.0
 			.session
-			.clone()
-			.invite_to_workspace(InviteRequest {
-				workspace: workspace_name.as_ref().to_string(),
-				user: user_name.as_ref().to_string(),
+			.call_members(|mut members| {
+				let request = request.clone();
+				async move { members.set_role(request).await }
+			})
+			.await?;
+		Ok(())
+	}
+
+	/// Change how the current user presents themselves to others: an optional display name to
+	/// show instead of their user name, and an optional URL of an avatar picture.
+	///
+	/// Other users see the change in [`crate::api::Presence`] after
+	/// [`Workspace::fetch_presence`].
+	pub async fn set_profile(
+		&self,
+		display_name: Option<String>,
+		avatar: Option<String>,
+	) -> RemoteResult<()> {
+		let update = ProfileUpdate {
+			display_name,
+			avatar,
+		};
+		self.0
+			.session
+			.call_presence(|mut presence| {
+				let update = update.clone();
+				async move { presence.set_profile(update).await }
 			})
 			.await?;
 		Ok(())
@@ -144,10 +288,8 @@ impl Client {
 		let workspaces = self
 			.0
 			.session
-			.clone()
-			.list_workspaces(Empty {})
-			.await?
-			.into_inner();
+			.call(|mut session| async move { session.list_workspaces(Empty {}).await })
+			.await?;
 
 		if owned {
 			Ok(workspaces.owned)
@@ -161,22 +303,11 @@ impl Client {
 		&self,
 		workspace: impl AsRef<str>,
 	) -> ConnectionResult<Workspace> {
-		let token = self
-			.0
-			.session
-			.clone()
-			.access_workspace(WorkspaceRequest {
-				workspace: workspace.as_ref().to_string(),
-			})
-			.await?
-			.into_inner();
-
 		let ws = Workspace::connect(
 			workspace.as_ref().to_string(),
 			self.0.user.clone(),
 			self.0.config.clone(),
-			token,
-			self.0.claims.channel(),
+			self.0.session.clone(),
 		)
 		.await?;
 
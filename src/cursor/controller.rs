@@ -11,6 +11,7 @@ use crate::{
 		Controller, Cursor, Selection,
 	},
 	errors::ControllerResult,
+	ext::ControllerStream,
 };
 use codemp_proto::{
 	cursor::{CursorPosition, RowCol},
@@ -25,6 +26,18 @@ use codemp_proto::{
 #[cfg_attr(feature = "js", napi_derive::napi)]
 pub struct CursorController(pub(crate) Arc<CursorControllerInner>);
 
+impl CursorController {
+	/// Turn this controller into a [`tokio_stream::Stream`] of [`Cursor`] events.
+	///
+	/// The stream ends once the controller worker stops, see [`ControllerStream`].
+	pub fn into_stream(self) -> ControllerStream<Cursor> {
+		ControllerStream::new(move || {
+			let controller = self.clone();
+			Box::pin(async move { controller.recv().await })
+		})
+	}
+}
+
 #[derive(Debug)]
 pub(crate) struct CursorControllerInner {
 	pub(crate) op: mpsc::UnboundedSender<CursorPosition>,
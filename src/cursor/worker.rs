@@ -5,7 +5,8 @@ use tonic::Streaming;
 use uuid::Uuid;
 
 use crate::{
-	api::{controller::ControllerCallback, Cursor, Selection, User},
+	api::{presence::Tracked, Cursor, Selection},
+	dispatch::{Dispatcher, Strategy},
 	ext::IgnorableError,
 };
 use codemp_proto::cursor::{CursorEvent, CursorPosition};
@@ -14,18 +15,21 @@ use super::controller::{CursorController, CursorControllerInner};
 
 struct CursorWorker {
 	op: mpsc::UnboundedReceiver<CursorPosition>,
-	map: Arc<dashmap::DashMap<Uuid, User>>,
+	map: Arc<dashmap::DashMap<Uuid, Tracked>>,
+	activity: mpsc::UnboundedSender<Uuid>,
 	stream: mpsc::Receiver<oneshot::Sender<Option<Cursor>>>,
 	poll: mpsc::UnboundedReceiver<oneshot::Sender<()>>,
 	pollers: Vec<oneshot::Sender<()>>,
 	store: std::collections::VecDeque<Cursor>,
 	controller: std::sync::Weak<CursorControllerInner>,
-	callback: watch::Receiver<Option<ControllerCallback<CursorController>>>,
+	callback: Dispatcher<CursorController>,
 }
 
 impl CursorController {
 	pub(crate) fn spawn(
-		user_map: Arc<dashmap::DashMap<Uuid, User>>,
+		user_map: Arc<dashmap::DashMap<Uuid, Tracked>>,
+		activity: mpsc::UnboundedSender<Uuid>,
+		callbacks: Strategy,
 		tx: mpsc::Sender<CursorPosition>,
 		rx: Streaming<CursorEvent>,
 	) -> Self {
@@ -46,10 +50,11 @@ impl CursorController {
 		let worker = CursorWorker {
 			op: op_rx,
 			map: user_map,
+			activity,
 			stream: stream_rx,
 			store: std::collections::VecDeque::default(),
 			controller: weak,
-			callback: cb_rx,
+			callback: Dispatcher::new(callbacks, cb_rx),
 			poll: poll_rx,
 			pollers: Vec::new(),
 		};
@@ -82,30 +87,12 @@ impl CursorController {
 				},
 
 				// server sents us a cursor
-				Ok(Some(cur)) = rx.message() => match worker.controller.upgrade() {
-					None => break, // clean exit, just weird that we got it here
-					Some(controller) => {
-						tracing::debug!("received cursor from server");
-						let user_id = Uuid::from(cur.user);
-						let cursor = Cursor {
-							user: worker.map.get(&user_id).map(|u| u.name.clone()).unwrap_or_default(),
-							sel: Selection {
-								buffer: cur.position.buffer.path,
-								start_row: cur.position.start.row,
-								start_col: cur.position.start.col,
-								end_row: cur.position.end.row,
-								end_col: cur.position.end.col
-							}
-						};
-
-						worker.store.push_back(cursor);
-						for tx in worker.pollers.drain(..) {
-							tx.send(()).unwrap_or_warn("poller dropped before unblocking");
-						}
-						if let Some(cb) = worker.callback.borrow().as_ref() {
-							tracing::debug!("running cursor callback");
-							cb.call(CursorController(controller)); // TODO should this run in its own task/thread?
-						}
+				res = rx.message() => match res {
+					Err(e) => break tracing::warn!("error receiving cursors: {e}"),
+					Ok(None) => break tracing::info!("cursor stream closed by server"),
+					Ok(Some(cur)) => match worker.controller.upgrade() {
+						None => break, // clean exit, just weird that we got it here
+						Some(controller) => worker.handle_cursor(controller, cur),
 					},
 				},
 
@@ -118,3 +105,36 @@ impl CursorController {
 		}
 	}
 }
+
+impl CursorWorker {
+	fn handle_cursor(&mut self, controller: Arc<CursorControllerInner>, cur: CursorEvent) {
+		tracing::debug!("received cursor from server");
+		let user_id = Uuid::from(cur.user);
+		let mut user = String::new();
+		if let Some(mut tracked) = self.map.get_mut(&user_id) {
+			user = tracked.user.name.clone();
+			if tracked.touch(cur.position.buffer.path.clone()) {
+				self.activity
+					.send(user_id)
+					.unwrap_or_warn("could not report user activity");
+			}
+		}
+		let cursor = Cursor {
+			user,
+			sel: Selection {
+				buffer: cur.position.buffer.path,
+				start_row: cur.position.start.row,
+				start_col: cur.position.start.col,
+				end_row: cur.position.end.row,
+				end_col: cur.position.end.col,
+			},
+		};
+
+		self.store.push_back(cursor);
+		for tx in self.pollers.drain(..) {
+			tx.send(())
+				.unwrap_or_warn("poller dropped before unblocking");
+		}
+		self.callback.dispatch(CursorController(controller));
+	}
+}
@@ -0,0 +1,109 @@
+//! ### Decoration Controller
+//! A [Controller] implementation for [crate::api::Decoration]s shared on a buffer
+
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, oneshot, watch};
+
+use crate::{
+	api::{
+		controller::{AsyncReceiver, AsyncSender, ControllerCallback},
+		Controller, Decoration,
+	},
+	errors::ControllerResult,
+	ext::ControllerStream,
+	protocol::annotations::Anchor,
+};
+
+/// A [Controller] for asynchronously sending and receiving [Decoration]s on a buffer.
+///
+/// An unique [DecorationController] exists for each attached buffer, obtained with
+/// [crate::Workspace::attach_decorations]. Ranges of sent decorations refer to the buffer as
+/// shown to the editor when they are sent, ranges of received ones to the buffer as shown to
+/// the editor when they are received: in between, they move along with the buffer content.
+/// Decorations made on changes the editor hasn't received yet are held back until it does,
+/// without delaying those received after them.
+#[derive(Debug, Clone)]
+#[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass)]
+#[cfg_attr(feature = "js", napi_derive::napi)]
+pub struct DecorationController(pub(crate) Arc<DecorationControllerInner>);
+
+impl DecorationController {
+	/// Get the path of the buffer decorated.
+	pub fn path(&self) -> &str {
+		self.0.buffer.path()
+	}
+
+	/// Get the decorations received which didn't expire yet, oldest first, including those
+	/// already consumed.
+	///
+	/// Decorations made on changes the editor hasn't received yet are left out until it does.
+	pub async fn active(&self) -> ControllerResult<Vec<Decoration>> {
+		let (tx, rx) = oneshot::channel();
+		self.0.active_request.send(tx).await?;
+		Ok(rx.await?)
+	}
+
+	/// Turn this controller into a [`tokio_stream::Stream`] of [`Decoration`]s.
+	///
+	/// The stream ends once the controller worker stops, see [`ControllerStream`].
+	pub fn into_stream(self) -> ControllerStream<Decoration> {
+		ControllerStream::new(move || {
+			let controller = self.clone();
+			Box::pin(async move { controller.recv().await })
+		})
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct DecorationControllerInner {
+	pub(crate) buffer: crate::buffer::Controller,
+	pub(crate) op: mpsc::UnboundedSender<(Decoration, oneshot::Receiver<Anchor>)>,
+	pub(crate) stream: mpsc::Sender<oneshot::Sender<Option<Decoration>>>,
+	pub(crate) active_request: mpsc::Sender<oneshot::Sender<Vec<Decoration>>>,
+	pub(crate) poll: mpsc::UnboundedSender<oneshot::Sender<()>>,
+	pub(crate) callback: watch::Sender<Option<ControllerCallback<DecorationController>>>,
+}
+
+#[cfg_attr(feature = "async-trait", async_trait::async_trait)]
+impl Controller<Decoration, Decoration> for DecorationController {}
+
+#[cfg_attr(feature = "async-trait", async_trait::async_trait)]
+impl AsyncSender<Decoration> for DecorationController {
+	/// Send a decoration to the other users attached to the buffer. Its author is ignored.
+	fn send(&self, decoration: Decoration) -> ControllerResult<()> {
+		let anchor = self
+			.0
+			.buffer
+			.anchor(decoration.start_idx, decoration.end_idx)?;
+		Ok(self.0.op.send((decoration, anchor))?)
+	}
+}
+
+#[cfg_attr(feature = "async-trait", async_trait::async_trait)]
+impl AsyncReceiver<Decoration> for DecorationController {
+	async fn try_recv(&self) -> ControllerResult<Option<Decoration>> {
+		let (tx, rx) = oneshot::channel();
+		self.0.stream.send(tx).await?;
+		Ok(rx.await?)
+	}
+
+	async fn poll(&self) -> ControllerResult<()> {
+		let (tx, rx) = oneshot::channel();
+		self.0.poll.send(tx)?;
+		rx.await?;
+		Ok(())
+	}
+
+	fn callback(&self, cb: impl Into<ControllerCallback<DecorationController>>) {
+		if self.0.callback.send(Some(cb.into())).is_err() {
+			tracing::error!("no active decoration worker to run registered callback!");
+		}
+	}
+
+	fn clear_callback(&self) {
+		if self.0.callback.send(None).is_err() {
+			tracing::warn!("no active decoration worker to clear callback");
+		}
+	}
+}
@@ -0,0 +1,10 @@
+//! ### Decoration
+//! Users attached to a buffer can share short-lived marks on it, such as diagnostics or search
+//! highlights, which move along with the buffer content until they expire.
+
+/// decoration worker implementation
+pub(crate) mod worker;
+
+/// decoration controller implementation
+pub mod controller;
+pub use controller::DecorationController as Controller;
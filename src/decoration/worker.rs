@@ -0,0 +1,242 @@
+use std::{
+	collections::VecDeque,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use tokio::sync::{mpsc, oneshot, watch};
+use tonic::Streaming;
+use uuid::Uuid;
+
+use crate::{
+	api::{presence::Tracked, Decoration},
+	buffer,
+	dispatch::{Dispatcher, Strategy},
+	ext::IgnorableError,
+	protocol::{annotations::Anchor, decorations as proto},
+};
+
+use super::controller::{DecorationController, DecorationControllerInner};
+
+/// A decoration relayed by the server, until it expires.
+#[derive(Clone)]
+struct Received {
+	decoration: proto::Decoration,
+	author: String,
+	expires: Instant,
+}
+
+impl Received {
+	fn expired(&self) -> bool {
+		Instant::now() >= self.expires
+	}
+
+	fn at(&self, (start_idx, end_idx): (u32, u32)) -> Decoration {
+		Decoration {
+			kind: self.decoration.kind().into(),
+			message: self.decoration.message.clone(),
+			author: self.author.clone(),
+			start_idx,
+			end_idx,
+			ttl_ms: self.decoration.ttl_ms,
+		}
+	}
+}
+
+struct DecorationWorker {
+	buffer: buffer::Controller,
+	op: mpsc::UnboundedReceiver<(Decoration, oneshot::Receiver<Anchor>)>,
+	map: Arc<dashmap::DashMap<Uuid, Tracked>>,
+	stream: mpsc::Receiver<oneshot::Sender<Option<Decoration>>>,
+	active_req: mpsc::Receiver<oneshot::Sender<Vec<Decoration>>>,
+	poll: mpsc::UnboundedReceiver<oneshot::Sender<()>>,
+	pollers: Vec<oneshot::Sender<()>>,
+	store: VecDeque<Received>,
+	active: Vec<Received>,
+	controller: std::sync::Weak<DecorationControllerInner>,
+	callback: Dispatcher<DecorationController>,
+}
+
+impl DecorationController {
+	pub(crate) fn spawn(
+		buffer: buffer::Controller,
+		user_map: Arc<dashmap::DashMap<Uuid, Tracked>>,
+		callbacks: Strategy,
+		tx: mpsc::Sender<proto::Decoration>,
+		rx: Streaming<proto::Decoration>,
+	) -> Self {
+		let (op_tx, op_rx) = mpsc::unbounded_channel();
+		let (stream_tx, stream_rx) = mpsc::channel(1);
+		let (active_tx, active_rx) = mpsc::channel(1);
+		let (cb_tx, cb_rx) = watch::channel(None);
+		let (poll_tx, poll_rx) = mpsc::unbounded_channel();
+		let controller = Arc::new(DecorationControllerInner {
+			buffer: buffer.clone(),
+			op: op_tx,
+			stream: stream_tx,
+			active_request: active_tx,
+			callback: cb_tx,
+			poll: poll_tx,
+		});
+
+		let weak = Arc::downgrade(&controller);
+
+		let worker = DecorationWorker {
+			buffer,
+			op: op_rx,
+			map: user_map,
+			stream: stream_rx,
+			active_req: active_rx,
+			store: VecDeque::default(),
+			active: Vec::new(),
+			controller: weak,
+			callback: Dispatcher::new(callbacks, cb_rx),
+			poll: poll_rx,
+			pollers: Vec::new(),
+		};
+
+		tokio::spawn(async move { DecorationController::work(worker, tx, rx).await });
+
+		DecorationController(controller)
+	}
+
+	async fn work(
+		mut worker: DecorationWorker,
+		tx: mpsc::Sender<proto::Decoration>,
+		mut rx: Streaming<proto::Decoration>,
+	) {
+		loop {
+			tracing::debug!("decoration worker polling");
+			if worker.controller.upgrade().is_none() {
+				break;
+			}; // clean exit: all controllers dropped
+			tokio::select! {
+				biased;
+
+				// new poller
+				Some(poller) = worker.poll.recv() => worker.pollers.push(poller),
+
+				// client decorated the buffer
+				Some((decoration, anchor)) = worker.op.recv() => {
+					tracing::debug!("received decoration from editor");
+					let Ok(anchor) = anchor.await else {
+						break tracing::error!("buffer {} stopped: can't anchor decorations", worker.buffer.path());
+					};
+					let mut msg = proto::Decoration {
+						author_hi: None,
+						author_lo: None,
+						kind: 0,
+						message: decoration.message,
+						anchor,
+						ttl_ms: decoration.ttl_ms,
+					};
+					msg.set_kind(decoration.kind.into());
+					tx.send(msg).await.unwrap_or_warn("could not send decoration");
+				},
+
+				// server relayed a decoration
+				res = rx.message() => match res {
+					Err(e) => break tracing::warn!("error receiving decorations for buffer {}: {e}", worker.buffer.path()),
+					Ok(None) => break tracing::info!("decorations of buffer {} closed", worker.buffer.path()),
+					Ok(Some(msg)) => match worker.controller.upgrade() {
+						None => break, // clean exit, just weird that we got it here
+						Some(controller) => worker.handle_decoration(controller, msg),
+					},
+				},
+
+				// client wants to get next decoration
+				Some(tx) = worker.stream.recv() => {
+					let next = worker.next().await;
+					tx.send(next).unwrap_or_warn("client gave up receiving");
+				},
+
+				// client wants to get all decorations still shown
+				Some(tx) = worker.active_req.recv() => {
+					let active = worker.locate_active().await;
+					tx.send(active).unwrap_or_warn("client gave up listing decorations");
+				},
+
+				else => break,
+			}
+		}
+		tracing::debug!("decoration worker stopping");
+	}
+}
+
+impl DecorationWorker {
+	fn handle_decoration(
+		&mut self,
+		controller: Arc<DecorationControllerInner>,
+		msg: proto::Decoration,
+	) {
+		tracing::debug!("received decoration from server");
+		let author = self
+			.map
+			.get(&msg.author())
+			.map(|tracked| tracked.user.name.clone())
+			.unwrap_or_default();
+		let received = Received {
+			expires: Instant::now() + Duration::from_millis(msg.ttl_ms.into()),
+			decoration: msg,
+			author,
+		};
+		self.active.retain(|r| !r.expired());
+		self.active.push(received.clone());
+		self.store.push_back(received);
+		for tx in self.pollers.drain(..) {
+			tx.send(())
+				.unwrap_or_warn("poller dropped before unblocking");
+		}
+		self.callback.dispatch(DecorationController(controller));
+	}
+
+	/// Next decoration not delivered yet which didn't expire, oldest first.
+	///
+	/// Decorations made on changes the editor hasn't received yet stay queued until they can be
+	/// delivered or expire, without holding back later ones.
+	async fn next(&mut self) -> Option<Decoration> {
+		self.store.retain(|r| !r.expired());
+		if self.store.is_empty() {
+			return None;
+		}
+		let anchors = self
+			.store
+			.iter()
+			.map(|r| r.decoration.anchor.clone())
+			.collect();
+		match self.buffer.locate(anchors).await {
+			Err(e) => {
+				tracing::warn!("could not locate decorations: {e}");
+				None
+			}
+			Ok(ranges) => {
+				let (index, range) = ranges
+					.into_iter()
+					.enumerate()
+					.find_map(|(index, range)| Some((index, range?)))?;
+				self.store.remove(index).map(|received| received.at(range))
+			}
+		}
+	}
+
+	async fn locate_active(&mut self) -> Vec<Decoration> {
+		self.active.retain(|r| !r.expired());
+		let anchors = self
+			.active
+			.iter()
+			.map(|r| r.decoration.anchor.clone())
+			.collect();
+		match self.buffer.locate(anchors).await {
+			Err(e) => {
+				tracing::warn!("could not locate decorations: {e}");
+				Vec::new()
+			}
+			Ok(ranges) => self
+				.active
+				.iter()
+				.zip(ranges)
+				.filter_map(|(received, range)| Some(received.at(range?)))
+				.collect(),
+		}
+	}
+}
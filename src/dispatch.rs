@@ -0,0 +1,167 @@
+//! ### Dispatch
+//! Runs user callbacks on behalf of controller workers, so that slow or misbehaving callbacks
+//! can't stall network processing.
+
+use std::panic::AssertUnwindSafe;
+
+use tokio::sync::{mpsc, watch};
+
+use crate::api::controller::ControllerCallback;
+
+/// How callbacks are run, see [`crate::api::Tuning::callback_strategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Strategy {
+	/// Run callbacks directly on the worker, blocking it until they return.
+	Inline,
+	/// Run callbacks in order on a dedicated task, never dropping any.
+	Task,
+	/// Like [`Strategy::Task`], but discard new invocations while this many are pending.
+	Drop(usize),
+	/// Like [`Strategy::Task`], but skip new invocations while one is already pending.
+	Coalesce,
+}
+
+type Callback<T> = watch::Receiver<Option<ControllerCallback<T>>>;
+
+/// Hands values to the currently registered callback, according to a [`Strategy`].
+///
+/// Dropping the dispatcher stops its task, if any, after pending invocations have run.
+#[derive(Debug)]
+pub(crate) struct Dispatcher<T> {
+	callback: Callback<T>,
+	queue: Option<Queue<T>>,
+}
+
+#[derive(Debug)]
+enum Queue<T> {
+	Unbounded(mpsc::UnboundedSender<T>),
+	Bounded(mpsc::Sender<T>),
+}
+
+impl<T: Send + 'static> Queue<T> {
+	fn unbounded(cb: Callback<T>) -> Self {
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		tokio::spawn(async move {
+			while let Some(x) = rx.recv().await {
+				run_blocking(&cb, x).await;
+			}
+		});
+		Self::Unbounded(tx)
+	}
+
+	fn bounded(capacity: usize, cb: Callback<T>) -> Self {
+		let (tx, mut rx) = mpsc::channel(capacity.max(1));
+		tokio::spawn(async move {
+			while let Some(x) = rx.recv().await {
+				run_blocking(&cb, x).await;
+			}
+		});
+		Self::Bounded(tx)
+	}
+}
+
+impl<T: Send + 'static> Dispatcher<T> {
+	pub(crate) fn new(strategy: Strategy, callback: Callback<T>) -> Self {
+		let queue = match strategy {
+			Strategy::Inline => None,
+			Strategy::Task => Some(Queue::unbounded(callback.clone())),
+			Strategy::Drop(capacity) => Some(Queue::bounded(capacity, callback.clone())),
+			// a pending invocation will observe the latest state anyway, no need to queue more
+			Strategy::Coalesce => Some(Queue::bounded(1, callback.clone())),
+		};
+		Self { callback, queue }
+	}
+
+	/// Invoke the registered callback with given value, if any is registered.
+	///
+	/// Never blocks, unless the strategy is [`Strategy::Inline`].
+	pub(crate) fn dispatch(&self, x: T) {
+		if self.callback.borrow().is_none() {
+			return;
+		}
+		match &self.queue {
+			None => run(&self.callback, x),
+			Some(Queue::Unbounded(tx)) => {
+				if tx.send(x).is_err() {
+					tracing::error!("callback task stopped unexpectedly");
+				}
+			}
+			Some(Queue::Bounded(tx)) => match tx.try_send(x) {
+				Ok(()) => {}
+				Err(mpsc::error::TrySendError::Full(_)) => {
+					tracing::debug!("callback queue is full, skipping invocation")
+				}
+				Err(mpsc::error::TrySendError::Closed(_)) => {
+					tracing::error!("callback task stopped unexpectedly")
+				}
+			},
+		}
+	}
+}
+
+async fn run_blocking<T: Send + 'static>(callback: &Callback<T>, x: T) {
+	let cb = callback.clone();
+	if tokio::task::spawn_blocking(move || run(&cb, x))
+		.await
+		.is_err()
+	{
+		tracing::error!("callback task was cancelled");
+	}
+}
+
+fn run<T>(callback: &Callback<T>, x: T) {
+	if let Some(cb) = callback.borrow().as_ref() {
+		if std::panic::catch_unwind(AssertUnwindSafe(|| cb.call(x))).is_err() {
+			tracing::error!("registered callback panicked, ignoring");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Dispatcher, Strategy};
+	use crate::api::controller::ControllerCallback;
+	use std::sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	};
+
+	fn counting(strategy: Strategy) -> (Dispatcher<()>, Arc<AtomicUsize>) {
+		let count = Arc::new(AtomicUsize::new(0));
+		let _count = count.clone();
+		let (_tx, rx) = tokio::sync::watch::channel(Some(ControllerCallback::from(move |()| {
+			_count.fetch_add(1, Ordering::SeqCst);
+			panic!("callbacks may panic");
+		})));
+		(Dispatcher::new(strategy, rx), count)
+	}
+
+	#[tokio::test]
+	async fn panicking_callbacks_dont_stop_dispatch() {
+		let (dispatcher, count) = counting(Strategy::Inline);
+		dispatcher.dispatch(());
+		dispatcher.dispatch(());
+		assert_eq!(count.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn task_strategy_runs_every_invocation() {
+		let (dispatcher, count) = counting(Strategy::Task);
+		for _ in 0..10 {
+			dispatcher.dispatch(());
+		}
+		while count.load(Ordering::SeqCst) < 10 {
+			tokio::task::yield_now().await;
+		}
+	}
+
+	#[tokio::test]
+	async fn coalesce_strategy_never_queues_more_than_one() {
+		let (dispatcher, count) = counting(Strategy::Coalesce);
+		for _ in 0..10 {
+			dispatcher.dispatch(());
+		}
+		tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+		assert!(count.load(Ordering::SeqCst) <= 2);
+	}
+}
@@ -1,14 +1,139 @@
 //! ### Errors
 //! Contains the crate's error types.
+//!
+//! Every error can be classified with an [`ErrorKind`], to react to it (or decide whether to try
+//! again) without matching on messages.
+
+/// Broad category of an error, shared by every error type in this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// Requested resource (workspace, buffer, user...) does not exist.
+	NotFound,
+	/// Credentials are missing, wrong or expired.
+	Unauthorized,
+	/// Credentials are valid, but don't allow this operation.
+	Forbidden,
+	/// Resource to create already exists.
+	AlreadyExists,
+	/// Server can't be reached, or is temporarily unable to serve requests.
+	Unavailable,
+	/// Request or configuration is malformed.
+	InvalidArgument,
+	/// Operation did not complete in time.
+	TimedOut,
+	/// Too many requests were made, or some quota is exhausted.
+	RateLimited,
+	/// Operation conflicts with the current state of the resource.
+	Conflict,
+	/// Underlying worker or connection is already closed.
+	Closed,
+	/// Anything else, such as internal server errors.
+	Other,
+}
+
+impl ErrorKind {
+	/// Every kind, in declaration order.
+	pub const ALL: [ErrorKind; 11] = [
+		Self::NotFound,
+		Self::Unauthorized,
+		Self::Forbidden,
+		Self::AlreadyExists,
+		Self::Unavailable,
+		Self::InvalidArgument,
+		Self::TimedOut,
+		Self::RateLimited,
+		Self::Conflict,
+		Self::Closed,
+		Self::Other,
+	];
+
+	/// Classify a procedure status code.
+	pub fn from_code(code: tonic::Code) -> Self {
+		use tonic::Code;
+		match code {
+			Code::NotFound => Self::NotFound,
+			Code::Unauthenticated => Self::Unauthorized,
+			Code::PermissionDenied => Self::Forbidden,
+			Code::AlreadyExists => Self::AlreadyExists,
+			Code::Unavailable => Self::Unavailable,
+			Code::InvalidArgument | Code::OutOfRange => Self::InvalidArgument,
+			Code::DeadlineExceeded => Self::TimedOut,
+			Code::ResourceExhausted => Self::RateLimited,
+			Code::FailedPrecondition | Code::Aborted => Self::Conflict,
+			_ => Self::Other,
+		}
+	}
+
+	/// Whether trying the same operation again later may succeed.
+	pub fn is_retryable(self) -> bool {
+		matches!(self, Self::Unavailable | Self::TimedOut | Self::RateLimited)
+	}
+
+	/// Stable `snake_case` name of this kind, as exposed to bindings.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::NotFound => "not_found",
+			Self::Unauthorized => "unauthorized",
+			Self::Forbidden => "forbidden",
+			Self::AlreadyExists => "already_exists",
+			Self::Unavailable => "unavailable",
+			Self::InvalidArgument => "invalid_argument",
+			Self::TimedOut => "timed_out",
+			Self::RateLimited => "rate_limited",
+			Self::Conflict => "conflict",
+			Self::Closed => "closed",
+			Self::Other => "other",
+		}
+	}
+
+	/// Inverse of [`ErrorKind::as_str`].
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|kind| kind.as_str() == name)
+	}
+}
+
+impl std::fmt::Display for ErrorKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl AsRef<str> for ErrorKind {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
 
 /// An error returned by the server as response to a request.
 ///
-/// This currently wraps an [http code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status),
-/// returned as procedure status.
+/// This wraps the procedure status returned by the server, see [`RemoteError::kind`].
 #[derive(Debug, thiserror::Error)]
-#[error("server rejected procedure with error code: {0:?}")]
+#[error("server rejected procedure with {:?}: {}", .0.code(), .0.message())]
 pub struct RemoteError(#[from] tonic::Status);
 
+impl RemoteError {
+	/// Category of this error, derived from its status code.
+	pub fn kind(&self) -> ErrorKind {
+		ErrorKind::from_code(self.0.code())
+	}
+
+	/// Whether trying the same request again later may succeed.
+	pub fn is_retryable(&self) -> bool {
+		self.kind().is_retryable()
+	}
+
+	/// Status code returned by the server.
+	pub fn code(&self) -> tonic::Code {
+		self.0.code()
+	}
+
+	/// Human readable description given by the server.
+	pub fn message(&self) -> &str {
+		self.0.message()
+	}
+}
+
 /// Wraps [std::result::Result] with a [RemoteError].
 pub type RemoteResult<T> = std::result::Result<T, RemoteError>;
 
@@ -20,8 +145,33 @@ pub enum ConnectionError {
 	Transport(#[from] tonic::transport::Error),
 
 	/// Error from the remote server, see [`RemoteError`].
-	#[error("server rejected connection attempt: {0:?}")]
+	#[error("server rejected connection attempt: {0}")]
 	Remote(#[from] RemoteError),
+
+	/// Given [`crate::api::Config`] can't be used to connect.
+	#[error("invalid configuration: {0}")]
+	InvalidConfig(String),
+
+	/// Credentials could not be obtained from the [`crate::auth::AuthProvider`].
+	#[error("could not obtain credentials: {0}")]
+	Auth(String),
+}
+
+impl ConnectionError {
+	/// Category of this error: transport failures are [`ErrorKind::Unavailable`].
+	pub fn kind(&self) -> ErrorKind {
+		match self {
+			Self::Transport(_) => ErrorKind::Unavailable,
+			Self::Remote(e) => e.kind(),
+			Self::InvalidConfig(_) => ErrorKind::InvalidArgument,
+			Self::Auth(_) => ErrorKind::Unauthorized,
+		}
+	}
+
+	/// Whether trying to connect again later may succeed.
+	pub fn is_retryable(&self) -> bool {
+		self.kind().is_retryable()
+	}
 }
 
 impl From<tonic::Status> for ConnectionError {
@@ -45,6 +195,27 @@ pub enum ControllerError {
 	/// fulfilling the request, without rejecting it first.
 	#[error("worker stopped before completing requested operation")]
 	Unfulfilled,
+
+	/// Error occurred because the current user may not edit this buffer, see
+	/// [`crate::api::Role`].
+	#[error("buffer is read-only for the current user")]
+	ReadOnly,
+}
+
+impl ControllerError {
+	/// Category of this error: [`ErrorKind::Forbidden`] for read-only buffers,
+	/// [`ErrorKind::Closed`] otherwise.
+	pub fn kind(&self) -> ErrorKind {
+		match self {
+			Self::Stopped | Self::Unfulfilled => ErrorKind::Closed,
+			Self::ReadOnly => ErrorKind::Forbidden,
+		}
+	}
+
+	/// Whether trying the same operation again may succeed: never, as workers don't restart.
+	pub fn is_retryable(&self) -> bool {
+		false
+	}
 }
 
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for ControllerError {
@@ -61,3 +232,37 @@ impl From<tokio::sync::oneshot::error::RecvError> for ControllerError {
 
 /// Wraps [std::result::Result] with a [ControllerError].
 pub type ControllerResult<T> = std::result::Result<T, ControllerError>;
+
+#[cfg(test)]
+mod tests {
+	use super::{ErrorKind, RemoteError};
+
+	#[test]
+	fn status_codes_are_classified() {
+		let status = tonic::Status::not_found("no such workspace");
+		let err = RemoteError::from(status);
+		assert_eq!(err.kind(), ErrorKind::NotFound);
+		assert_eq!(err.message(), "no such workspace");
+		assert!(!err.is_retryable());
+
+		assert_eq!(
+			ErrorKind::from_code(tonic::Code::Unauthenticated),
+			ErrorKind::Unauthorized
+		);
+		assert_eq!(
+			ErrorKind::from_code(tonic::Code::PermissionDenied),
+			ErrorKind::Forbidden
+		);
+		assert!(ErrorKind::from_code(tonic::Code::Unavailable).is_retryable());
+		assert!(ErrorKind::from_code(tonic::Code::ResourceExhausted).is_retryable());
+		assert!(!ErrorKind::from_code(tonic::Code::Internal).is_retryable());
+	}
+
+	#[test]
+	fn kinds_round_trip_through_names() {
+		for kind in ErrorKind::ALL {
+			assert_eq!(ErrorKind::from_name(kind.as_str()), Some(kind));
+		}
+		assert_eq!(ErrorKind::from_name("nope"), None);
+	}
+}
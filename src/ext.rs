@@ -1,56 +1,194 @@
 //! ### Extensions
 //! Contains a number of utils used internally or that may be of general interest.
 
-use crate::{api::controller::AsyncReceiver, errors::ControllerResult};
-use tokio::sync::mpsc;
+use std::{
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll},
+};
 
-/// Poll all given buffer controllers and wait, returning the first one ready.
+use crate::{
+	api::controller::AsyncReceiver,
+	errors::{ControllerError, ControllerResult},
+};
+
+/// Any controller which can be waited on with [`select`].
+#[derive(Debug, Clone)]
+pub enum Selectable {
+	Buffer(crate::buffer::Controller),
+	Cursor(crate::cursor::Controller),
+	Workspace(crate::Workspace),
+	Subscription(crate::workspace::Subscription),
+}
+
+impl Selectable {
+	async fn poll(&self) -> ControllerResult<()> {
+		match self {
+			Self::Buffer(x) => x.poll().await,
+			Self::Cursor(x) => x.poll().await,
+			Self::Workspace(x) => x.poll().await,
+			Self::Subscription(x) => x.poll().await,
+		}
+	}
+}
+
+impl From<crate::buffer::Controller> for Selectable {
+	fn from(value: crate::buffer::Controller) -> Self {
+		Self::Buffer(value)
+	}
+}
+
+impl From<crate::cursor::Controller> for Selectable {
+	fn from(value: crate::cursor::Controller) -> Self {
+		Self::Cursor(value)
+	}
+}
+
+impl From<crate::Workspace> for Selectable {
+	fn from(value: crate::Workspace) -> Self {
+		Self::Workspace(value)
+	}
+}
+
+impl From<crate::workspace::Subscription> for Selectable {
+	fn from(value: crate::workspace::Subscription) -> Self {
+		Self::Subscription(value)
+	}
+}
+
+/// Wait on all given controllers at once, returning the index of the first one ready.
+///
+/// The outcome of [`AsyncReceiver::poll`] is returned along with the index: an error means that
+/// controller can't be waited on anymore, and should likely be removed before selecting again.
+/// Nothing is consumed, so the ready controller should be checked with [`AsyncReceiver::try_recv`].
 ///
-/// It will spawn tasks blocked on [`AsyncReceiver::poll`] for each buffer controller.
-/// As soon as one finishes, its controller is returned and all other tasks are canceled.
+/// Everything runs on the current task. The result is `None` if no controllers were given or
+/// if the timeout, when provided, expires before any controller is ready.
+pub async fn select(
+	controllers: &[Selectable],
+	timeout: Option<std::time::Duration>,
+) -> Option<(usize, ControllerResult<()>)> {
+	if controllers.is_empty() {
+		return None;
+	}
+	let mut polls = controllers
+		.iter()
+		.map(|c| Box::pin(c.poll()))
+		.collect::<Vec<_>>();
+	let any = std::future::poll_fn(|cx| {
+		for (i, poll) in polls.iter_mut().enumerate() {
+			if let Poll::Ready(res) = poll.as_mut().poll(cx) {
+				return Poll::Ready((i, res));
+			}
+		}
+		Poll::Pending
+	});
+	match timeout {
+		None => Some(any.await),
+		Some(d) => tokio::time::timeout(d, any).await.ok(),
+	}
+}
+
+/// Poll all given buffer controllers and wait, returning the first one ready.
 ///
-/// If a timeout is provided, the result may be `None` if it expires before any task is
-/// complete.
+/// Buffers failing while polling are skipped. If a timeout is provided, the result may be
+/// `None` if it expires before any buffer is ready.
 ///
 /// It may return an error if all buffers returned errors while polling.
+#[deprecated(note = "use select, which accepts any controller and reports errors")]
 pub async fn select_buffer(
 	buffers: &[crate::buffer::Controller],
 	timeout: Option<std::time::Duration>,
-	runtime: &tokio::runtime::Runtime,
+	_runtime: &tokio::runtime::Runtime,
 ) -> ControllerResult<Option<crate::buffer::Controller>> {
-	let (tx, mut rx) = mpsc::unbounded_channel();
-	let mut tasks = Vec::new();
-	for buffer in buffers {
-		let _tx = tx.clone();
-		let _buffer = buffer.clone();
-		tasks.push(runtime.spawn(async move {
-			match _buffer.poll().await {
-				Ok(()) => _tx.send(Ok(Some(_buffer))),
-				Err(e) => _tx.send(Err(e)),
+	let mut pending = buffers.to_vec();
+	let deadline = timeout.map(|d| tokio::time::Instant::now() + d);
+	loop {
+		let selectables = pending
+			.iter()
+			.cloned()
+			.map(Selectable::Buffer)
+			.collect::<Vec<_>>();
+		let timeout = deadline.map(|d| d.saturating_duration_since(tokio::time::Instant::now()));
+		match select(&selectables, timeout).await {
+			None if pending.is_empty() && !buffers.is_empty() => {
+				return Err(ControllerError::Unfulfilled)
+			}
+			None => return Ok(None),
+			Some((i, Ok(()))) => return Ok(Some(pending.swap_remove(i))),
+			Some((i, Err(e))) => {
+				let buffer = pending.swap_remove(i);
+				tracing::warn!("skipping buffer {} failing to poll: {e}", buffer.path());
 			}
-		}))
+		}
 	}
-	if let Some(d) = timeout {
-		let _tx = tx.clone();
-		tasks.push(runtime.spawn(async move {
-			tokio::time::sleep(d).await;
-			_tx.send(Ok(None))
-		}));
+}
+
+type BoxedRecv<T> = Pin<Box<dyn Future<Output = ControllerResult<T>> + Send>>;
+
+/// A [`tokio_stream::Stream`] of values received from a controller, see for example
+/// [`crate::buffer::Controller::into_stream`].
+///
+/// Values are only requested from the controller while the stream is being polled, so consumers
+/// naturally apply backpressure. Once the underlying worker stops, the stream ends: if that
+/// happened while a value was being requested, one last [`ControllerError::Unfulfilled`] is
+/// yielded first.
+///
+/// Note that the stream holds a handle to its controller, keeping the worker alive.
+pub struct ControllerStream<T> {
+	recv: Box<dyn Fn() -> BoxedRecv<T> + Send + Sync>,
+	next: Option<BoxedRecv<T>>,
+	done: bool,
+}
+
+impl<T> ControllerStream<T> {
+	pub(crate) fn new(recv: impl Fn() -> BoxedRecv<T> + Send + Sync + 'static) -> Self {
+		Self {
+			recv: Box::new(recv),
+			next: None,
+			done: false,
+		}
 	}
-	loop {
-		match rx.recv().await {
-			None => return Err(crate::errors::ControllerError::Unfulfilled),
-			Some(Err(_)) => continue, // TODO log errors maybe?
-			Some(Ok(x)) => {
-				for t in tasks {
-					t.abort();
-				}
-				return Ok(x);
+}
+
+impl<T> tokio_stream::Stream for ControllerStream<T> {
+	type Item = ControllerResult<T>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if self.done {
+			return Poll::Ready(None);
+		}
+		let mut next = match self.next.take() {
+			Some(next) => next,
+			None => (self.recv)(),
+		};
+		match next.as_mut().poll(cx) {
+			Poll::Pending => {
+				self.next = Some(next);
+				Poll::Pending
+			}
+			Poll::Ready(Ok(x)) => Poll::Ready(Some(Ok(x))),
+			Poll::Ready(Err(ControllerError::Stopped)) => {
+				self.done = true;
+				Poll::Ready(None)
+			}
+			Poll::Ready(Err(e)) => {
+				self.done = true;
+				Poll::Ready(Some(Err(e)))
 			}
 		}
 	}
 }
 
+impl<T> std::fmt::Debug for ControllerStream<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ControllerStream")
+			.field("pending", &self.next.is_some())
+			.field("done", &self.done)
+			.finish()
+	}
+}
+
 /// Hash a given byte array with the internally used algorithm.
 ///
 /// Currently, it uses [`xxhash_rust::xxh3::xxh3_64`].
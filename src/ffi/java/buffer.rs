@@ -2,7 +2,10 @@ use jni::{objects::JObject, JNIEnv};
 use jni_toolbox::jni;
 
 use crate::{
-	api::{AsyncReceiver, AsyncSender, BufferUpdate, TextChange},
+	api::{
+		Annotation, AsyncReceiver, AsyncSender, BufferMetadata, BufferUpdate, LineEnding,
+		TextChange,
+	},
 	errors::ControllerError,
 };
 
@@ -20,6 +23,21 @@ fn get_content(controller: &mut crate::buffer::Controller) -> Result<String, Con
 	super::tokio().block_on(controller.content())
 }
 
+/// Check whether the given hash matches the content the editor should be displaying.
+#[jni(package = "mp.code", class = "BufferController")]
+fn verify(controller: &mut crate::buffer::Controller, hash: i64) -> Result<bool, ControllerError> {
+	super::tokio().block_on(controller.verify(hash))
+}
+
+/// Get the [TextChange] needed to bring the given content back in sync, or null if it already is.
+#[jni(package = "mp.code", class = "BufferController")]
+fn resync(
+	controller: &mut crate::buffer::Controller,
+	content: String,
+) -> Result<Option<TextChange>, ControllerError> {
+	super::tokio().block_on(controller.resync(&content))
+}
+
 /// Try to fetch a [TextChange], or return null if there's nothing.
 #[jni(package = "mp.code", class = "BufferController")]
 fn try_recv(
@@ -34,6 +52,12 @@ fn recv(controller: &mut crate::buffer::Controller) -> Result<BufferUpdate, Cont
 	super::tokio().block_on(controller.recv())
 }
 
+/// Check whether the current user may only read this buffer.
+#[jni(package = "mp.code", class = "BufferController")]
+fn is_read_only(controller: &mut crate::buffer::Controller) -> bool {
+	controller.is_read_only()
+}
+
 /// Send a [TextChange] to the server.
 #[jni(package = "mp.code", class = "BufferController")]
 fn send(
@@ -102,6 +126,171 @@ fn ack(controller: &mut crate::buffer::Controller, version: Vec<i64>) {
 	controller.ack(version)
 }
 
+/// Annotate a range of the buffer, returning the id of the new [Annotation].
+#[jni(package = "mp.code", class = "BufferController")]
+fn annotate(
+	controller: &mut crate::buffer::Controller,
+	start_idx: i64,
+	end_idx: i64,
+	text: String,
+) -> Result<String, ControllerError> {
+	controller.annotate(
+		start_idx.clamp(0, u32::MAX.into()) as u32,
+		end_idx.clamp(0, u32::MAX.into()) as u32,
+		&text,
+	)
+}
+
+/// Mark an [Annotation] as resolved.
+#[jni(package = "mp.code", class = "BufferController")]
+fn resolve_annotation(
+	controller: &mut crate::buffer::Controller,
+	id: String,
+) -> Result<(), ControllerError> {
+	controller.resolve_annotation(&id)
+}
+
+/// Delete an [Annotation].
+#[jni(package = "mp.code", class = "BufferController")]
+fn delete_annotation(
+	controller: &mut crate::buffer::Controller,
+	id: String,
+) -> Result<(), ControllerError> {
+	controller.delete_annotation(&id)
+}
+
+/// Get all [Annotation]s of the buffer.
+#[jni(package = "mp.code", class = "BufferController")]
+fn annotations(
+	controller: &mut crate::buffer::Controller,
+) -> Result<Vec<Annotation>, ControllerError> {
+	super::tokio().block_on(controller.annotations())
+}
+
+/// Register a callback for annotation changes.
+#[jni(package = "mp.code", class = "BufferController")]
+fn annotation_callback<'local>(
+	env: &mut JNIEnv<'local>,
+	controller: &mut crate::buffer::Controller,
+	cb: JObject<'local>,
+) {
+	null_check!(env, cb, {});
+	let Ok(cb_ref) = env.new_global_ref(cb) else {
+		env.throw_new(
+			"mp/code/exceptions/JNIException",
+			"Failed to pin callback reference!",
+		)
+		.expect("Failed to throw exception!");
+		return;
+	};
+
+	controller.annotation_callback(move |controller: crate::buffer::Controller| {
+		let jvm = super::jvm();
+		let mut env = jvm
+			.attach_current_thread_permanently()
+			.expect("failed attaching to main JVM thread");
+		if let Err(e) = env.with_local_frame(5, |env| {
+			use jni_toolbox::IntoJavaObject;
+			let jcontroller = controller.into_java_object(env)?;
+			if let Err(e) = env.call_method(
+				&cb_ref,
+				"accept",
+				"(Ljava/lang/Object;)V",
+				&[jni::objects::JValueGen::Object(&jcontroller)],
+			) {
+				tracing::error!("error invoking callback: {e:?}");
+			};
+			Ok::<(), jni::errors::Error>(())
+		}) {
+			tracing::error!("error invoking callback: {e}");
+			let _ = env.exception_describe();
+		}
+	});
+}
+
+/// Clear the callback for annotation changes.
+#[jni(package = "mp.code", class = "BufferController")]
+fn clear_annotation_callback(controller: &mut crate::buffer::Controller) {
+	controller.clear_annotation_callback()
+}
+
+/// Get the latest [BufferMetadata] announced for the buffer.
+#[jni(package = "mp.code", class = "BufferController")]
+fn get_metadata(controller: &mut crate::buffer::Controller) -> BufferMetadata {
+	controller.metadata()
+}
+
+/// Register a callback for metadata changes.
+#[jni(package = "mp.code", class = "BufferController")]
+fn metadata_callback<'local>(
+	env: &mut JNIEnv<'local>,
+	controller: &mut crate::buffer::Controller,
+	cb: JObject<'local>,
+) {
+	null_check!(env, cb, {});
+	let Ok(cb_ref) = env.new_global_ref(cb) else {
+		env.throw_new(
+			"mp/code/exceptions/JNIException",
+			"Failed to pin callback reference!",
+		)
+		.expect("Failed to throw exception!");
+		return;
+	};
+
+	controller.metadata_callback(move |controller: crate::buffer::Controller| {
+		let jvm = super::jvm();
+		let mut env = jvm
+			.attach_current_thread_permanently()
+			.expect("failed attaching to main JVM thread");
+		if let Err(e) = env.with_local_frame(5, |env| {
+			use jni_toolbox::IntoJavaObject;
+			let jcontroller = controller.into_java_object(env)?;
+			if let Err(e) = env.call_method(
+				&cb_ref,
+				"accept",
+				"(Ljava/lang/Object;)V",
+				&[jni::objects::JValueGen::Object(&jcontroller)],
+			) {
+				tracing::error!("error invoking callback: {e:?}");
+			};
+			Ok::<(), jni::errors::Error>(())
+		}) {
+			tracing::error!("error invoking callback: {e}");
+			let _ = env.exception_describe();
+		}
+	});
+}
+
+/// Clear the callback for metadata changes.
+#[jni(package = "mp.code", class = "BufferController")]
+fn clear_metadata_callback(controller: &mut crate::buffer::Controller) {
+	controller.clear_metadata_callback()
+}
+
+/// Toggle replacing line endings of sent changes with the ones of the [BufferMetadata].
+#[jni(package = "mp.code", class = "BufferController")]
+fn normalize_line_endings(controller: &mut crate::buffer::Controller, enabled: bool) {
+	controller.normalize_line_endings(enabled)
+}
+
+/// Check whether line endings of sent changes are being normalized.
+#[jni(package = "mp.code", class = "BufferController")]
+fn is_normalizing_line_endings(controller: &mut crate::buffer::Controller) -> bool {
+	controller.is_normalizing_line_endings()
+}
+
+/// Translate line endings for an editor using given [LineEnding], or stop if null.
+#[jni(package = "mp.code", class = "BufferController")]
+fn translate_line_endings(controller: &mut crate::buffer::Controller, local: Option<LineEnding>) {
+	controller.translate_line_endings(local)
+}
+
+/// Get the [LineEnding] of the editor, or null if line endings are not translated.
+#[jni(package = "mp.code", class = "BufferController")]
+fn local_line_ending(controller: &mut crate::buffer::Controller) -> Option<LineEnding> {
+	controller.local_line_ending()
+}
+
 /// Called by the Java GC to drop a [crate::buffer::Controller].
 #[jni(package = "mp.code", class = "BufferController")]
 fn free(input: jni::sys::jlong) {
@@ -0,0 +1,97 @@
+use crate::{
+	api::{AsyncReceiver, AsyncSender, Draft, Message},
+	errors::ControllerError,
+};
+use jni::{objects::JObject, JNIEnv};
+use jni_toolbox::jni;
+
+use super::null_check;
+
+/// Try to fetch a [Message], or returns null if there's nothing.
+#[jni(package = "mp.code", class = "ChatController")]
+fn try_recv(controller: &mut crate::chat::Controller) -> Result<Option<Message>, ControllerError> {
+	super::tokio().block_on(controller.try_recv())
+}
+
+/// Block until it receives a [Message].
+#[jni(package = "mp.code", class = "ChatController")]
+fn recv(controller: &mut crate::chat::Controller) -> Result<Message, ControllerError> {
+	super::tokio().block_on(controller.recv())
+}
+
+/// Receive from Java, converts and sends a [Draft].
+#[jni(package = "mp.code", class = "ChatController")]
+fn send(controller: &mut crate::chat::Controller, draft: Draft) -> Result<(), ControllerError> {
+	controller.send(draft)
+}
+
+/// Get the last [Message]s received, oldest first.
+#[jni(package = "mp.code", class = "ChatController")]
+fn history(controller: &mut crate::chat::Controller) -> Vec<Message> {
+	controller.history()
+}
+
+/// Get the last [Message]s received in threads about given buffer, oldest first.
+#[jni(package = "mp.code", class = "ChatController")]
+fn thread_history(controller: &mut crate::chat::Controller, buffer: String) -> Vec<Message> {
+	controller.thread_history(&buffer)
+}
+
+/// Register a callback for new messages.
+#[jni(package = "mp.code", class = "ChatController")]
+fn callback<'local>(
+	env: &mut JNIEnv<'local>,
+	controller: &mut crate::chat::Controller,
+	cb: JObject<'local>,
+) {
+	null_check!(env, cb, {});
+	let Ok(cb_ref) = env.new_global_ref(cb) else {
+		env.throw_new(
+			"mp/code/exceptions/JNIException",
+			"Failed to pin callback reference!",
+		)
+		.expect("Failed to throw exception!");
+		return;
+	};
+
+	controller.callback(move |controller: crate::chat::Controller| {
+		let jvm = super::jvm();
+		let mut env = jvm
+			.attach_current_thread_permanently()
+			.expect("failed attaching to main JVM thread");
+		if let Err(e) = env.with_local_frame(5, |env| {
+			use jni_toolbox::IntoJavaObject;
+			let jcontroller = controller.into_java_object(env)?;
+			if let Err(e) = env.call_method(
+				&cb_ref,
+				"accept",
+				"(Ljava/lang/Object;)V",
+				&[jni::objects::JValueGen::Object(&jcontroller)],
+			) {
+				tracing::error!("error invoking callback: {e:?}");
+			};
+			Ok::<(), jni::errors::Error>(())
+		}) {
+			tracing::error!("error invoking callback: {e}");
+			let _ = env.exception_describe();
+		}
+	});
+}
+
+/// Clear the callback for new messages.
+#[jni(package = "mp.code", class = "ChatController")]
+fn clear_callback(controller: &mut crate::chat::Controller) {
+	controller.clear_callback()
+}
+
+/// Block until there is a new value available.
+#[jni(package = "mp.code", class = "ChatController")]
+fn poll(controller: &mut crate::chat::Controller) -> Result<(), ControllerError> {
+	super::tokio().block_on(controller.poll())
+}
+
+/// Called by the Java GC to drop a [crate::chat::Controller].
+#[jni(package = "mp.code", class = "ChatController")]
+fn free(input: jni::sys::jlong) {
+	let _ = unsafe { Box::from_raw(input as *mut crate::chat::Controller) };
+}
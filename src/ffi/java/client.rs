@@ -1,5 +1,5 @@
 use crate::{
-	api::Config,
+	api::{Config, Member, Role},
 	client::Client,
 	errors::{ConnectionError, RemoteError},
 	Workspace,
@@ -12,6 +12,16 @@ fn connect(config: Config) -> Result<Client, ConnectionError> {
 	super::tokio().block_on(Client::connect(config))
 }
 
+/// Load a [Config] from given file (or the default one) and the environment, see [crate::loader].
+#[allow(non_snake_case)]
+#[jni(package = "mp.code", class = "Client")]
+fn loadConfig(path: Option<String>, explicit: Config) -> Result<Config, ConnectionError> {
+	match path {
+		Some(path) => crate::loader::load_file(path, explicit),
+		None => crate::loader::load(explicit),
+	}
+}
+
 /// Gets the current [crate::api::User].
 #[jni(package = "mp.code", class = "Client")]
 fn current_user(client: &mut Client) -> crate::api::User {
@@ -46,6 +56,46 @@ fn invite_to_workspace(
 	super::tokio().block_on(client.invite_to_workspace(workspace, user))
 }
 
+/// List the members of a workspace, including pending invites.
+#[jni(package = "mp.code", class = "Client")]
+fn fetch_workspace_members(
+	client: &mut Client,
+	workspace: String,
+) -> Result<Vec<Member>, RemoteError> {
+	super::tokio().block_on(client.fetch_workspace_members(workspace))
+}
+
+/// Remove a member from an owned workspace.
+#[jni(package = "mp.code", class = "Client")]
+fn remove_from_workspace(
+	client: &mut Client,
+	workspace: String,
+	user: String,
+) -> Result<(), RemoteError> {
+	super::tokio().block_on(client.remove_from_workspace(workspace, user))
+}
+
+/// Change the [Role] of a member of an owned workspace.
+#[jni(package = "mp.code", class = "Client")]
+fn set_workspace_role(
+	client: &mut Client,
+	workspace: String,
+	user: String,
+	role: Role,
+) -> Result<(), RemoteError> {
+	super::tokio().block_on(client.set_workspace_role(workspace, user, role))
+}
+
+/// Change the display name and avatar shown to other users.
+#[jni(package = "mp.code", class = "Client")]
+fn set_profile(
+	client: &mut Client,
+	display_name: Option<String>,
+	avatar: Option<String>,
+) -> Result<(), RemoteError> {
+	super::tokio().block_on(client.set_profile(display_name, avatar))
+}
+
 /// List owned workspaces.
 #[jni(package = "mp.code", class = "Client")]
 fn fetch_owned_workspaces(client: &mut Client) -> Result<Vec<String>, RemoteError> {
@@ -0,0 +1,104 @@
+use crate::{
+	api::{AsyncReceiver, AsyncSender, Decoration},
+	errors::ControllerError,
+};
+use jni::{objects::JObject, JNIEnv};
+use jni_toolbox::jni;
+
+use super::null_check;
+
+/// Get the path of the buffer decorated.
+#[jni(package = "mp.code", class = "DecorationController")]
+fn get_path(controller: &mut crate::decoration::Controller) -> String {
+	controller.path().to_string()
+}
+
+/// Try to fetch a [Decoration], or returns null if there's nothing.
+#[jni(package = "mp.code", class = "DecorationController")]
+fn try_recv(
+	controller: &mut crate::decoration::Controller,
+) -> Result<Option<Decoration>, ControllerError> {
+	super::tokio().block_on(controller.try_recv())
+}
+
+/// Block until it receives a [Decoration].
+#[jni(package = "mp.code", class = "DecorationController")]
+fn recv(controller: &mut crate::decoration::Controller) -> Result<Decoration, ControllerError> {
+	super::tokio().block_on(controller.recv())
+}
+
+/// Receive from Java, converts and sends a [Decoration].
+#[jni(package = "mp.code", class = "DecorationController")]
+fn send(
+	controller: &mut crate::decoration::Controller,
+	decoration: Decoration,
+) -> Result<(), ControllerError> {
+	controller.send(decoration)
+}
+
+/// Get the [Decoration]s received which didn't expire yet, oldest first.
+#[jni(package = "mp.code", class = "DecorationController")]
+fn active(
+	controller: &mut crate::decoration::Controller,
+) -> Result<Vec<Decoration>, ControllerError> {
+	super::tokio().block_on(controller.active())
+}
+
+/// Register a callback for new decorations.
+#[jni(package = "mp.code", class = "DecorationController")]
+fn callback<'local>(
+	env: &mut JNIEnv<'local>,
+	controller: &mut crate::decoration::Controller,
+	cb: JObject<'local>,
+) {
+	null_check!(env, cb, {});
+	let Ok(cb_ref) = env.new_global_ref(cb) else {
+		env.throw_new(
+			"mp/code/exceptions/JNIException",
+			"Failed to pin callback reference!",
+		)
+		.expect("Failed to throw exception!");
+		return;
+	};
+
+	controller.callback(move |controller: crate::decoration::Controller| {
+		let jvm = super::jvm();
+		let mut env = jvm
+			.attach_current_thread_permanently()
+			.expect("failed attaching to main JVM thread");
+		if let Err(e) = env.with_local_frame(5, |env| {
+			use jni_toolbox::IntoJavaObject;
+			let jcontroller = controller.into_java_object(env)?;
+			if let Err(e) = env.call_method(
+				&cb_ref,
+				"accept",
+				"(Ljava/lang/Object;)V",
+				&[jni::objects::JValueGen::Object(&jcontroller)],
+			) {
+				tracing::error!("error invoking callback: {e:?}");
+			};
+			Ok::<(), jni::errors::Error>(())
+		}) {
+			tracing::error!("error invoking callback: {e}");
+			let _ = env.exception_describe();
+		}
+	});
+}
+
+/// Clear the callback for new decorations.
+#[jni(package = "mp.code", class = "DecorationController")]
+fn clear_callback(controller: &mut crate::decoration::Controller) {
+	controller.clear_callback()
+}
+
+/// Block until there is a new value available.
+#[jni(package = "mp.code", class = "DecorationController")]
+fn poll(controller: &mut crate::decoration::Controller) -> Result<(), ControllerError> {
+	super::tokio().block_on(controller.poll())
+}
+
+/// Called by the Java GC to drop a [crate::decoration::Controller].
+#[jni(package = "mp.code", class = "DecorationController")]
+fn free(input: jni::sys::jlong) {
+	let _ = unsafe { Box::from_raw(input as *mut crate::decoration::Controller) };
+}
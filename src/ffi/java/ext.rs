@@ -13,6 +13,13 @@ fn hash(content: String) -> i64 {
 	i64::from_ne_bytes(hash.to_ne_bytes())
 }
 
+/// Gets the color assigned to a user, as `#rrggbb`, optionally adapted to a [crate::api::Theme].
+#[allow(non_snake_case)]
+#[jni(package = "mp.code", class = "Extensions")]
+fn userColor(id: uuid::Uuid, theme: Option<crate::api::Theme>) -> String {
+	crate::api::color::user_color(id, theme).to_string()
+}
+
 /// Tells the [tokio] runtime how to drive the event loop.
 #[jni(package = "mp.code", class = "Extensions")]
 fn drive(block: bool) {
@@ -1,6 +1,8 @@
 pub mod buffer;
+pub mod chat;
 pub mod client;
 pub mod cursor;
+pub mod decoration;
 pub mod ext;
 pub mod workspace;
 
@@ -86,19 +88,35 @@ impl jni_toolbox::JniToolboxError for crate::errors::ConnectionError {
 	fn jclass(&self) -> String {
 		match self {
 			crate::errors::ConnectionError::Transport(_) => {
-				"mp/code/exceptions/ConnectionTransportException"
+				"mp/code/exceptions/ConnectionTransportException".to_string()
 			}
-			crate::errors::ConnectionError::Remote(_) => {
-				"mp/code/exceptions/ConnectionRemoteException"
+			crate::errors::ConnectionError::Remote(e) => e.jclass(),
+			crate::errors::ConnectionError::InvalidConfig(_) => {
+				"mp/code/exceptions/ConnectionConfigException".to_string()
+			}
+			crate::errors::ConnectionError::Auth(_) => {
+				"mp/code/exceptions/ConnectionAuthException".to_string()
 			}
 		}
-		.to_string()
 	}
 }
 
 impl jni_toolbox::JniToolboxError for crate::errors::RemoteError {
 	fn jclass(&self) -> String {
-		"mp/code/exceptions/ConnectionRemoteException".to_string()
+		use crate::errors::ErrorKind;
+		let kind = match self.kind() {
+			ErrorKind::NotFound => "NotFound",
+			ErrorKind::Unauthorized => "Unauthorized",
+			ErrorKind::Forbidden => "Forbidden",
+			ErrorKind::AlreadyExists => "AlreadyExists",
+			ErrorKind::Unavailable => "Unavailable",
+			ErrorKind::InvalidArgument => "InvalidArgument",
+			ErrorKind::TimedOut => "TimedOut",
+			ErrorKind::RateLimited => "RateLimited",
+			ErrorKind::Conflict => "Conflict",
+			_ => "Other",
+		};
+		format!("mp/code/exceptions/Remote{kind}Exception")
 	}
 }
 
@@ -111,6 +129,9 @@ impl jni_toolbox::JniToolboxError for crate::errors::ControllerError {
 			crate::errors::ControllerError::Unfulfilled => {
 				"mp/code/exceptions/ControllerUnfulfilledException"
 			}
+			crate::errors::ControllerError::ReadOnly => {
+				"mp/code/exceptions/ControllerReadOnlyException"
+			}
 		}
 		.to_string()
 	}
@@ -142,6 +163,11 @@ into_java_ptr_class!(crate::Client, "mp/code/Client");
 into_java_ptr_class!(crate::Workspace, "mp/code/Workspace");
 into_java_ptr_class!(crate::cursor::Controller, "mp/code/CursorController");
 into_java_ptr_class!(crate::buffer::Controller, "mp/code/BufferController");
+into_java_ptr_class!(crate::chat::Controller, "mp/code/ChatController");
+into_java_ptr_class!(
+	crate::decoration::Controller,
+	"mp/code/DecorationController"
+);
 
 impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::User {
 	const CLASS: &'static str = "mp/code/data/User";
@@ -163,6 +189,95 @@ impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::User {
 	}
 }
 
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Role {
+	const CLASS: &'static str = "mp/code/data/Role";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let ordinal = match self {
+			crate::api::Role::ReadWrite => 0,
+			crate::api::Role::ReadOnly => 1,
+			crate::api::Role::Owner => 2,
+		};
+		let class = env.find_class(Self::CLASS)?;
+		let variants: jni::objects::JObjectArray = env
+			.call_method(class, "getEnumConstants", "()[Ljava/lang/Object;", &[])?
+			.l()?
+			.into();
+		env.get_object_array_element(variants, ordinal)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Member {
+	const CLASS: &'static str = "mp/code/data/Member";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let user_field = self.user.into_java_object(env)?;
+		let role_field = self.role.into_java_object(env)?;
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			&class,
+			"(Lmp/code/data/User;Lmp/code/data/Role;Z)V",
+			&[
+				jni::objects::JValueGen::Object(&user_field),
+				jni::objects::JValueGen::Object(&role_field),
+				jni::objects::JValueGen::Bool(self.pending.into()),
+			],
+		)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Status {
+	const CLASS: &'static str = "mp/code/data/Status";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let ordinal = match self {
+			crate::api::Status::Active => 0,
+			crate::api::Status::Idle => 1,
+			crate::api::Status::Away => 2,
+		};
+		let class = env.find_class(Self::CLASS)?;
+		let variants: jni::objects::JObjectArray = env
+			.call_method(class, "getEnumConstants", "()[Ljava/lang/Object;", &[])?
+			.l()?
+			.into();
+		env.get_object_array_element(variants, ordinal)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Presence {
+	const CLASS: &'static str = "mp/code/data/Presence";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let user_field = self.user.into_java_object(env)?;
+		let display_name_field = optional_string(env, self.display_name)?;
+		let avatar_field = optional_string(env, self.avatar)?;
+		let color_field = env.new_string(self.color)?;
+		let status_field = self.status.into_java_object(env)?;
+		let buffer_field = optional_string(env, self.buffer)?;
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			&class,
+			"(Lmp/code/data/User;Ljava/util/Optional;Ljava/util/Optional;Ljava/lang/String;Lmp/code/data/Status;Ljava/util/Optional;)V",
+			&[
+				jni::objects::JValueGen::Object(&user_field),
+				jni::objects::JValueGen::Object(&display_name_field),
+				jni::objects::JValueGen::Object(&avatar_field),
+				jni::objects::JValueGen::Object(&color_field),
+				jni::objects::JValueGen::Object(&status_field),
+				jni::objects::JValueGen::Object(&buffer_field),
+			],
+		)
+	}
+}
+
 impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Event {
 	const CLASS: &'static str = "mp/code/Workspace$Event";
 	fn into_java_object(
@@ -173,6 +288,7 @@ impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Event {
 			crate::api::Event::UserJoin { name: arg } => (0, env.new_string(arg)?),
 			crate::api::Event::UserLeave { name: arg } => (1, env.new_string(arg)?),
 			crate::api::Event::FileTreeUpdated { path: arg } => (2, env.new_string(arg)?),
+			crate::api::Event::PresenceChanged { name: arg } => (3, env.new_string(arg)?),
 		};
 
 		let type_class = env.find_class("mp/code/Workspace$Event$Type")?;
@@ -250,6 +366,150 @@ impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::TextChange {
 	}
 }
 
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Annotation {
+	const CLASS: &'static str = "mp/code/data/Annotation";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let class = env.find_class(Self::CLASS)?;
+		let id = env.new_string(&self.id)?;
+		let author = env.new_string(&self.author)?;
+		let text = env.new_string(&self.text)?;
+		env.new_object(
+			class,
+			"(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;JJZ)V",
+			&[
+				jni::objects::JValueGen::Object(&id),
+				jni::objects::JValueGen::Object(&author),
+				jni::objects::JValueGen::Object(&text),
+				jni::objects::JValueGen::Long(self.start_idx.into()),
+				jni::objects::JValueGen::Long(self.end_idx.into()),
+				jni::objects::JValueGen::Bool(self.resolved.into()),
+			],
+		)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::DecorationKind {
+	const CLASS: &'static str = "mp/code/data/DecorationKind";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let ordinal = match self {
+			crate::api::DecorationKind::Highlight => 0,
+			crate::api::DecorationKind::Mark => 1,
+			crate::api::DecorationKind::Error => 2,
+			crate::api::DecorationKind::Warning => 3,
+			crate::api::DecorationKind::Info => 4,
+			crate::api::DecorationKind::Hint => 5,
+		};
+		let class = env.find_class(Self::CLASS)?;
+		let variants: jni::objects::JObjectArray = env
+			.call_method(class, "getEnumConstants", "()[Ljava/lang/Object;", &[])?
+			.l()?
+			.into();
+		env.get_object_array_element(variants, ordinal)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Decoration {
+	const CLASS: &'static str = "mp/code/data/Decoration";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let kind = self.kind.into_java_object(env)?;
+		let message = env.new_string(&self.message)?;
+		let author = env.new_string(&self.author)?;
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			class,
+			"(Lmp/code/data/DecorationKind;Ljava/lang/String;Ljava/lang/String;JJJ)V",
+			&[
+				jni::objects::JValueGen::Object(&kind),
+				jni::objects::JValueGen::Object(&message),
+				jni::objects::JValueGen::Object(&author),
+				jni::objects::JValueGen::Long(self.start_idx.into()),
+				jni::objects::JValueGen::Long(self.end_idx.into()),
+				jni::objects::JValueGen::Long(self.ttl_ms.into()),
+			],
+		)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::LineEnding {
+	const CLASS: &'static str = "mp/code/data/LineEnding";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let ordinal = match self {
+			crate::api::LineEnding::Lf => 0,
+			crate::api::LineEnding::Crlf => 1,
+			crate::api::LineEnding::Cr => 2,
+		};
+		let class = env.find_class(Self::CLASS)?;
+		let variants: jni::objects::JObjectArray = env
+			.call_method(class, "getEnumConstants", "()[Ljava/lang/Object;", &[])?
+			.l()?
+			.into();
+		env.get_object_array_element(variants, ordinal)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Indentation {
+	const CLASS: &'static str = "mp/code/data/Indentation";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			class,
+			"(ZI)V",
+			&[
+				jni::objects::JValueGen::Bool(self.tabs.into()),
+				jni::objects::JValueGen::Int(self.width.min(i32::MAX as u32) as i32),
+			],
+		)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::BufferMetadata {
+	const CLASS: &'static str = "mp/code/data/BufferMetadata";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let language = optional_string(env, self.language)?;
+		let line_ending = match self.line_ending {
+			Some(line_ending) => Some(line_ending.into_java_object(env)?),
+			None => None,
+		};
+		let line_ending = optional(env, line_ending)?;
+		let indentation = match self.indentation {
+			Some(indentation) => Some(indentation.into_java_object(env)?),
+			None => None,
+		};
+		let indentation = optional(env, indentation)?;
+		let encoding = optional_string(env, self.encoding)?;
+
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			class,
+			"(Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;)V",
+			&[
+				jni::objects::JValueGen::Object(&language),
+				jni::objects::JValueGen::Object(&line_ending),
+				jni::objects::JValueGen::Object(&indentation),
+				jni::objects::JValueGen::Object(&encoding),
+			],
+		)
+	}
+}
+
 impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Cursor {
 	const CLASS: &'static str = "mp/code/data/Cursor";
 	fn into_java_object(
@@ -271,6 +531,34 @@ impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Cursor {
 	}
 }
 
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Message {
+	const CLASS: &'static str = "mp/code/data/Message";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let class = env.find_class(Self::CLASS)?;
+		let user = env.new_string(&self.user)?;
+		let text = env.new_string(&self.text)?;
+		let thread = match self.thread {
+			Some(sel) => Some(sel.into_java_object(env)?),
+			None => None,
+		};
+		let thread = optional(env, thread)?;
+
+		env.new_object(
+			class,
+			"(Ljava/lang/String;Ljava/lang/String;JLjava/util/Optional;)V",
+			&[
+				jni::objects::JValueGen::Object(&user),
+				jni::objects::JValueGen::Object(&text),
+				jni::objects::JValueGen::Long(self.timestamp.min(i64::MAX as u64) as i64),
+				jni::objects::JValueGen::Object(&thread),
+			],
+		)
+	}
+}
+
 impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Selection {
 	const CLASS: &'static str = "mp/code/data/Selection";
 	fn into_java_object(
@@ -294,6 +582,212 @@ impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Selection {
 	}
 }
 
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Config {
+	const CLASS: &'static str = "mp/code/data/Config";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let username: jni::objects::JObject = env.new_string(self.username)?.into();
+		let password: jni::objects::JObject = env.new_string(self.password)?.into();
+		let token = optional_string(env, self.token)?;
+		let token_env = optional_string(env, self.token_env)?;
+		let token_command = optional_string(env, self.token_command)?;
+		let endpoint = optional_string(env, self.endpoint)?;
+		let host = optional_string(env, self.host)?;
+		let port = optional_int(env, self.port.map(i32::from))?;
+		let tls = optional_bool(env, self.tls)?;
+		let tls_settings = self
+			.tls_settings
+			.map(|x| jni_toolbox::IntoJavaObject::into_java_object(x, env))
+			.transpose()?;
+		let tls_settings = optional(env, tls_settings)?;
+		let proxy = optional_string(env, self.proxy)?;
+		let tuning = self
+			.tuning
+			.map(|x| jni_toolbox::IntoJavaObject::into_java_object(x, env))
+			.transpose()?;
+		let tuning = optional(env, tuning)?;
+
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			class,
+			"(Ljava/lang/String;Ljava/lang/String;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/OptionalInt;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;)V",
+			&[
+				jni::objects::JValueGen::Object(&username),
+				jni::objects::JValueGen::Object(&password),
+				jni::objects::JValueGen::Object(&token),
+				jni::objects::JValueGen::Object(&token_env),
+				jni::objects::JValueGen::Object(&token_command),
+				jni::objects::JValueGen::Object(&endpoint),
+				jni::objects::JValueGen::Object(&host),
+				jni::objects::JValueGen::Object(&port),
+				jni::objects::JValueGen::Object(&tls),
+				jni::objects::JValueGen::Object(&tls_settings),
+				jni::objects::JValueGen::Object(&proxy),
+				jni::objects::JValueGen::Object(&tuning),
+			],
+		)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::TlsSettings {
+	const CLASS: &'static str = "mp/code/data/TlsSettings";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let ca_bundle = optional_string(env, self.ca_bundle)?;
+		let native_roots = optional_bool(env, self.native_roots)?;
+		let client_cert = optional_string(env, self.client_cert)?;
+		let client_key = optional_string(env, self.client_key)?;
+		let domain = optional_string(env, self.domain)?;
+		let pinned_sha256 = optional_string(env, self.pinned_sha256)?;
+
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			class,
+			"(Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;Ljava/util/Optional;)V",
+			&[
+				jni::objects::JValueGen::Object(&ca_bundle),
+				jni::objects::JValueGen::Object(&native_roots),
+				jni::objects::JValueGen::Object(&client_cert),
+				jni::objects::JValueGen::Object(&client_key),
+				jni::objects::JValueGen::Object(&domain),
+				jni::objects::JValueGen::Object(&pinned_sha256),
+			],
+		)
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::Tuning {
+	const CLASS: &'static str = "mp/code/data/Tuning";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let callback_strategy = match self.callback_strategy {
+			Some(strategy) => Some(strategy.into_java_object(env)?),
+			None => None,
+		};
+		let callback_strategy = optional(env, callback_strategy)?;
+		let mut int =
+			|value: Option<u32>| optional_int(env, value.map(|x| x.min(i32::MAX as u32) as i32));
+		let fields = [
+			int(self.buffer_queue)?,
+			int(self.cursor_queue)?,
+			int(self.hash_period)?,
+			int(self.worker_tick_ms)?,
+			int(self.connect_timeout_ms)?,
+			int(self.request_timeout_ms)?,
+			int(self.http2_keepalive_ms)?,
+			int(self.http2_keepalive_timeout_ms)?,
+			int(self.tcp_keepalive_ms)?,
+			int(self.connect_retries)?,
+			int(self.retry_backoff_ms)?,
+			int(self.refresh_margin_ms)?,
+			callback_strategy,
+			optional_int(
+				env,
+				self.callback_queue.map(|x| x.min(i32::MAX as u32) as i32),
+			)?,
+			optional_int(
+				env,
+				self.idle_after_ms.map(|x| x.min(i32::MAX as u32) as i32),
+			)?,
+			optional_int(
+				env,
+				self.away_after_ms.map(|x| x.min(i32::MAX as u32) as i32),
+			)?,
+			optional_int(
+				env,
+				self.chat_history.map(|x| x.min(i32::MAX as u32) as i32),
+			)?,
+		];
+		let args = fields
+			.iter()
+			.map(jni::objects::JValueGen::Object)
+			.collect::<Vec<_>>();
+
+		let class = env.find_class(Self::CLASS)?;
+		env.new_object(
+			class,
+			"(Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/Optional;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;Ljava/util/OptionalInt;)V",
+			&args,
+		)
+	}
+}
+
+/// Wraps given object in a `java.util.Optional`.
+fn optional<'j>(
+	env: &mut jni::JNIEnv<'j>,
+	value: Option<jni::objects::JObject<'j>>,
+) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+	match value {
+		Some(value) => env.call_static_method(
+			"java/util/Optional",
+			"of",
+			"(Ljava/lang/Object;)Ljava/util/Optional;",
+			&[jni::objects::JValueGen::Object(&value)],
+		),
+		None => {
+			env.call_static_method("java/util/Optional", "empty", "()Ljava/util/Optional;", &[])
+		}
+	}?
+	.l()
+}
+
+fn optional_string<'j>(
+	env: &mut jni::JNIEnv<'j>,
+	value: Option<String>,
+) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+	let value = match value {
+		Some(value) => Some(env.new_string(value)?.into()),
+		None => None,
+	};
+	optional(env, value)
+}
+
+fn optional_bool<'j>(
+	env: &mut jni::JNIEnv<'j>,
+	value: Option<bool>,
+) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+	let value = match value {
+		Some(value) => Some(
+			env.call_static_method(
+				"java/lang/Boolean",
+				"valueOf",
+				"(Z)Ljava/lang/Boolean;",
+				&[jni::objects::JValueGen::Bool(value.into())],
+			)?
+			.l()?,
+		),
+		None => None,
+	};
+	optional(env, value)
+}
+
+fn optional_int<'j>(
+	env: &mut jni::JNIEnv<'j>,
+	value: Option<i32>,
+) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+	match value {
+		Some(value) => env.call_static_method(
+			"java/util/OptionalInt",
+			"of",
+			"(I)Ljava/util/OptionalInt;",
+			&[jni::objects::JValueGen::Int(value)],
+		),
+		None => env.call_static_method(
+			"java/util/OptionalInt",
+			"empty",
+			"()Ljava/util/OptionalInt;",
+			&[],
+		),
+	}?
+	.l()
+}
+
 macro_rules! from_java_ptr {
 	($type: ty) => {
 		impl<'j> jni_toolbox::FromJava<'j> for &mut $type {
@@ -390,12 +884,207 @@ impl<'j> jni_toolbox::FromJava<'j> for crate::api::Config {
 			}
 		};
 
+		let tls_settings = {
+			let jfield = env
+				.get_field(&config, "tlsSettings", "Ljava/util/Optional;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Some(crate::api::TlsSettings::from_java(env, field)?)
+			} else {
+				None
+			}
+		};
+
+		let tuning = {
+			let jfield = env
+				.get_field(&config, "tuning", "Ljava/util/Optional;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Some(crate::api::Tuning::from_java(env, field)?)
+			} else {
+				None
+			}
+		};
+
+		let mut optional_string = |name: &str| -> Result<Option<String>, jni::errors::Error> {
+			let jfield = env.get_field(&config, name, "Ljava/util/Optional;")?.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Ok(Some(
+					unsafe { env.get_string_unchecked(&field.into()) }?.into(),
+				))
+			} else {
+				Ok(None)
+			}
+		};
+
 		Ok(Self {
 			username,
 			password,
+			token: optional_string("token")?,
+			token_env: optional_string("tokenEnv")?,
+			token_command: optional_string("tokenCommand")?,
+			endpoint: optional_string("endpoint")?,
+			proxy: optional_string("proxy")?,
 			host,
 			port,
 			tls,
+			tls_settings,
+			tuning,
+		})
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::TlsSettings {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		settings: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		let native_roots = {
+			let jfield = env
+				.get_field(&settings, "nativeRoots", "Ljava/util/Optional;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Some(env.call_method(field, "booleanValue", "()Z", &[])?.z()?)
+			} else {
+				None
+			}
+		};
+
+		let mut optional_string = |name: &str| -> Result<Option<String>, jni::errors::Error> {
+			let jfield = env
+				.get_field(&settings, name, "Ljava/util/Optional;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Ok(Some(
+					unsafe { env.get_string_unchecked(&field.into()) }?.into(),
+				))
+			} else {
+				Ok(None)
+			}
+		};
+
+		Ok(Self {
+			ca_bundle: optional_string("caBundle")?,
+			native_roots,
+			client_cert: optional_string("clientCert")?,
+			client_key: optional_string("clientKey")?,
+			domain: optional_string("domain")?,
+			pinned_sha256: optional_string("pinnedSha256")?,
+		})
+	}
+}
+
+impl<'j> jni_toolbox::IntoJavaObject<'j> for crate::api::CallbackStrategy {
+	const CLASS: &'static str = "mp/code/data/CallbackStrategy";
+	fn into_java_object(
+		self,
+		env: &mut jni::JNIEnv<'j>,
+	) -> Result<jni::objects::JObject<'j>, jni::errors::Error> {
+		let ordinal = match self {
+			crate::api::CallbackStrategy::Inline => 0,
+			crate::api::CallbackStrategy::Task => 1,
+			crate::api::CallbackStrategy::Drop => 2,
+			crate::api::CallbackStrategy::Coalesce => 3,
+		};
+		let class = env.find_class(Self::CLASS)?;
+		let variants: jni::objects::JObjectArray = env
+			.call_method(class, "getEnumConstants", "()[Ljava/lang/Object;", &[])?
+			.l()?
+			.into();
+		env.get_object_array_element(variants, ordinal)
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::CallbackStrategy {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		strategy: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		if strategy.is_null() {
+			return Err(jni::errors::Error::NullPtr(
+				"CallbackStrategy can never be null!",
+			));
+		}
+		match env.call_method(&strategy, "ordinal", "()I", &[])?.i()? {
+			0 => Ok(crate::api::CallbackStrategy::Inline),
+			1 => Ok(crate::api::CallbackStrategy::Task),
+			2 => Ok(crate::api::CallbackStrategy::Drop),
+			3 => Ok(crate::api::CallbackStrategy::Coalesce),
+			_ => Err(jni::errors::Error::WrongJValueType(
+				"CallbackStrategy",
+				"unknown ordinal",
+			)),
+		}
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::Tuning {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		tuning: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		let callback_strategy = {
+			let jfield = env
+				.get_field(&tuning, "callbackStrategy", "Ljava/util/Optional;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Some(crate::api::CallbackStrategy::from_java(env, field)?)
+			} else {
+				None
+			}
+		};
+
+		let mut optional_int = |name: &str| -> Result<Option<u32>, jni::errors::Error> {
+			let jfield = env
+				.get_field(&tuning, name, "Ljava/util/OptionalInt;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let ivalue = env.call_method(&jfield, "getAsInt", "()I", &[])?.i()?;
+				Ok(Some(ivalue.max(0) as u32))
+			} else {
+				Ok(None)
+			}
+		};
+
+		Ok(Self {
+			buffer_queue: optional_int("bufferQueue")?,
+			cursor_queue: optional_int("cursorQueue")?,
+			hash_period: optional_int("hashPeriod")?,
+			worker_tick_ms: optional_int("workerTickMs")?,
+			connect_timeout_ms: optional_int("connectTimeoutMs")?,
+			request_timeout_ms: optional_int("requestTimeoutMs")?,
+			http2_keepalive_ms: optional_int("http2KeepaliveMs")?,
+			http2_keepalive_timeout_ms: optional_int("http2KeepaliveTimeoutMs")?,
+			tcp_keepalive_ms: optional_int("tcpKeepaliveMs")?,
+			connect_retries: optional_int("connectRetries")?,
+			retry_backoff_ms: optional_int("retryBackoffMs")?,
+			refresh_margin_ms: optional_int("refreshMarginMs")?,
+			callback_strategy,
+			callback_queue: optional_int("callbackQueue")?,
+			idle_after_ms: optional_int("idleAfterMs")?,
+			away_after_ms: optional_int("awayAfterMs")?,
+			chat_history: optional_int("chatHistory")?,
 		})
 	}
 }
@@ -463,3 +1152,225 @@ impl<'j> jni_toolbox::FromJava<'j> for crate::api::TextChange {
 		})
 	}
 }
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::Draft {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(env: &mut jni::JNIEnv<'j>, draft: Self::From) -> Result<Self, jni::errors::Error> {
+		let text = {
+			let jfield = env.get_field(&draft, "text", "Ljava/lang/String;")?.l()?;
+			if jfield.is_null() {
+				return Err(jni::errors::Error::NullPtr("Text can never be null!"));
+			}
+			unsafe { env.get_string_unchecked(&jfield.into()) }?.into()
+		};
+
+		let thread = {
+			let jfield = env
+				.get_field(&draft, "thread", "Ljava/util/Optional;")?
+				.l()?;
+			if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+				let field = env
+					.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+					.l()?;
+				Some(<crate::api::Selection as jni_toolbox::FromJava>::from_java(
+					env, field,
+				)?)
+			} else {
+				None
+			}
+		};
+
+		Ok(Self { text, thread })
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::DecorationKind {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(env: &mut jni::JNIEnv<'j>, kind: Self::From) -> Result<Self, jni::errors::Error> {
+		if kind.is_null() {
+			return Err(jni::errors::Error::NullPtr("Kind can never be null!"));
+		}
+		match env.call_method(&kind, "ordinal", "()I", &[])?.i()? {
+			0 => Ok(crate::api::DecorationKind::Highlight),
+			1 => Ok(crate::api::DecorationKind::Mark),
+			2 => Ok(crate::api::DecorationKind::Error),
+			3 => Ok(crate::api::DecorationKind::Warning),
+			4 => Ok(crate::api::DecorationKind::Info),
+			5 => Ok(crate::api::DecorationKind::Hint),
+			_ => Err(jni::errors::Error::WrongJValueType(
+				"DecorationKind",
+				"unknown ordinal",
+			)),
+		}
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::Decoration {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		decoration: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		let kind = {
+			let jfield = env
+				.get_field(&decoration, "kind", "Lmp/code/data/DecorationKind;")?
+				.l()?;
+			<crate::api::DecorationKind as jni_toolbox::FromJava>::from_java(env, jfield)?
+		};
+
+		let message = {
+			let jfield = env
+				.get_field(&decoration, "message", "Ljava/lang/String;")?
+				.l()?;
+			if jfield.is_null() {
+				return Err(jni::errors::Error::NullPtr("Message can never be null!"));
+			}
+			unsafe { env.get_string_unchecked(&jfield.into()) }?.into()
+		};
+
+		let start_idx = env
+			.get_field(&decoration, "startIdx", "J")?
+			.j()?
+			.clamp(0, u32::MAX.into()) as u32;
+
+		let end_idx = env
+			.get_field(&decoration, "endIdx", "J")?
+			.j()?
+			.clamp(0, u32::MAX.into()) as u32;
+
+		let ttl_ms = env
+			.get_field(&decoration, "ttlMs", "J")?
+			.j()?
+			.clamp(0, u32::MAX.into()) as u32;
+
+		Ok(Self {
+			kind,
+			message,
+			author: String::new(),
+			start_idx,
+			end_idx,
+			ttl_ms,
+		})
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::LineEnding {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		line_ending: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		if line_ending.is_null() {
+			return Err(jni::errors::Error::NullPtr(
+				"Line ending can never be null!",
+			));
+		}
+		match env.call_method(&line_ending, "ordinal", "()I", &[])?.i()? {
+			0 => Ok(crate::api::LineEnding::Lf),
+			1 => Ok(crate::api::LineEnding::Crlf),
+			2 => Ok(crate::api::LineEnding::Cr),
+			_ => Err(jni::errors::Error::WrongJValueType(
+				"LineEnding",
+				"unknown ordinal",
+			)),
+		}
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::Indentation {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		indentation: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		let tabs = env.get_field(&indentation, "tabs", "Z")?.z()?;
+		let width = env.get_field(&indentation, "width", "I")?.i()?.max(0) as u32;
+		Ok(Self { tabs, width })
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::BufferMetadata {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(
+		env: &mut jni::JNIEnv<'j>,
+		metadata: Self::From,
+	) -> Result<Self, jni::errors::Error> {
+		let mut optional =
+			|name: &str| -> Result<Option<jni::objects::JObject<'j>>, jni::errors::Error> {
+				let jfield = env
+					.get_field(&metadata, name, "Ljava/util/Optional;")?
+					.l()?;
+				if env.call_method(&jfield, "isPresent", "()Z", &[])?.z()? {
+					Ok(Some(
+						env.call_method(&jfield, "get", "()Ljava/lang/Object;", &[])?
+							.l()?,
+					))
+				} else {
+					Ok(None)
+				}
+			};
+
+		let language = optional("language")?;
+		let line_ending = optional("lineEnding")?;
+		let indentation = optional("indentation")?;
+		let encoding = optional("encoding")?;
+
+		Ok(Self {
+			language: match language {
+				Some(field) => Some(unsafe { env.get_string_unchecked(&field.into()) }?.into()),
+				None => None,
+			},
+			line_ending: match line_ending {
+				Some(field) => {
+					Some(<crate::api::LineEnding as jni_toolbox::FromJava>::from_java(env, field)?)
+				}
+				None => None,
+			},
+			indentation: match indentation {
+				Some(field) => {
+					Some(<crate::api::Indentation as jni_toolbox::FromJava>::from_java(env, field)?)
+				}
+				None => None,
+			},
+			encoding: match encoding {
+				Some(field) => Some(unsafe { env.get_string_unchecked(&field.into()) }?.into()),
+				None => None,
+			},
+		})
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::Theme {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(env: &mut jni::JNIEnv<'j>, theme: Self::From) -> Result<Self, jni::errors::Error> {
+		if theme.is_null() {
+			return Err(jni::errors::Error::NullPtr("Theme can never be null!"));
+		}
+		match env.call_method(&theme, "ordinal", "()I", &[])?.i()? {
+			0 => Ok(crate::api::Theme::Light),
+			1 => Ok(crate::api::Theme::Dark),
+			_ => Err(jni::errors::Error::WrongJValueType(
+				"Theme",
+				"unknown ordinal",
+			)),
+		}
+	}
+}
+
+impl<'j> jni_toolbox::FromJava<'j> for crate::api::Role {
+	type From = jni::objects::JObject<'j>;
+	fn from_java(env: &mut jni::JNIEnv<'j>, role: Self::From) -> Result<Self, jni::errors::Error> {
+		if role.is_null() {
+			return Err(jni::errors::Error::NullPtr("Role can never be null!"));
+		}
+		match env.call_method(&role, "ordinal", "()I", &[])?.i()? {
+			0 => Ok(crate::api::Role::ReadWrite),
+			1 => Ok(crate::api::Role::ReadOnly),
+			2 => Ok(crate::api::Role::Owner),
+			_ => Err(jni::errors::Error::WrongJValueType(
+				"Role",
+				"unknown ordinal",
+			)),
+		}
+	}
+}
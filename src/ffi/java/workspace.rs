@@ -1,5 +1,5 @@
 use crate::{
-	api::{controller::AsyncReceiver, User},
+	api::{controller::AsyncReceiver, BufferMetadata, Member, Presence, Role, User},
 	errors::{ConnectionError, ControllerError, RemoteError},
 	ffi::java::null_check,
 	Workspace,
@@ -58,6 +58,46 @@ fn attach_buffer(
 	super::tokio().block_on(workspace.attach_buffer(&path))
 }
 
+/// Attach to a buffer as a spectator and return a pointer to its [`crate::buffer::Controller`].
+#[jni(package = "mp.code", class = "Workspace")]
+fn attach_buffer_read_only(
+	workspace: &mut Workspace,
+	path: String,
+) -> Result<crate::buffer::Controller, ConnectionError> {
+	super::tokio().block_on(workspace.attach_buffer_read_only(&path))
+}
+
+/// Attach to the chat and return a pointer to its [`crate::chat::Controller`].
+#[jni(package = "mp.code", class = "Workspace")]
+fn attach_chat(workspace: &mut Workspace) -> Result<crate::chat::Controller, ConnectionError> {
+	super::tokio().block_on(workspace.attach_chat())
+}
+
+/// Get the chat controller, if the chat was attached.
+#[jni(package = "mp.code", class = "Workspace")]
+fn get_chat(workspace: &mut Workspace) -> Option<crate::chat::Controller> {
+	workspace.get_chat()
+}
+
+/// Attach to the decorations of a buffer and return a pointer to its
+/// [`crate::decoration::Controller`].
+#[jni(package = "mp.code", class = "Workspace")]
+fn attach_decorations(
+	workspace: &mut Workspace,
+	path: String,
+) -> Result<crate::decoration::Controller, ConnectionError> {
+	super::tokio().block_on(workspace.attach_decorations(&path))
+}
+
+/// Get the decoration controller of a buffer, if its decorations were attached.
+#[jni(package = "mp.code", class = "Workspace")]
+fn get_decorations(
+	workspace: &mut Workspace,
+	path: String,
+) -> Option<crate::decoration::Controller> {
+	workspace.get_decorations(&path)
+}
+
 /// Detach from a buffer.
 #[jni(package = "mp.code", class = "Workspace")]
 fn detach_buffer(workspace: &mut Workspace, path: String) -> bool {
@@ -76,6 +116,61 @@ fn fetch_users(workspace: &mut Workspace) -> Result<Vec<User>, RemoteError> {
 	super::tokio().block_on(workspace.fetch_users())
 }
 
+/// List the members of this workspace, including pending invites.
+#[jni(package = "mp.code", class = "Workspace")]
+fn fetch_members(workspace: &mut Workspace) -> Result<Vec<Member>, RemoteError> {
+	super::tokio().block_on(workspace.fetch_members())
+}
+
+/// Fetch the [BufferMetadata] of a buffer from the server.
+#[jni(package = "mp.code", class = "Workspace")]
+fn fetch_buffer_metadata(
+	workspace: &mut Workspace,
+	path: String,
+) -> Result<BufferMetadata, RemoteError> {
+	super::tokio().block_on(workspace.fetch_buffer_metadata(&path))
+}
+
+/// Replace the [BufferMetadata] of a buffer, announcing it to everyone attached to it.
+#[jni(package = "mp.code", class = "Workspace")]
+fn set_buffer_metadata(
+	workspace: &mut Workspace,
+	path: String,
+	metadata: BufferMetadata,
+) -> Result<(), RemoteError> {
+	super::tokio().block_on(workspace.set_buffer_metadata(&path, metadata))
+}
+
+/// Get the [Presence] of all users in this workspace, as last known.
+#[jni(package = "mp.code", class = "Workspace")]
+fn presence(workspace: &mut Workspace) -> Vec<Presence> {
+	workspace.presence()
+}
+
+/// Get the [Presence] of a user in this workspace by name, or null if they are not there.
+#[jni(package = "mp.code", class = "Workspace")]
+fn user_presence(workspace: &mut Workspace, name: String) -> Option<Presence> {
+	workspace.user_presence(&name)
+}
+
+/// Update users in this workspace and their profiles, and get their [Presence].
+#[jni(package = "mp.code", class = "Workspace")]
+fn fetch_presence(workspace: &mut Workspace) -> Result<Vec<Presence>, RemoteError> {
+	super::tokio().block_on(workspace.fetch_presence())
+}
+
+/// Get the [Role] of the current user in this workspace.
+#[jni(package = "mp.code", class = "Workspace")]
+fn role(workspace: &mut Workspace) -> Role {
+	workspace.role()
+}
+
+/// Update and get the [Role] of the current user in this workspace.
+#[jni(package = "mp.code", class = "Workspace")]
+fn fetch_role(workspace: &mut Workspace) -> Result<Role, RemoteError> {
+	super::tokio().block_on(workspace.fetch_role())
+}
+
 /// Fetch users attached to a buffer.
 #[jni(package = "mp.code", class = "Workspace")]
 fn fetch_buffer_users(
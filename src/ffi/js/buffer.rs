@@ -1,11 +1,50 @@
 use crate::api::controller::{AsyncReceiver, AsyncSender};
-use crate::api::{BufferUpdate, TextChange};
+use crate::api::{Annotation, BufferMetadata, BufferUpdate, Indentation, TextChange};
 use crate::buffer::controller::BufferController;
 use napi::threadsafe_function::{
 	ErrorStrategy::Fatal, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
 };
 use napi_derive::napi;
 
+use super::JsResult;
+
+#[napi(object, js_name = "BufferMetadata")]
+pub struct JsBufferMetadata {
+	pub language: Option<String>,
+	/// one of "lf", "crlf" or "cr"
+	pub line_ending: Option<String>,
+	pub indentation: Option<Indentation>,
+	pub encoding: Option<String>,
+}
+
+impl TryFrom<JsBufferMetadata> for BufferMetadata {
+	type Error = napi::Error<crate::errors::ErrorKind>;
+
+	fn try_from(value: JsBufferMetadata) -> Result<Self, Self::Error> {
+		Ok(Self {
+			language: value.language,
+			line_ending: value
+				.line_ending
+				.map(|l| l.parse())
+				.transpose()
+				.map_err(super::invalid_argument)?,
+			indentation: value.indentation,
+			encoding: value.encoding,
+		})
+	}
+}
+
+impl From<BufferMetadata> for JsBufferMetadata {
+	fn from(value: BufferMetadata) -> Self {
+		Self {
+			language: value.language,
+			line_ending: value.line_ending.map(|l| l.to_string()),
+			indentation: value.indentation,
+			encoding: value.encoding,
+		}
+	}
+}
+
 #[napi]
 impl BufferController {
 	/// Register a callback to be invoked every time a new event is available to consume
@@ -50,33 +89,157 @@ impl BufferController {
 		self.path()
 	}
 
+	/// Check whether changes sent to this buffer are refused, because of the current user's role
+	#[napi(js_name = "isReadOnly")]
+	pub fn js_is_read_only(&self) -> bool {
+		self.is_read_only()
+	}
+
 	/// Block until next buffer event without returning it
 	#[napi(js_name = "poll")]
-	pub async fn js_poll(&self) -> napi::Result<()> {
+	pub async fn js_poll(&self) -> JsResult<()> {
 		Ok(self.poll().await?)
 	}
 
 	/// Return next buffer event if present
 	#[napi(js_name = "tryRecv")]
-	pub async fn js_try_recv(&self) -> napi::Result<Option<BufferUpdate>> {
+	pub async fn js_try_recv(&self) -> JsResult<Option<BufferUpdate>> {
 		Ok(self.try_recv().await?)
 	}
 
 	/// Wait for next buffer event and return it
 	#[napi(js_name = "recv")]
-	pub async fn js_recv(&self) -> napi::Result<BufferUpdate> {
+	pub async fn js_recv(&self) -> JsResult<BufferUpdate> {
 		Ok(self.recv().await?)
 	}
 
 	/// Send a buffer update to workspace
 	#[napi(js_name = "send")]
-	pub fn js_send(&self, op: TextChange) -> napi::Result<()> {
+	pub fn js_send(&self, op: TextChange) -> JsResult<()> {
 		Ok(self.send(op)?)
 	}
 
 	/// Return buffer whole content
 	#[napi(js_name = "content")]
-	pub async fn js_content(&self) -> napi::Result<String> {
+	pub async fn js_content(&self) -> JsResult<String> {
 		Ok(self.content().await?)
 	}
+
+	/// Check if given hash matches the content the editor should be displaying
+	#[napi(js_name = "verify")]
+	pub async fn js_verify(&self, hash: i64) -> JsResult<bool> {
+		Ok(self.verify(hash).await?)
+	}
+
+	/// Return the change needed to bring given editor content back in sync, if any
+	#[napi(js_name = "resync")]
+	pub async fn js_resync(&self, content: String) -> JsResult<Option<TextChange>> {
+		Ok(self.resync(&content).await?)
+	}
+
+	/// Annotate a range of the buffer, returning the id of the new annotation
+	#[napi(js_name = "annotate")]
+	pub fn js_annotate(&self, start_idx: u32, end_idx: u32, text: String) -> JsResult<String> {
+		Ok(self.annotate(start_idx, end_idx, &text)?)
+	}
+
+	/// Mark an annotation as resolved
+	#[napi(js_name = "resolveAnnotation")]
+	pub fn js_resolve_annotation(&self, id: String) -> JsResult<()> {
+		Ok(self.resolve_annotation(&id)?)
+	}
+
+	/// Delete an annotation
+	#[napi(js_name = "deleteAnnotation")]
+	pub fn js_delete_annotation(&self, id: String) -> JsResult<()> {
+		Ok(self.delete_annotation(&id)?)
+	}
+
+	/// Return all annotations of this buffer, with their ranges as the editor should see them
+	#[napi(js_name = "annotations")]
+	pub async fn js_annotations(&self) -> JsResult<Vec<Annotation>> {
+		Ok(self.annotations().await?)
+	}
+
+	/// Register a callback to be invoked every time an annotation changes
+	/// There can only be one annotation callback registered at any given time.
+	#[napi(
+		js_name = "annotationCallback",
+		ts_args_type = "fun: (event: BufferController) => void"
+	)]
+	pub fn js_annotation_callback(&self, fun: napi::JsFunction) -> napi::Result<()> {
+		let tsfn: ThreadsafeFunction<BufferController, Fatal> = fun
+			.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<BufferController>| {
+				Ok(vec![ctx.value])
+			})?;
+		self.annotation_callback(move |controller: BufferController| {
+			tsfn.call(controller.clone(), ThreadsafeFunctionCallMode::Blocking);
+		});
+		Ok(())
+	}
+
+	/// Remove registered annotation callback
+	#[napi(js_name = "clearAnnotationCallback")]
+	pub fn js_clear_annotation_callback(&self) {
+		self.clear_annotation_callback();
+	}
+
+	/// Return the latest metadata announced for this buffer
+	#[napi(js_name = "metadata")]
+	pub fn js_metadata(&self) -> JsBufferMetadata {
+		self.metadata().into()
+	}
+
+	/// Register a callback to be invoked every time new metadata is announced for this buffer
+	/// There can only be one metadata callback registered at any given time.
+	#[napi(
+		js_name = "metadataCallback",
+		ts_args_type = "fun: (event: BufferController) => void"
+	)]
+	pub fn js_metadata_callback(&self, fun: napi::JsFunction) -> napi::Result<()> {
+		let tsfn: ThreadsafeFunction<BufferController, Fatal> = fun
+			.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<BufferController>| {
+				Ok(vec![ctx.value])
+			})?;
+		self.metadata_callback(move |controller: BufferController| {
+			tsfn.call(controller.clone(), ThreadsafeFunctionCallMode::Blocking);
+		});
+		Ok(())
+	}
+
+	/// Remove registered metadata callback
+	#[napi(js_name = "clearMetadataCallback")]
+	pub fn js_clear_metadata_callback(&self) {
+		self.clear_metadata_callback();
+	}
+
+	/// Toggle replacing line endings of sent changes with the ones announced for this buffer
+	#[napi(js_name = "normalizeLineEndings")]
+	pub fn js_normalize_line_endings(&self, enabled: bool) {
+		self.normalize_line_endings(enabled);
+	}
+
+	/// Whether line endings of sent changes are being normalized
+	#[napi(js_name = "isNormalizingLineEndings")]
+	pub fn js_is_normalizing_line_endings(&self) -> bool {
+		self.is_normalizing_line_endings()
+	}
+
+	/// Translate line endings between the buffer and an editor using given one
+	/// (one of "lf", "crlf" or "cr"), or stop translating them if missing
+	#[napi(js_name = "translateLineEndings")]
+	pub fn js_translate_line_endings(&self, local: Option<String>) -> JsResult<()> {
+		let local = local
+			.map(|l| l.parse())
+			.transpose()
+			.map_err(super::invalid_argument)?;
+		self.translate_line_endings(local);
+		Ok(())
+	}
+
+	/// Line ending of the editor, if line endings are being translated
+	#[napi(js_name = "localLineEnding")]
+	pub fn js_local_line_ending(&self) -> Option<String> {
+		self.local_line_ending().map(|l| l.to_string())
+	}
 }
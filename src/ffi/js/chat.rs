@@ -0,0 +1,106 @@
+use crate::api::controller::{AsyncReceiver, AsyncSender};
+use crate::chat::controller::ChatController;
+use napi::threadsafe_function::ErrorStrategy::Fatal;
+use napi::threadsafe_function::{
+	ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi_derive::napi;
+
+use super::JsResult;
+
+#[napi(object, js_name = "Draft")]
+pub struct JsDraft {
+	pub text: String,
+	/// buffer range this message is about, if it belongs to a thread
+	pub thread: Option<crate::api::Selection>,
+}
+
+impl From<JsDraft> for crate::api::Draft {
+	fn from(value: JsDraft) -> Self {
+		Self {
+			text: value.text,
+			thread: value.thread,
+		}
+	}
+}
+
+#[napi(object, js_name = "Message")]
+pub struct JsMessage {
+	pub user: String,
+	pub text: String,
+	/// milliseconds since the UNIX epoch, as received by the server
+	pub timestamp: f64,
+	/// buffer range this message is about, if it belongs to a thread
+	pub thread: Option<crate::api::Selection>,
+}
+
+impl From<crate::api::Message> for JsMessage {
+	fn from(value: crate::api::Message) -> Self {
+		Self {
+			user: value.user,
+			text: value.text,
+			timestamp: value.timestamp as f64,
+			thread: value.thread,
+		}
+	}
+}
+
+#[napi]
+impl ChatController {
+	/// Register a callback to be called on receive.
+	/// There can only be one callback registered at any given time.
+	#[napi(
+		js_name = "callback",
+		ts_args_type = "fun: (event: ChatController) => void"
+	)]
+	pub fn js_callback(&self, fun: napi::JsFunction) -> napi::Result<()> {
+		let tsfn: ThreadsafeFunction<ChatController, Fatal> = fun
+			.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<ChatController>| {
+				Ok(vec![ctx.value])
+			})?;
+		self.callback(move |controller: ChatController| {
+			tsfn.call(controller.clone(), ThreadsafeFunctionCallMode::Blocking);
+		});
+
+		Ok(())
+	}
+
+	/// Clear the registered callback
+	#[napi(js_name = "clearCallback")]
+	pub fn js_clear_callback(&self) {
+		self.clear_callback();
+	}
+
+	/// Send a new chat message to everyone in the workspace
+	#[napi(js_name = "send")]
+	pub fn js_send(&self, draft: JsDraft) -> JsResult<()> {
+		Ok(self.send(draft.into())?)
+	}
+
+	/// Get last messages received, oldest first
+	#[napi(js_name = "history")]
+	pub fn js_history(&self) -> Vec<JsMessage> {
+		self.history().into_iter().map(JsMessage::from).collect()
+	}
+
+	/// Get last messages received in threads about given buffer, oldest first
+	#[napi(js_name = "threadHistory")]
+	pub fn js_thread_history(&self, buffer: String) -> Vec<JsMessage> {
+		self.thread_history(&buffer)
+			.into_iter()
+			.map(JsMessage::from)
+			.collect()
+	}
+
+	/// Get next chat message if available without blocking
+	#[napi(js_name = "tryRecv")]
+	pub async fn js_try_recv(&self) -> JsResult<Option<JsMessage>> {
+		Ok(self.try_recv().await?.map(JsMessage::from))
+	}
+
+	/// Block until next chat message
+	#[napi(js_name = "recv")]
+	pub async fn js_recv(&self) -> JsResult<JsMessage> {
+		Ok(self.recv().await?.into())
+	}
+}
@@ -1,6 +1,8 @@
 use crate::{Client, Workspace};
 use napi_derive::napi;
 
+use super::JsResult;
+
 #[napi(object, js_name = "User")]
 pub struct JsUser {
 	pub uuid: String,
@@ -26,51 +28,121 @@ impl From<crate::api::User> for JsUser {
 	}
 }
 
+#[napi(object, js_name = "Member")]
+pub struct JsMember {
+	pub user: JsUser,
+	/// one of "read_write", "read_only" or "owner"
+	pub role: String,
+	pub pending: bool,
+}
+
+impl From<crate::api::Member> for JsMember {
+	fn from(value: crate::api::Member) -> Self {
+		Self {
+			user: value.user.into(),
+			role: value.role.to_string(),
+			pending: value.pending,
+		}
+	}
+}
+
 #[napi]
 /// connect to codemp servers and return a client session
-pub async fn connect(config: crate::api::Config) -> napi::Result<crate::Client> {
+pub async fn connect(config: crate::api::Config) -> JsResult<crate::Client> {
 	Ok(crate::Client::connect(config).await?)
 }
 
+#[napi(js_name = "loadConfig")]
+/// load configuration from given file (or the default one) and the environment, with given
+/// (possibly partial) config values taking precedence
+pub fn js_load_config(
+	path: Option<String>,
+	explicit: Option<serde_json::Value>,
+) -> JsResult<crate::api::Config> {
+	let explicit = match explicit {
+		Some(values) => serde_json::from_value(values).map_err(super::invalid_argument)?,
+		None => crate::api::Config::default(),
+	};
+	Ok(match path {
+		Some(path) => crate::loader::load_file(path, explicit)?,
+		None => crate::loader::load(explicit)?,
+	})
+}
+
 #[napi]
 impl Client {
 	#[napi(js_name = "createWorkspace")]
 	/// create workspace with given id, if able to
-	pub async fn js_create_workspace(&self, workspace: String) -> napi::Result<()> {
+	pub async fn js_create_workspace(&self, workspace: String) -> JsResult<()> {
 		Ok(self.create_workspace(workspace).await?)
 	}
 
 	#[napi(js_name = "deleteWorkspace")]
 	/// delete workspace with given id, if able to
-	pub async fn js_delete_workspace(&self, workspace: String) -> napi::Result<()> {
+	pub async fn js_delete_workspace(&self, workspace: String) -> JsResult<()> {
 		Ok(self.delete_workspace(workspace).await?)
 	}
 
 	#[napi(js_name = "fetchOwnedWorkspaces")]
 	/// fetch owned workspaces
-	pub async fn js_fetch_owned_workspaces(&self) -> napi::Result<Vec<String>> {
+	pub async fn js_fetch_owned_workspaces(&self) -> JsResult<Vec<String>> {
 		Ok(self.fetch_owned_workspaces().await?)
 	}
 
 	#[napi(js_name = "fetchJoinedWorkspaces")]
 	/// fetch joined workspaces
-	pub async fn js_fetch_joined_workspaces(&self) -> napi::Result<Vec<String>> {
+	pub async fn js_fetch_joined_workspaces(&self) -> JsResult<Vec<String>> {
 		Ok(self.fetch_joined_workspaces().await?)
 	}
 
 	#[napi(js_name = "inviteToWorkspace")]
 	/// invite user to given workspace, if able to
-	pub async fn js_invite_to_workspace(
+	pub async fn js_invite_to_workspace(&self, workspace: String, user: String) -> JsResult<()> {
+		Ok(self.invite_to_workspace(workspace, user).await?)
+	}
+
+	#[napi(js_name = "fetchWorkspaceMembers")]
+	/// fetch members of given workspace with their roles, including pending invites
+	pub async fn js_fetch_workspace_members(&self, workspace: String) -> JsResult<Vec<JsMember>> {
+		Ok(self
+			.fetch_workspace_members(workspace)
+			.await?
+			.into_iter()
+			.map(JsMember::from)
+			.collect())
+	}
+
+	#[napi(js_name = "removeFromWorkspace")]
+	/// revoke access of user to given workspace (or their pending invite), if able to
+	pub async fn js_remove_from_workspace(&self, workspace: String, user: String) -> JsResult<()> {
+		Ok(self.remove_from_workspace(workspace, user).await?)
+	}
+
+	#[napi(js_name = "setWorkspaceRole")]
+	/// change role of user in given workspace ("read_write", "read_only" or "owner"), if able to
+	pub async fn js_set_workspace_role(
 		&self,
 		workspace: String,
 		user: String,
-	) -> napi::Result<()> {
-		Ok(self.invite_to_workspace(workspace, user).await?)
+		role: String,
+	) -> JsResult<()> {
+		let role = role.parse().map_err(super::invalid_argument)?;
+		Ok(self.set_workspace_role(workspace, user, role).await?)
+	}
+
+	#[napi(js_name = "setProfile")]
+	/// change display name and avatar url shown to other users, clearing those not given
+	pub async fn js_set_profile(
+		&self,
+		display_name: Option<String>,
+		avatar: Option<String>,
+	) -> JsResult<()> {
+		Ok(self.set_profile(display_name, avatar).await?)
 	}
 
 	#[napi(js_name = "attachWorkspace")]
 	/// join workspace with given id (will start its cursor controller)
-	pub async fn js_attach_workspace(&self, workspace: String) -> napi::Result<Workspace> {
+	pub async fn js_attach_workspace(&self, workspace: String) -> JsResult<Workspace> {
 		Ok(self.attach_workspace(workspace).await?)
 	}
 
@@ -100,7 +172,7 @@ impl Client {
 
 	#[napi(js_name = "refresh")]
 	/// refresh client session token
-	pub async fn js_refresh(&self) -> napi::Result<()> {
+	pub async fn js_refresh(&self) -> JsResult<()> {
 		Ok(self.refresh().await?)
 	}
 }
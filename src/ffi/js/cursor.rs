@@ -6,6 +6,8 @@ use napi::threadsafe_function::{
 };
 use napi_derive::napi;
 
+use super::JsResult;
+
 #[napi]
 impl CursorController {
 	/// Register a callback to be called on receive.
@@ -39,19 +41,19 @@ impl CursorController {
 
 	/// Send a new cursor event to remote
 	#[napi(js_name = "send")]
-	pub fn js_send(&self, sel: crate::api::Selection) -> napi::Result<()> {
+	pub fn js_send(&self, sel: crate::api::Selection) -> JsResult<()> {
 		Ok(self.send(sel)?)
 	}
 
 	/// Get next cursor event if available without blocking
 	#[napi(js_name = "tryRecv")]
-	pub async fn js_try_recv(&self) -> napi::Result<Option<crate::api::Cursor>> {
+	pub async fn js_try_recv(&self) -> JsResult<Option<crate::api::Cursor>> {
 		Ok(self.try_recv().await?.map(crate::api::Cursor::from))
 	}
 
 	/// Block until next
 	#[napi(js_name = "recv")]
-	pub async fn js_recv(&self) -> napi::Result<crate::api::Cursor> {
+	pub async fn js_recv(&self) -> JsResult<crate::api::Cursor> {
 		Ok(self.recv().await?)
 	}
 }
@@ -0,0 +1,112 @@
+use crate::api::controller::{AsyncReceiver, AsyncSender};
+use crate::decoration::controller::DecorationController;
+use napi::threadsafe_function::ErrorStrategy::Fatal;
+use napi::threadsafe_function::{
+	ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi_derive::napi;
+
+use super::JsResult;
+
+#[napi(object, js_name = "Decoration")]
+pub struct JsDecoration {
+	/// one of "highlight", "mark", "error", "warning", "info" or "hint"
+	pub kind: String,
+	pub message: String,
+	/// ignored when sending
+	pub author: String,
+	pub start_idx: u32,
+	pub end_idx: u32,
+	/// how long the decoration should be shown once received, in milliseconds
+	pub ttl_ms: u32,
+}
+
+impl TryFrom<JsDecoration> for crate::api::Decoration {
+	type Error = napi::Error<crate::errors::ErrorKind>;
+
+	fn try_from(value: JsDecoration) -> Result<Self, Self::Error> {
+		Ok(Self {
+			kind: value.kind.parse().map_err(super::invalid_argument)?,
+			message: value.message,
+			author: value.author,
+			start_idx: value.start_idx,
+			end_idx: value.end_idx,
+			ttl_ms: value.ttl_ms,
+		})
+	}
+}
+
+impl From<crate::api::Decoration> for JsDecoration {
+	fn from(value: crate::api::Decoration) -> Self {
+		Self {
+			kind: value.kind.to_string(),
+			message: value.message,
+			author: value.author,
+			start_idx: value.start_idx,
+			end_idx: value.end_idx,
+			ttl_ms: value.ttl_ms,
+		}
+	}
+}
+
+#[napi]
+impl DecorationController {
+	/// Register a callback to be called on receive.
+	/// There can only be one callback registered at any given time.
+	#[napi(
+		js_name = "callback",
+		ts_args_type = "fun: (event: DecorationController) => void"
+	)]
+	pub fn js_callback(&self, fun: napi::JsFunction) -> napi::Result<()> {
+		let tsfn: ThreadsafeFunction<DecorationController, Fatal> = fun
+			.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<DecorationController>| {
+				Ok(vec![ctx.value])
+			})?;
+		self.callback(move |controller: DecorationController| {
+			tsfn.call(controller.clone(), ThreadsafeFunctionCallMode::Blocking);
+		});
+
+		Ok(())
+	}
+
+	/// Clear the registered callback
+	#[napi(js_name = "clearCallback")]
+	pub fn js_clear_callback(&self) {
+		self.clear_callback();
+	}
+
+	/// Get the path of the buffer decorated
+	#[napi(js_name = "path")]
+	pub fn js_path(&self) -> String {
+		self.path().to_string()
+	}
+
+	/// Send a decoration to everyone attached to the buffer
+	#[napi(js_name = "send")]
+	pub fn js_send(&self, decoration: JsDecoration) -> JsResult<()> {
+		Ok(self.send(decoration.try_into()?)?)
+	}
+
+	/// Get decorations received which didn't expire yet, oldest first
+	#[napi(js_name = "active")]
+	pub async fn js_active(&self) -> JsResult<Vec<JsDecoration>> {
+		Ok(self
+			.active()
+			.await?
+			.into_iter()
+			.map(JsDecoration::from)
+			.collect())
+	}
+
+	/// Get next decoration if available without blocking
+	#[napi(js_name = "tryRecv")]
+	pub async fn js_try_recv(&self) -> JsResult<Option<JsDecoration>> {
+		Ok(self.try_recv().await?.map(JsDecoration::from))
+	}
+
+	/// Block until next decoration
+	#[napi(js_name = "recv")]
+	pub async fn js_recv(&self) -> JsResult<JsDecoration> {
+		Ok(self.recv().await?.into())
+	}
+}
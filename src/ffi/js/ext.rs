@@ -1,5 +1,7 @@
 use napi_derive::napi;
 
+use super::JsResult;
+
 /// Hash function
 #[napi(js_name = "hash")]
 pub fn js_hash(data: String) -> i64 {
@@ -11,3 +13,14 @@ pub fn js_hash(data: String) -> i64 {
 pub fn js_version() -> &'static str {
 	crate::version()
 }
+
+/// Get the color assigned to a user as "#rrggbb", optionally for a "light" or "dark" background
+#[napi(js_name = "userColor")]
+pub fn js_user_color(uuid: String, theme: Option<String>) -> JsResult<&'static str> {
+	let id = uuid.parse().map_err(super::invalid_argument)?;
+	let theme = theme
+		.map(|t| t.parse())
+		.transpose()
+		.map_err(super::invalid_argument)?;
+	Ok(crate::api::color::user_color(id, theme))
+}
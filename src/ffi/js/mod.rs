@@ -1,27 +1,40 @@
 pub mod buffer;
+pub mod chat;
 pub mod client;
 pub mod cursor;
+pub mod decoration;
 pub mod ext;
 pub mod workspace;
 
-impl From<crate::errors::ConnectionError> for napi::Error {
+/// Result of bindings which may fail with errors from codemp: they are thrown with their
+/// [`crate::errors::ErrorKind`] name (like "not_found") as `code`.
+pub type JsResult<T> = napi::Result<T, crate::errors::ErrorKind>;
+
+impl From<crate::errors::ConnectionError> for napi::Error<crate::errors::ErrorKind> {
 	fn from(value: crate::errors::ConnectionError) -> Self {
-		napi::Error::new(napi::Status::GenericFailure, format!("{value}"))
+		napi::Error::new(value.kind(), value.to_string())
 	}
 }
 
-impl From<crate::errors::RemoteError> for napi::Error {
+impl From<crate::errors::RemoteError> for napi::Error<crate::errors::ErrorKind> {
 	fn from(value: crate::errors::RemoteError) -> Self {
-		napi::Error::new(napi::Status::GenericFailure, format!("{value}"))
+		napi::Error::new(value.kind(), value.to_string())
 	}
 }
 
-impl From<crate::errors::ControllerError> for napi::Error {
+impl From<crate::errors::ControllerError> for napi::Error<crate::errors::ErrorKind> {
 	fn from(value: crate::errors::ControllerError) -> Self {
-		napi::Error::new(napi::Status::GenericFailure, format!("{value}"))
+		napi::Error::new(value.kind(), value.to_string())
 	}
 }
 
+/// Error for invalid arguments passed to bindings returning a [`JsResult`].
+pub(crate) fn invalid_argument(
+	error: impl std::fmt::Display,
+) -> napi::Error<crate::errors::ErrorKind> {
+	napi::Error::new(crate::errors::ErrorKind::InvalidArgument, error.to_string())
+}
+
 use napi_derive::napi;
 
 #[napi]
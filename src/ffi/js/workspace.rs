@@ -1,6 +1,8 @@
 use crate::api::controller::AsyncReceiver;
 use crate::buffer::controller::BufferController;
+use crate::chat::controller::ChatController;
 use crate::cursor::controller::CursorController;
+use crate::decoration::controller::DecorationController;
 use crate::Workspace;
 use napi::threadsafe_function::ErrorStrategy::Fatal;
 use napi::threadsafe_function::{
@@ -8,7 +10,9 @@ use napi::threadsafe_function::{
 };
 use napi_derive::napi;
 
-use super::client::JsUser;
+use super::buffer::JsBufferMetadata;
+use super::client::{JsMember, JsUser};
+use super::JsResult;
 
 #[napi(object, js_name = "Event")]
 pub struct JsEvent {
@@ -31,6 +35,35 @@ impl From<crate::api::Event> for JsEvent {
 				r#type: "leave".into(),
 				value,
 			},
+			crate::api::Event::PresenceChanged { name: value } => Self {
+				r#type: "presence".into(),
+				value,
+			},
+		}
+	}
+}
+
+#[napi(object, js_name = "Presence")]
+pub struct JsPresence {
+	pub user: JsUser,
+	pub display_name: Option<String>,
+	pub avatar: Option<String>,
+	/// as "#rrggbb"
+	pub color: String,
+	/// one of "active", "idle" or "away"
+	pub status: String,
+	pub buffer: Option<String>,
+}
+
+impl From<crate::api::Presence> for JsPresence {
+	fn from(value: crate::api::Presence) -> Self {
+		Self {
+			user: value.user.into(),
+			display_name: value.display_name,
+			avatar: value.avatar,
+			color: value.color,
+			status: value.status.to_string(),
+			buffer: value.buffer,
 		}
 	}
 }
@@ -61,6 +94,24 @@ impl Workspace {
 		self.active_buffers()
 	}
 
+	/// Get the role of the current user in this workspace ("read_write", "read_only" or "owner")
+	#[napi(js_name = "role")]
+	pub fn js_role(&self) -> String {
+		self.role().to_string()
+	}
+
+	/// Get the presence of all users in this workspace, as last known
+	#[napi(js_name = "presence")]
+	pub fn js_presence(&self) -> Vec<JsPresence> {
+		self.presence().into_iter().map(JsPresence::from).collect()
+	}
+
+	/// Get the presence of the user with given name, if they are in this workspace
+	#[napi(js_name = "userPresence")]
+	pub fn js_user_presence(&self, name: String) -> Option<JsPresence> {
+		self.user_presence(&name).map(JsPresence::from)
+	}
+
 	/// Get workspace's Cursor Controller
 	#[napi(js_name = "cursor")]
 	pub fn js_cursor(&self) -> CursorController {
@@ -73,36 +124,70 @@ impl Workspace {
 		self.get_buffer(&path)
 	}
 
+	/// Get the chat controller, if the chat was attached
+	#[napi(js_name = "getChat")]
+	pub fn js_get_chat(&self) -> Option<ChatController> {
+		self.get_chat()
+	}
+
+	/// Attach to the workspace chat, starting a ChatController, or get it if already attached
+	#[napi(js_name = "attachChat")]
+	pub async fn js_attach_chat(&self) -> JsResult<ChatController> {
+		Ok(self.attach_chat().await?)
+	}
+
+	/// Get the decoration controller of a buffer, if its decorations were attached
+	#[napi(js_name = "getDecorations")]
+	pub fn js_get_decorations(&self, path: String) -> Option<DecorationController> {
+		self.get_decorations(&path)
+	}
+
+	/// Attach to the decorations of a buffer, starting a DecorationController, or get it if
+	/// already attached
+	#[napi(js_name = "attachDecorations")]
+	pub async fn js_attach_decorations(&self, path: String) -> JsResult<DecorationController> {
+		Ok(self.attach_decorations(&path).await?)
+	}
+
 	/// Create a new buffer in the current workspace
 	#[napi(js_name = "createBuffer")]
-	pub async fn js_create_buffer(&self, path: String) -> napi::Result<()> {
+	pub async fn js_create_buffer(&self, path: String) -> JsResult<()> {
 		Ok(self.create_buffer(&path).await?)
 	}
 
 	/// Attach to a workspace buffer, starting a BufferController
 	#[napi(js_name = "attachBuffer")]
-	pub async fn js_attach_buffer(&self, path: String) -> napi::Result<BufferController> {
+	pub async fn js_attach_buffer(&self, path: String) -> JsResult<BufferController> {
 		Ok(self.attach_buffer(&path).await?)
 	}
 
+	/// Attach to a workspace buffer as a spectator, starting a BufferController which refuses changes
+	#[napi(js_name = "attachBufferReadOnly")]
+	pub async fn js_attach_buffer_read_only(
+		&self,
+		path: String,
+	) -> JsResult<BufferController> {
+		Ok(self.attach_buffer_read_only(&path).await?)
+	}
+
 	/// Delete a buffer from workspace
 	#[napi(js_name = "deleteBuffer")]
-	pub async fn js_delete_buffer(&self, path: String) -> napi::Result<()> {
+	pub async fn js_delete_buffer(&self, path: String) -> JsResult<()> {
 		Ok(self.delete_buffer(&path).await?)
 	}
 
 	#[napi(js_name = "recv")]
-	pub async fn js_recv(&self) -> napi::Result<JsEvent> {
+	pub async fn js_recv(&self) -> JsResult<JsEvent> {
 		Ok(JsEvent::from(self.recv().await?))
 	}
 
 	#[napi(js_name = "tryRecv")]
-	pub async fn js_try_recv(&self) -> napi::Result<Option<JsEvent>> {
+	pub async fn js_try_recv(&self) -> JsResult<Option<JsEvent>> {
 		Ok(self.try_recv().await?.map(JsEvent::from))
 	}
 
 	#[napi(js_name = "poll")]
-	pub async fn js_poll(&self) -> napi::Result<()> {
+	pub async fn js_poll(&self) -> JsResult<()> {
 		self.poll().await?;
 		Ok(())
 	}
@@ -137,12 +222,12 @@ impl Workspace {
 
 	/// Re-fetch remote buffer list
 	#[napi(js_name = "fetchBuffers")]
-	pub async fn js_fetch_buffers(&self) -> napi::Result<Vec<String>> {
+	pub async fn js_fetch_buffers(&self) -> JsResult<Vec<String>> {
 		Ok(self.fetch_buffers().await?)
 	}
 	/// Re-fetch the list of all users in the workspace.
 	#[napi(js_name = "fetchUsers")]
-	pub async fn js_fetch_users(&self) -> napi::Result<Vec<JsUser>> {
+	pub async fn js_fetch_users(&self) -> JsResult<Vec<JsUser>> {
 		Ok(self
 			.fetch_users()
 			.await?
@@ -156,7 +241,7 @@ impl Workspace {
 	pub async fn js_fetch_buffer_users(
 		&self,
 		path: String,
-	) -> napi::Result<Vec<crate::ffi::js::client::JsUser>> {
+	) -> JsResult<Vec<crate::ffi::js::client::JsUser>> {
 		Ok(self
 			.fetch_buffer_users(&path)
 			.await?
@@ -164,4 +249,51 @@ impl Workspace {
 			.map(super::client::JsUser::from)
 			.collect())
 	}
+
+	/// Fetch members of this workspace with their roles, including pending invites
+	#[napi(js_name = "fetchMembers")]
+	pub async fn js_fetch_members(&self) -> JsResult<Vec<JsMember>> {
+		Ok(self
+			.fetch_members()
+			.await?
+			.into_iter()
+			.map(JsMember::from)
+			.collect())
+	}
+
+	/// Fetch metadata of a buffer from the server
+	#[napi(js_name = "fetchBufferMetadata")]
+	pub async fn js_fetch_buffer_metadata(&self, path: String) -> JsResult<JsBufferMetadata> {
+		Ok(self.fetch_buffer_metadata(&path).await?.into())
+	}
+
+	/// Replace metadata of a buffer, announcing it to everyone attached to it
+	/// Unset fields are cleared.
+	#[napi(js_name = "setBufferMetadata")]
+	pub async fn js_set_buffer_metadata(
+		&self,
+		path: String,
+		metadata: JsBufferMetadata,
+	) -> JsResult<()> {
+		Ok(self
+			.set_buffer_metadata(&path, metadata.try_into()?)
+			.await?)
+	}
+
+	/// Re-fetch users in this workspace and their profiles, returning their presence
+	#[napi(js_name = "fetchPresence")]
+	pub async fn js_fetch_presence(&self) -> JsResult<Vec<JsPresence>> {
+		Ok(self
+			.fetch_presence()
+			.await?
+			.into_iter()
+			.map(JsPresence::from)
+			.collect())
+	}
+
+	/// Re-fetch the role of the current user in this workspace
+	#[napi(js_name = "fetchRole")]
+	pub async fn js_fetch_role(&self) -> JsResult<String> {
+		Ok(self.fetch_role().await?.to_string())
+	}
 }
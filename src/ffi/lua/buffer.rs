@@ -4,7 +4,10 @@ use mlua_codemp_patch as mlua;
 
 use super::ext::a_sync::a_sync;
 
-super::ext::impl_lua_serde! { CodempTextChange CodempBufferUpdate }
+super::ext::impl_lua_serde! {
+	CodempTextChange CodempBufferUpdate CodempAnnotation
+	CodempBufferMetadata CodempLineEnding CodempIndentation
+}
 
 impl LuaUserData for CodempBufferController {
 	fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
@@ -12,6 +15,8 @@ impl LuaUserData for CodempBufferController {
 			Ok(format!("{:?}", this))
 		});
 
+		methods.add_method("is_read_only", |_, this, ()| Ok(this.is_read_only()));
+
 		methods.add_method("send", |_, this, (change,): (CodempTextChange,)| {
 			Ok(this.send(change)?)
 		});
@@ -30,6 +35,14 @@ impl LuaUserData for CodempBufferController {
 			"content",
 			|_, this, ()| a_sync! { this => this.content().await? },
 		);
+		methods.add_method(
+			"verify",
+			|_, this, (hash,): (i64,)| a_sync! { this => this.verify(hash).await? },
+		);
+		methods.add_method(
+			"resync",
+			|_, this, (content,): (String,)| a_sync! { this => this.resync(&content).await? },
+		);
 
 		methods.add_method("clear_callback", |_, this, ()| Ok(this.clear_callback()));
 		methods.add_method("callback", |_, this, (cb,): (LuaFunction,)| {
@@ -37,5 +50,58 @@ impl LuaUserData for CodempBufferController {
 				super::ext::callback().invoke(cb.clone(), controller)
 			}))
 		});
+
+		methods.add_method(
+			"annotate",
+			|_, this, (start_idx, end_idx, text): (u32, u32, String)| {
+				Ok(this.annotate(start_idx, end_idx, &text)?)
+			},
+		);
+		methods.add_method("resolve_annotation", |_, this, (id,): (String,)| {
+			Ok(this.resolve_annotation(&id)?)
+		});
+		methods.add_method("delete_annotation", |_, this, (id,): (String,)| {
+			Ok(this.delete_annotation(&id)?)
+		});
+		methods.add_method(
+			"annotations",
+			|_, this, ()| a_sync! { this => this.annotations().await? },
+		);
+
+		methods.add_method("clear_annotation_callback", |_, this, ()| {
+			Ok(this.clear_annotation_callback())
+		});
+		methods.add_method("annotation_callback", |_, this, (cb,): (LuaFunction,)| {
+			Ok(
+				this.annotation_callback(move |controller: CodempBufferController| {
+					super::ext::callback().invoke(cb.clone(), controller)
+				}),
+			)
+		});
+
+		methods.add_method("metadata", |_, this, ()| Ok(this.metadata()));
+		methods.add_method("clear_metadata_callback", |_, this, ()| {
+			Ok(this.clear_metadata_callback())
+		});
+		methods.add_method("metadata_callback", |_, this, (cb,): (LuaFunction,)| {
+			Ok(
+				this.metadata_callback(move |controller: CodempBufferController| {
+					super::ext::callback().invoke(cb.clone(), controller)
+				}),
+			)
+		});
+		methods.add_method("normalize_line_endings", |_, this, (enabled,): (bool,)| {
+			Ok(this.normalize_line_endings(enabled))
+		});
+		methods.add_method("is_normalizing_line_endings", |_, this, ()| {
+			Ok(this.is_normalizing_line_endings())
+		});
+		methods.add_method(
+			"translate_line_endings",
+			|_, this, (local,): (Option<CodempLineEnding>,)| Ok(this.translate_line_endings(local)),
+		);
+		methods.add_method("local_line_ending", |_, this, ()| {
+			Ok(this.local_line_ending())
+		});
 	}
 }
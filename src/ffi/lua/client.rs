@@ -4,7 +4,7 @@ use mlua_codemp_patch as mlua;
 
 use super::ext::a_sync::a_sync;
 
-super::ext::impl_lua_serde! { CodempConfig CodempUser }
+super::ext::impl_lua_serde! { CodempConfig CodempUser CodempMember CodempRole }
 
 impl LuaUserData for CodempClient {
 	fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
@@ -43,6 +43,32 @@ impl LuaUserData for CodempClient {
 			a_sync! { this => this.invite_to_workspace(ws, user).await? }
 		);
 
+		methods.add_method(
+			"fetch_workspace_members",
+			|_, this, (ws,): (String,)| a_sync! { this => this.fetch_workspace_members(ws).await? },
+		);
+
+		methods.add_method(
+			"remove_from_workspace",
+			|_, this, (ws, user): (String, String)| {
+				a_sync! { this => this.remove_from_workspace(ws, user).await? }
+			},
+		);
+
+		methods.add_method(
+			"set_workspace_role",
+			|_, this, (ws, user, role): (String, String, CodempRole)| {
+				a_sync! { this => this.set_workspace_role(ws, user, role).await? }
+			},
+		);
+
+		methods.add_method(
+			"set_profile",
+			|_, this, (display_name, avatar): (Option<String>, Option<String>)| {
+				a_sync! { this => this.set_profile(display_name, avatar).await? }
+			},
+		);
+
 		methods.add_method(
 			"fetch_owned_workspaces",
 			|_, this, ()| a_sync! { this => this.fetch_owned_workspaces().await? },
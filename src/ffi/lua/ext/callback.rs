@@ -31,11 +31,9 @@ impl CallbackChannel<LuaCallback> {
 			.unwrap_or_warn("error scheduling callback")
 	}
 
-	pub(crate) fn failure(&self, err: impl std::error::Error) {
+	pub(crate) fn failure(&self, err: LuaError) {
 		self.tx
-			.send(LuaCallback::Fail(format!(
-				"promise failed with error: {err:?}"
-			)))
+			.send(LuaCallback::Fail(err))
 			.unwrap_or_warn("error scheduling callback failure")
 	}
 
@@ -58,7 +56,7 @@ impl CallbackChannel<LuaCallback> {
 }
 
 pub(crate) enum LuaCallback {
-	Fail(String),
+	Fail(LuaError),
 	Invoke(LuaFunction, CallbackArg),
 }
 
@@ -99,12 +97,18 @@ macro_rules! callback_args {
 }
 
 callback_args! {
+	Bool: bool,
 	Str: String,
 	VecStr: Vec<String>,
 	VecUser: Vec<CodempUser>,
+	VecMember: Vec<CodempMember>,
+	Role: CodempRole,
+	VecPresence: Vec<CodempPresence>,
 	Client: CodempClient,
 	CursorController: CodempCursorController,
 	BufferController: CodempBufferController,
+	ChatController: CodempChatController,
+	DecorationController: CodempDecorationController,
 	Workspace: CodempWorkspace,
 	Event: CodempEvent,
 	MaybeEvent: Option<CodempEvent>,
@@ -116,4 +120,12 @@ callback_args! {
 	MaybeTextChange: Option<CodempTextChange>,
 	BufferUpdate: CodempBufferUpdate,
 	MaybeBufferUpdate: Option<CodempBufferUpdate>,
+	VecAnnotation: Vec<CodempAnnotation>,
+	BufferMetadata: CodempBufferMetadata,
+	Message: CodempMessage,
+	MaybeMessage: Option<CodempMessage>,
+	VecMessage: Vec<CodempMessage>,
+	Decoration: CodempDecoration,
+	MaybeDecoration: Option<CodempDecoration>,
+	VecDecoration: Vec<CodempDecoration>,
 }
@@ -1,6 +1,8 @@
 mod buffer;
+mod chat;
 mod client;
 mod cursor;
+mod decoration;
 mod ext;
 mod workspace;
 
@@ -8,6 +10,8 @@ use crate::prelude::*;
 use mlua::prelude::*;
 use mlua_codemp_patch as mlua;
 
+ext::impl_lua_serde! { CodempTheme }
+
 // define multiple entrypoints, so this library can have multiple names and still work
 #[mlua::lua_module(name = "codemp")]
 fn entry_1(lua: &Lua) -> LuaResult<LuaTable> {
@@ -37,6 +41,19 @@ fn entrypoint(lua: &Lua) -> LuaResult<LuaTable> {
 		)?,
 	)?;
 
+	exports.set(
+		"load_config",
+		lua.create_function(
+			|_, (path, explicit): (Option<String>, Option<CodempConfig>)| {
+				let explicit = explicit.unwrap_or_default();
+				Ok(match path {
+					Some(path) => crate::loader::load_file(path, explicit)?,
+					None => crate::loader::load(explicit)?,
+				})
+			},
+		)?,
+	)?;
+
 	// utils
 	exports.set(
 		"hash",
@@ -48,6 +65,24 @@ fn entrypoint(lua: &Lua) -> LuaResult<LuaTable> {
 		lua.create_function(|_, ()| Ok(crate::version()))?,
 	)?;
 
+	exports.set(
+		"user_color",
+		lua.create_function(|_, (id, theme): (String, Option<CodempTheme>)| {
+			let id = id.parse::<uuid::Uuid>().map_err(LuaError::runtime)?;
+			Ok(crate::api::color::user_color(id, theme))
+		})?,
+	)?;
+
+	exports.set(
+		"error",
+		lua.create_function(|_, (err,): (LuaValue,)| {
+			Ok(match err {
+				LuaValue::Error(err) => CodempError::raised(&err).cloned(),
+				_ => None,
+			})
+		})?,
+	)?;
+
 	// runtime
 	exports.set(
 		"setup_driver",
@@ -63,9 +98,14 @@ fn entrypoint(lua: &Lua) -> LuaResult<LuaTable> {
 					val.push_back(LuaValue::Function(cb));
 					val.push_back(arg.into_lua(lua)?);
 				}
-				Some(ext::callback::LuaCallback::Fail(msg)) => {
+				Some(ext::callback::LuaCallback::Fail(err)) => {
 					val.push_back(false.into_lua(lua)?);
-					val.push_back(msg.into_lua(lua)?);
+					match CodempError::raised(&err) {
+						Some(err) => val.push_back(err.clone().into_lua(lua)?),
+						None => val.push_back(
+							format!("promise failed with error: {err:?}").into_lua(lua)?,
+						),
+					}
 				}
 			}
 			Ok(val)
@@ -81,20 +121,63 @@ fn entrypoint(lua: &Lua) -> LuaResult<LuaTable> {
 	Ok(exports)
 }
 
+/// Error raised by codemp, carrying its kind so that scripts don't need to parse its message.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{message}")]
+pub(crate) struct CodempError {
+	kind: crate::errors::ErrorKind,
+	message: String,
+}
+
+impl CodempError {
+	/// Find the codemp error which caused given error, if any.
+	fn raised(err: &LuaError) -> Option<&CodempError> {
+		match err {
+			LuaError::ExternalError(err) => err.downcast_ref(),
+			LuaError::CallbackError { cause, .. } => Self::raised(cause),
+			LuaError::WithContext { cause, .. } => Self::raised(cause),
+			_ => None,
+		}
+	}
+}
+
+impl LuaUserData for CodempError {
+	fn add_fields<F: LuaUserDataFields<Self>>(fields: &mut F) {
+		fields.add_field_method_get("kind", |_, this| Ok(this.kind.as_str()));
+		fields.add_field_method_get("message", |_, this| Ok(this.message.clone()));
+		fields.add_field_method_get("retryable", |_, this| Ok(this.kind.is_retryable()));
+	}
+
+	fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+		methods.add_meta_method(LuaMetaMethod::ToString, |_, this, ()| {
+			Ok(format!("{} error: {}", this.kind, this.message))
+		});
+	}
+}
+
 impl From<crate::errors::ConnectionError> for LuaError {
 	fn from(value: crate::errors::ConnectionError) -> Self {
-		LuaError::runtime(value.to_string())
+		LuaError::external(CodempError {
+			kind: value.kind(),
+			message: value.to_string(),
+		})
 	}
 }
 
 impl From<crate::errors::RemoteError> for LuaError {
 	fn from(value: crate::errors::RemoteError) -> Self {
-		LuaError::runtime(value.to_string())
+		LuaError::external(CodempError {
+			kind: value.kind(),
+			message: value.to_string(),
+		})
 	}
 }
 
 impl From<crate::errors::ControllerError> for LuaError {
 	fn from(value: crate::errors::ControllerError) -> Self {
-		LuaError::runtime(value.to_string())
+		LuaError::external(CodempError {
+			kind: value.kind(),
+			message: value.to_string(),
+		})
 	}
 }
@@ -4,7 +4,7 @@ use mlua_codemp_patch as mlua;
 
 use super::ext::a_sync::a_sync;
 
-super::ext::impl_lua_serde! { CodempEvent }
+super::ext::impl_lua_serde! { CodempEvent CodempPresence }
 
 impl LuaUserData for CodempWorkspace {
 	fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
@@ -21,6 +21,25 @@ impl LuaUserData for CodempWorkspace {
 			|_, this, (name,): (String,)| a_sync! { this => this.attach_buffer(&name).await? },
 		);
 
+		methods.add_method("attach_buffer_read_only", |_, this, (name,): (String,)| {
+			a_sync! { this => this.attach_buffer_read_only(&name).await? }
+		});
+
+		methods.add_method(
+			"attach_chat",
+			|_, this, ()| a_sync! { this => this.attach_chat().await? },
+		);
+
+		methods.add_method("get_chat", |_, this, ()| Ok(this.get_chat()));
+
+		methods.add_method("attach_decorations", |_, this, (path,): (String,)| {
+			a_sync! { this => this.attach_decorations(&path).await? }
+		});
+
+		methods.add_method("get_decorations", |_, this, (path,): (String,)| {
+			Ok(this.get_decorations(&path))
+		});
+
 		methods.add_method("detach_buffer", |_, this, (name,): (String,)| {
 			Ok(this.detach_buffer(&name))
 		});
@@ -43,6 +62,19 @@ impl LuaUserData for CodempWorkspace {
 			|_, this, ()| a_sync! { this => this.fetch_users().await? },
 		);
 
+		methods.add_method(
+			"fetch_members",
+			|_, this, ()| a_sync! { this => this.fetch_members().await? },
+		);
+		methods.add_method(
+			"fetch_role",
+			|_, this, ()| a_sync! { this => this.fetch_role().await? },
+		);
+		methods.add_method(
+			"fetch_presence",
+			|_, this, ()| a_sync! { this => this.fetch_presence().await? },
+		);
+
 		methods.add_method("search_buffers", |_, this, (filter,): (Option<String>,)| {
 			Ok(this.search_buffers(filter.as_deref()))
 		});
@@ -53,10 +85,29 @@ impl LuaUserData for CodempWorkspace {
 			}
 		});
 
+		methods.add_method("fetch_buffer_metadata", |_, this, (path,): (String,)| {
+			a_sync! {
+				this => this.fetch_buffer_metadata(&path).await?
+			}
+		});
+		methods.add_method(
+			"set_buffer_metadata",
+			|_, this, (path, metadata): (String, CodempBufferMetadata)| {
+				a_sync! {
+					this => this.set_buffer_metadata(&path, metadata).await?
+				}
+			},
+		);
+
 		methods.add_method("id", |_, this, ()| Ok(this.id()));
 		methods.add_method("cursor", |_, this, ()| Ok(this.cursor()));
 		methods.add_method("active_buffers", |_, this, ()| Ok(this.active_buffers()));
 		methods.add_method("user_list", |_, this, ()| Ok(this.user_list()));
+		methods.add_method("role", |_, this, ()| Ok(this.role()));
+		methods.add_method("presence", |_, this, ()| Ok(this.presence()));
+		methods.add_method("user_presence", |_, this, (name,): (String,)| {
+			Ok(this.user_presence(&name))
+		});
 
 		methods.add_method("recv", |_, this, ()| a_sync! { this => this.recv().await? });
 
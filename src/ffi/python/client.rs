@@ -1,6 +1,6 @@
 use super::a_sync_allow_threads;
 use super::Client;
-use crate::api::User;
+use crate::api::{Role, User};
 use crate::workspace::Workspace;
 use pyo3::prelude::*;
 
@@ -55,6 +55,54 @@ impl Client {
 		a_sync_allow_threads!(py, this.invite_to_workspace(workspace, user).await)
 	}
 
+	#[pyo3(name = "fetch_workspace_members")]
+	fn pyfetch_workspace_members(
+		&self,
+		py: Python<'_>,
+		workspace: String,
+	) -> PyResult<super::Promise> {
+		tracing::info!("attempting to fetch members of workspace {workspace}");
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.fetch_workspace_members(workspace).await)
+	}
+
+	#[pyo3(name = "remove_from_workspace")]
+	fn pyremove_from_workspace(
+		&self,
+		py: Python<'_>,
+		workspace: String,
+		user: String,
+	) -> PyResult<super::Promise> {
+		tracing::info!("attempting to remove {user} from workspace {workspace}");
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.remove_from_workspace(workspace, user).await)
+	}
+
+	#[pyo3(name = "set_workspace_role")]
+	fn pyset_workspace_role(
+		&self,
+		py: Python<'_>,
+		workspace: String,
+		user: String,
+		role: Role,
+	) -> PyResult<super::Promise> {
+		tracing::info!("attempting to make {user} {role} in workspace {workspace}");
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.set_workspace_role(workspace, user, role).await)
+	}
+
+	#[pyo3(name = "set_profile")]
+	#[pyo3(signature = (display_name=None, avatar=None))]
+	fn pyset_profile(
+		&self,
+		py: Python<'_>,
+		display_name: Option<String>,
+		avatar: Option<String>,
+	) -> PyResult<super::Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.set_profile(display_name, avatar).await)
+	}
+
 	#[pyo3(name = "fetch_owned_workspaces")]
 	fn pyfetch_owned_workspaces(&self, py: Python<'_>) -> PyResult<super::Promise> {
 		tracing::info!("attempting to fetch owned workspaces");
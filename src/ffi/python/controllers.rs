@@ -1,8 +1,10 @@
 use crate::api::controller::{AsyncReceiver, AsyncSender};
 use crate::api::TextChange;
-use crate::api::{Cursor, Selection};
+use crate::api::{Cursor, Decoration, Draft, LineEnding, Selection};
 use crate::buffer::Controller as BufferController;
+use crate::chat::Controller as ChatController;
 use crate::cursor::Controller as CursorController;
+use crate::decoration::Controller as DecorationController;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
@@ -65,12 +67,29 @@ impl BufferController {
 		self.path().to_string()
 	}
 
+	#[pyo3(name = "is_read_only")]
+	fn pyis_read_only(&self) -> bool {
+		self.is_read_only()
+	}
+
 	#[pyo3(name = "content")]
 	fn pycontent(&self, py: Python) -> PyResult<Promise> {
 		let this = self.clone();
 		a_sync_allow_threads!(py, this.content().await)
 	}
 
+	#[pyo3(name = "verify")]
+	fn pyverify(&self, py: Python, hash: i64) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.verify(hash).await)
+	}
+
+	#[pyo3(name = "resync")]
+	fn pyresync(&self, py: Python, content: String) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.resync(&content).await)
+	}
+
 	#[pyo3(name = "send")]
 	fn pysend(&self, _py: Python, op: TextChange) -> PyResult<()> {
 		let this = self.clone();
@@ -115,6 +134,209 @@ impl BufferController {
 	fn pyclear_callback(&self) {
 		self.clear_callback();
 	}
+
+	#[pyo3(name = "annotate")]
+	fn pyannotate(&self, start_idx: u32, end_idx: u32, text: String) -> PyResult<String> {
+		Ok(self.annotate(start_idx, end_idx, &text)?)
+	}
+
+	#[pyo3(name = "resolve_annotation")]
+	fn pyresolve_annotation(&self, id: String) -> PyResult<()> {
+		Ok(self.resolve_annotation(&id)?)
+	}
+
+	#[pyo3(name = "delete_annotation")]
+	fn pydelete_annotation(&self, id: String) -> PyResult<()> {
+		Ok(self.delete_annotation(&id)?)
+	}
+
+	#[pyo3(name = "annotations")]
+	fn pyannotations(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.annotations().await)
+	}
+
+	#[pyo3(name = "annotation_callback")]
+	fn pyannotation_callback(&self, py: Python, cb: PyObject) -> PyResult<()> {
+		if !cb.bind_borrowed(py).is_callable() {
+			return Err(PyValueError::new_err("The object passed must be callable."));
+		}
+
+		self.annotation_callback(move |ctl| {
+			Python::with_gil(|py| {
+				// TODO what to do with this error?
+				let _ = cb.call1(py, (ctl,));
+			})
+		});
+		Ok(())
+	}
+
+	#[pyo3(name = "clear_annotation_callback")]
+	fn pyclear_annotation_callback(&self) {
+		self.clear_annotation_callback();
+	}
+
+	#[pyo3(name = "metadata")]
+	fn pymetadata(&self) -> crate::api::BufferMetadata {
+		self.metadata()
+	}
+
+	#[pyo3(name = "metadata_callback")]
+	fn pymetadata_callback(&self, py: Python, cb: PyObject) -> PyResult<()> {
+		if !cb.bind_borrowed(py).is_callable() {
+			return Err(PyValueError::new_err("The object passed must be callable."));
+		}
+
+		self.metadata_callback(move |ctl| {
+			Python::with_gil(|py| {
+				// TODO what to do with this error?
+				let _ = cb.call1(py, (ctl,));
+			})
+		});
+		Ok(())
+	}
+
+	#[pyo3(name = "clear_metadata_callback")]
+	fn pyclear_metadata_callback(&self) {
+		self.clear_metadata_callback();
+	}
+
+	#[pyo3(name = "normalize_line_endings")]
+	fn pynormalize_line_endings(&self, enabled: bool) {
+		self.normalize_line_endings(enabled);
+	}
+
+	#[pyo3(name = "is_normalizing_line_endings")]
+	fn pyis_normalizing_line_endings(&self) -> bool {
+		self.is_normalizing_line_endings()
+	}
+
+	#[pyo3(name = "translate_line_endings", signature = (local=None))]
+	fn pytranslate_line_endings(&self, local: Option<LineEnding>) {
+		self.translate_line_endings(local);
+	}
+
+	#[pyo3(name = "local_line_ending")]
+	fn pylocal_line_ending(&self) -> Option<LineEnding> {
+		self.local_line_ending()
+	}
+}
+
+// need to do manually since Controller is a trait implementation
+#[pymethods]
+impl ChatController {
+	#[pyo3(name = "send")]
+	fn pysend(&self, _py: Python, draft: Draft) -> PyResult<()> {
+		self.send(draft)?;
+		Ok(())
+	}
+
+	#[pyo3(name = "history")]
+	fn pyhistory(&self) -> Vec<crate::api::Message> {
+		self.history()
+	}
+
+	#[pyo3(name = "thread_history")]
+	fn pythread_history(&self, buffer: String) -> Vec<crate::api::Message> {
+		self.thread_history(&buffer)
+	}
+
+	#[pyo3(name = "try_recv")]
+	fn pytry_recv(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.try_recv().await)
+	}
+
+	#[pyo3(name = "recv")]
+	fn pyrecv(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.recv().await)
+	}
+
+	#[pyo3(name = "poll")]
+	fn pypoll(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.poll().await)
+	}
+
+	#[pyo3(name = "callback")]
+	fn pycallback(&self, py: Python, cb: PyObject) -> PyResult<()> {
+		if !cb.bind_borrowed(py).is_callable() {
+			return Err(PyValueError::new_err("The object passed must be callable."));
+		}
+
+		self.callback(move |ctl| {
+			Python::with_gil(|py| {
+				// TODO what to do with this error?
+				let _ = cb.call1(py, (ctl,));
+			})
+		});
+		Ok(())
+	}
+
+	#[pyo3(name = "clear_callback")]
+	fn pyclear_callback(&self) {
+		self.clear_callback();
+	}
+}
+
+// need to do manually since Controller is a trait implementation
+#[pymethods]
+impl DecorationController {
+	#[pyo3(name = "path")]
+	fn pypath(&self) -> String {
+		self.path().to_string()
+	}
+
+	#[pyo3(name = "send")]
+	fn pysend(&self, _py: Python, decoration: Decoration) -> PyResult<()> {
+		self.send(decoration)?;
+		Ok(())
+	}
+
+	#[pyo3(name = "active")]
+	fn pyactive(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.active().await)
+	}
+
+	#[pyo3(name = "try_recv")]
+	fn pytry_recv(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.try_recv().await)
+	}
+
+	#[pyo3(name = "recv")]
+	fn pyrecv(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.recv().await)
+	}
+
+	#[pyo3(name = "poll")]
+	fn pypoll(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.poll().await)
+	}
+
+	#[pyo3(name = "callback")]
+	fn pycallback(&self, py: Python, cb: PyObject) -> PyResult<()> {
+		if !cb.bind_borrowed(py).is_callable() {
+			return Err(PyValueError::new_err("The object passed must be callable."));
+		}
+
+		self.callback(move |ctl| {
+			Python::with_gil(|py| {
+				// TODO what to do with this error?
+				let _ = cb.call1(py, (ctl,));
+			})
+		});
+		Ok(())
+	}
+
+	#[pyo3(name = "clear_callback")]
+	fn pyclear_callback(&self) {
+		self.clear_callback();
+	}
 }
 
 // We have to write this manually since
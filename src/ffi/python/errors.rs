@@ -0,0 +1,111 @@
+use pyo3::{exceptions::PyException, PyErr};
+
+use crate::errors::{ConnectionError, ControllerError, ErrorKind, RemoteError};
+
+pyo3::create_exception!(
+	codemp,
+	CodempError,
+	PyException,
+	"Base class of codemp errors."
+);
+pyo3::create_exception!(
+	codemp,
+	RetryableError,
+	CodempError,
+	"Temporary failure: trying again later may succeed."
+);
+pyo3::create_exception!(
+	codemp,
+	NotFoundError,
+	CodempError,
+	"Resource does not exist."
+);
+pyo3::create_exception!(
+	codemp,
+	UnauthorizedError,
+	CodempError,
+	"Credentials are missing, wrong or expired."
+);
+pyo3::create_exception!(
+	codemp,
+	ForbiddenError,
+	CodempError,
+	"Credentials don't allow this operation."
+);
+pyo3::create_exception!(
+	codemp,
+	AlreadyExistsError,
+	CodempError,
+	"Resource to create already exists."
+);
+pyo3::create_exception!(
+	codemp,
+	UnavailableError,
+	RetryableError,
+	"Server can't be reached right now."
+);
+pyo3::create_exception!(
+	codemp,
+	InvalidArgumentError,
+	CodempError,
+	"Request or configuration is malformed."
+);
+pyo3::create_exception!(
+	codemp,
+	TimedOutError,
+	RetryableError,
+	"Operation did not complete in time."
+);
+pyo3::create_exception!(
+	codemp,
+	RateLimitedError,
+	RetryableError,
+	"Too many requests were made."
+);
+pyo3::create_exception!(
+	codemp,
+	ConflictError,
+	CodempError,
+	"Operation conflicts with the current state."
+);
+pyo3::create_exception!(
+	codemp,
+	ClosedError,
+	CodempError,
+	"Worker or connection is already closed."
+);
+
+/// Raise the exception class matching given error kind.
+fn py_error(kind: ErrorKind, message: String) -> PyErr {
+	match kind {
+		ErrorKind::NotFound => NotFoundError::new_err(message),
+		ErrorKind::Unauthorized => UnauthorizedError::new_err(message),
+		ErrorKind::Forbidden => ForbiddenError::new_err(message),
+		ErrorKind::AlreadyExists => AlreadyExistsError::new_err(message),
+		ErrorKind::Unavailable => UnavailableError::new_err(message),
+		ErrorKind::InvalidArgument => InvalidArgumentError::new_err(message),
+		ErrorKind::TimedOut => TimedOutError::new_err(message),
+		ErrorKind::RateLimited => RateLimitedError::new_err(message),
+		ErrorKind::Conflict => ConflictError::new_err(message),
+		ErrorKind::Closed => ClosedError::new_err(message),
+		_ => CodempError::new_err(message),
+	}
+}
+
+impl From<ConnectionError> for PyErr {
+	fn from(value: ConnectionError) -> Self {
+		py_error(value.kind(), format!("Connection error: {value}"))
+	}
+}
+
+impl From<RemoteError> for PyErr {
+	fn from(value: RemoteError) -> Self {
+		py_error(value.kind(), format!("Remote error: {value}"))
+	}
+}
+
+impl From<ControllerError> for PyErr {
+	fn from(value: ControllerError) -> Self {
+		py_error(value.kind(), format!("Controller error: {value}"))
+	}
+}
@@ -1,16 +1,24 @@
 pub mod client;
 pub mod controllers;
+#[allow(unexpected_cfgs)] // create_exception! checks features of pyo3 itself
+pub mod errors;
 pub mod workspace;
 
 use crate::{
-	api::{BufferUpdate, Config, Cursor, Selection, TextChange, User},
+	api::{
+		Annotation, BufferMetadata, BufferUpdate, CallbackStrategy, Config, Cursor, Decoration,
+		DecorationKind, Draft, Indentation, LineEnding, Member, Message, Presence, Role, Selection,
+		Status, TextChange, Theme, TlsSettings, Tuning, User,
+	},
 	buffer::Controller as BufferController,
+	chat::Controller as ChatController,
 	cursor::Controller as CursorController,
+	decoration::Controller as DecorationController,
 	Client, Workspace,
 };
 
 use pyo3::{
-	exceptions::{PyConnectionError, PyRuntimeError, PySystemError},
+	exceptions::{PyRuntimeError, PySystemError},
 	prelude::*,
 	types::PyDict,
 };
@@ -181,6 +189,23 @@ impl User {
 		Ok(())
 	}
 
+	#[pyo3(name = "color", signature = (theme=None))]
+	fn pycolor(&self, theme: Option<Theme>) -> &'static str {
+		self.color(theme)
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl Presence {
+	#[pyo3(name = "label")]
+	fn pylabel(&self) -> String {
+		self.label().to_string()
+	}
+
 	fn __str__(&self) -> String {
 		format!("{self:?}")
 	}
@@ -195,21 +220,115 @@ impl Config {
 		password: String,
 		kwds: Option<Bound<'_, PyDict>>,
 	) -> PyResult<Self> {
-		if let Some(kwgs) = kwds {
-			let host = kwgs.get_item("host")?.and_then(|e| e.extract().ok());
-			let port = kwgs.get_item("port")?.and_then(|e| e.extract().ok());
-			let tls = kwgs.get_item("tls")?.and_then(|e| e.extract().ok());
+		Self::with_kwargs(Self::new(username, password), kwds)
+	}
 
-			Ok(Self {
-				username,
-				password,
-				host,
-				port,
-				tls,
-			})
-		} else {
-			Ok(Self::new(username, password))
+	/// Load configuration from a file and the environment, see [`crate::loader`], with given
+	/// keyword arguments taking precedence.
+	#[staticmethod]
+	#[pyo3(signature = (path=None, **kwds))]
+	pub fn load(path: Option<String>, kwds: Option<Bound<'_, PyDict>>) -> PyResult<Self> {
+		let explicit = Self::with_kwargs(Self::default(), kwds)?;
+		Ok(match path {
+			Some(path) => crate::loader::load_file(path, explicit)?,
+			None => crate::loader::load(explicit)?,
+		})
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+impl Config {
+	fn with_kwargs(mut self, kwds: Option<Bound<'_, PyDict>>) -> PyResult<Self> {
+		let Some(kwgs) = kwds else {
+			return Ok(self);
+		};
+		if let Some(username) = kwgs.get_item("username")?.and_then(|e| e.extract().ok()) {
+			self.username = username;
 		}
+		if let Some(password) = kwgs.get_item("password")?.and_then(|e| e.extract().ok()) {
+			self.password = password;
+		}
+		self.token = kwgs.get_item("token")?.and_then(|e| e.extract().ok());
+		self.token_env = kwgs.get_item("token_env")?.and_then(|e| e.extract().ok());
+		self.token_command = kwgs
+			.get_item("token_command")?
+			.and_then(|e| e.extract().ok());
+		self.endpoint = kwgs.get_item("endpoint")?.and_then(|e| e.extract().ok());
+		self.host = kwgs.get_item("host")?.and_then(|e| e.extract().ok());
+		self.port = kwgs.get_item("port")?.and_then(|e| e.extract().ok());
+		self.tls = kwgs.get_item("tls")?.and_then(|e| e.extract().ok());
+		self.tls_settings = kwgs
+			.get_item("tls_settings")?
+			.and_then(|e| e.extract().ok());
+		self.proxy = kwgs.get_item("proxy")?.and_then(|e| e.extract().ok());
+		self.tuning = kwgs.get_item("tuning")?.and_then(|e| e.extract().ok());
+		Ok(self)
+	}
+}
+
+#[pymethods]
+impl TlsSettings {
+	#[new]
+	#[pyo3(signature = (**kwds))]
+	pub fn pynew(kwds: Option<Bound<'_, PyDict>>) -> PyResult<Self> {
+		let Some(kwgs) = kwds else {
+			return Ok(Self::default());
+		};
+		let get = |key: &str| -> PyResult<Option<String>> {
+			Ok(kwgs.get_item(key)?.and_then(|e| e.extract().ok()))
+		};
+		Ok(Self {
+			ca_bundle: get("ca_bundle")?,
+			native_roots: kwgs
+				.get_item("native_roots")?
+				.and_then(|e| e.extract().ok()),
+			client_cert: get("client_cert")?,
+			client_key: get("client_key")?,
+			domain: get("domain")?,
+			pinned_sha256: get("pinned_sha256")?,
+		})
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl Tuning {
+	#[new]
+	#[pyo3(signature = (**kwds))]
+	pub fn pynew(kwds: Option<Bound<'_, PyDict>>) -> PyResult<Self> {
+		let Some(kwgs) = kwds else {
+			return Ok(Self::default());
+		};
+		let get = |key: &str| -> PyResult<Option<u32>> {
+			Ok(kwgs.get_item(key)?.and_then(|e| e.extract().ok()))
+		};
+		Ok(Self {
+			buffer_queue: get("buffer_queue")?,
+			cursor_queue: get("cursor_queue")?,
+			hash_period: get("hash_period")?,
+			worker_tick_ms: get("worker_tick_ms")?,
+			connect_timeout_ms: get("connect_timeout_ms")?,
+			request_timeout_ms: get("request_timeout_ms")?,
+			http2_keepalive_ms: get("http2_keepalive_ms")?,
+			http2_keepalive_timeout_ms: get("http2_keepalive_timeout_ms")?,
+			tcp_keepalive_ms: get("tcp_keepalive_ms")?,
+			connect_retries: get("connect_retries")?,
+			retry_backoff_ms: get("retry_backoff_ms")?,
+			refresh_margin_ms: get("refresh_margin_ms")?,
+			callback_strategy: kwgs
+				.get_item("callback_strategy")?
+				.and_then(|e| e.extract().ok()),
+			callback_queue: get("callback_queue")?,
+			idle_after_ms: get("idle_after_ms")?,
+			away_after_ms: get("away_after_ms")?,
+			chat_history: get("chat_history")?,
+		})
 	}
 
 	fn __str__(&self) -> String {
@@ -277,6 +396,90 @@ impl Selection {
 	}
 }
 
+#[pymethods]
+impl Draft {
+	#[new]
+	#[pyo3(signature = (text, thread=None))]
+	pub fn py_new(text: String, thread: Option<Selection>) -> Self {
+		Self { text, thread }
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl Message {
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl Annotation {
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl Decoration {
+	#[new]
+	#[pyo3(signature = (kind, start_idx, end_idx, message=String::new(), ttl_ms=Decoration::DEFAULT_TTL_MS))]
+	pub fn py_new(
+		kind: DecorationKind,
+		start_idx: u32,
+		end_idx: u32,
+		message: String,
+		ttl_ms: u32,
+	) -> Self {
+		Self {
+			ttl_ms,
+			..Self::new(kind, start_idx, end_idx, message)
+		}
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl BufferMetadata {
+	#[new]
+	#[pyo3(signature = (language=None, line_ending=None, indentation=None, encoding=None))]
+	pub fn py_new(
+		language: Option<String>,
+		line_ending: Option<LineEnding>,
+		indentation: Option<Indentation>,
+		encoding: Option<String>,
+	) -> Self {
+		Self {
+			language,
+			line_ending,
+			indentation,
+			encoding,
+		}
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
+#[pymethods]
+impl Indentation {
+	#[new]
+	pub fn py_new(tabs: bool, width: u32) -> Self {
+		Self { tabs, width }
+	}
+
+	fn __str__(&self) -> String {
+		format!("{self:?}")
+	}
+}
+
 #[pymethods]
 impl BufferUpdate {
 	fn __str__(&self) -> String {
@@ -370,24 +573,6 @@ fn set_logger(py: Python, logging_cb: PyObject, debug: bool) -> bool {
 	log_subscribed
 }
 
-impl From<crate::errors::ConnectionError> for PyErr {
-	fn from(value: crate::errors::ConnectionError) -> Self {
-		PyConnectionError::new_err(format!("Connection error: {value}"))
-	}
-}
-
-impl From<crate::errors::RemoteError> for PyErr {
-	fn from(value: crate::errors::RemoteError) -> Self {
-		PyRuntimeError::new_err(format!("Remote error: {value}"))
-	}
-}
-
-impl From<crate::errors::ControllerError> for PyErr {
-	fn from(value: crate::errors::ControllerError) -> Self {
-		PyRuntimeError::new_err(format!("Controller error: {value}"))
-	}
-}
-
 #[pymodule]
 fn codemp(m: &Bound<'_, PyModule>) -> PyResult<()> {
 	m.add_function(wrap_pyfunction!(version, m)?)?;
@@ -399,16 +584,60 @@ fn codemp(m: &Bound<'_, PyModule>) -> PyResult<()> {
 	m.add_class::<BufferUpdate>()?;
 	m.add_class::<TextChange>()?;
 	m.add_class::<BufferController>()?;
+	m.add_class::<Annotation>()?;
+	m.add_class::<BufferMetadata>()?;
+	m.add_class::<LineEnding>()?;
+	m.add_class::<Indentation>()?;
 
 	m.add_class::<Cursor>()?;
 	m.add_class::<Selection>()?;
 	m.add_class::<CursorController>()?;
 
+	m.add_class::<Draft>()?;
+	m.add_class::<Message>()?;
+	m.add_class::<ChatController>()?;
+
+	m.add_class::<Decoration>()?;
+	m.add_class::<DecorationKind>()?;
+	m.add_class::<DecorationController>()?;
+
 	m.add_class::<User>()?;
+	m.add_class::<Member>()?;
+	m.add_class::<Role>()?;
+	m.add_class::<Presence>()?;
+	m.add_class::<Status>()?;
+	m.add_class::<Theme>()?;
 
 	m.add_class::<Workspace>()?;
 	m.add_class::<Client>()?;
 	m.add_class::<Config>()?;
+	m.add_class::<TlsSettings>()?;
+	m.add_class::<Tuning>()?;
+	m.add_class::<CallbackStrategy>()?;
+
+	use errors::*;
+	let py = m.py();
+	m.add("CodempError", py.get_type_bound::<CodempError>())?;
+	m.add("RetryableError", py.get_type_bound::<RetryableError>())?;
+	m.add("NotFoundError", py.get_type_bound::<NotFoundError>())?;
+	m.add(
+		"UnauthorizedError",
+		py.get_type_bound::<UnauthorizedError>(),
+	)?;
+	m.add("ForbiddenError", py.get_type_bound::<ForbiddenError>())?;
+	m.add(
+		"AlreadyExistsError",
+		py.get_type_bound::<AlreadyExistsError>(),
+	)?;
+	m.add("UnavailableError", py.get_type_bound::<UnavailableError>())?;
+	m.add(
+		"InvalidArgumentError",
+		py.get_type_bound::<InvalidArgumentError>(),
+	)?;
+	m.add("TimedOutError", py.get_type_bound::<TimedOutError>())?;
+	m.add("RateLimitedError", py.get_type_bound::<RateLimitedError>())?;
+	m.add("ConflictError", py.get_type_bound::<ConflictError>())?;
+	m.add("ClosedError", py.get_type_bound::<ClosedError>())?;
 
 	Ok(())
 }
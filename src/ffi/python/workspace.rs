@@ -1,7 +1,9 @@
 use crate::api::controller::AsyncReceiver;
-use crate::api::User;
+use crate::api::{BufferMetadata, Presence, Role, User};
 use crate::buffer::Controller as BufferController;
+use crate::chat::Controller as ChatController;
 use crate::cursor::Controller as CursorController;
+use crate::decoration::Controller as DecorationController;
 use crate::workspace::Workspace;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -24,6 +26,24 @@ impl Workspace {
 		a_sync_allow_threads!(py, this.attach_buffer(path.as_str()).await)
 	}
 
+	#[pyo3(name = "attach_buffer_read_only")]
+	fn pyattach_buffer_read_only(&self, py: Python, path: String) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.attach_buffer_read_only(path.as_str()).await)
+	}
+
+	#[pyo3(name = "attach_chat")]
+	fn pyattach_chat(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.attach_chat().await)
+	}
+
+	#[pyo3(name = "attach_decorations")]
+	fn pyattach_decorations(&self, py: Python, path: String) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.attach_decorations(path.as_str()).await)
+	}
+
 	#[pyo3(name = "detach_buffer")]
 	fn pydetach_buffer(&self, path: String) -> bool {
 		self.detach_buffer(path.as_str())
@@ -48,6 +68,41 @@ impl Workspace {
 		a_sync_allow_threads!(py, this.fetch_buffer_users(path.as_str()).await)
 	}
 
+	#[pyo3(name = "fetch_members")]
+	fn pyfetch_members(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.fetch_members().await)
+	}
+
+	#[pyo3(name = "fetch_role")]
+	fn pyfetch_role(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.fetch_role().await)
+	}
+
+	#[pyo3(name = "fetch_presence")]
+	fn pyfetch_presence(&self, py: Python) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.fetch_presence().await)
+	}
+
+	#[pyo3(name = "fetch_buffer_metadata")]
+	fn pyfetch_buffer_metadata(&self, py: Python, path: String) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.fetch_buffer_metadata(path.as_str()).await)
+	}
+
+	#[pyo3(name = "set_buffer_metadata")]
+	fn pyset_buffer_metadata(
+		&self,
+		py: Python,
+		path: String,
+		metadata: BufferMetadata,
+	) -> PyResult<Promise> {
+		let this = self.clone();
+		a_sync_allow_threads!(py, this.set_buffer_metadata(path.as_str(), metadata).await)
+	}
+
 	#[pyo3(name = "delete_buffer")]
 	fn pydelete_buffer(&self, py: Python, path: String) -> PyResult<Promise> {
 		let this = self.clone();
@@ -69,6 +124,16 @@ impl Workspace {
 		self.get_buffer(path.as_str())
 	}
 
+	#[pyo3(name = "get_chat")]
+	fn pyget_chat(&self) -> Option<ChatController> {
+		self.get_chat()
+	}
+
+	#[pyo3(name = "get_decorations")]
+	fn pyget_decorations(&self, path: String) -> Option<DecorationController> {
+		self.get_decorations(path.as_str())
+	}
+
 	#[pyo3(name = "active_buffers")]
 	fn pyactive_buffers(&self) -> Vec<String> {
 		self.active_buffers()
@@ -85,6 +150,21 @@ impl Workspace {
 		self.user_list()
 	}
 
+	#[pyo3(name = "presence")]
+	fn pypresence(&self) -> Vec<Presence> {
+		self.presence()
+	}
+
+	#[pyo3(name = "user_presence")]
+	fn pyuser_presence(&self, name: String) -> Option<Presence> {
+		self.user_presence(&name)
+	}
+
+	#[pyo3(name = "role")]
+	fn pyrole(&self) -> Role {
+		self.role()
+	}
+
 	#[pyo3(name = "recv")]
 	fn pyrecv(&self, py: Python) -> PyResult<Promise> {
 		let this = self.clone();
@@ -105,6 +105,12 @@ pub mod cursor;
 /// buffer related types and controller
 pub mod buffer;
 
+/// chat related types and controller
+pub mod chat;
+
+/// decoration related types and controller
+pub mod decoration;
+
 /// workspace handle and operations
 pub mod workspace;
 pub use workspace::Workspace;
@@ -113,9 +119,16 @@ pub use workspace::Workspace;
 pub mod client;
 pub use client::Client;
 
+/// credentials providers used to authenticate
+pub mod auth;
+
 /// crate error types
 pub mod errors;
 
+/// load client configuration from files and environment variables
+#[cfg(feature = "config-file")]
+pub mod loader;
+
 /// all-in-one imports : `use codemp::prelude::*;`
 pub mod prelude;
 
@@ -128,6 +141,20 @@ pub mod ffi;
 /// internal network services and interceptors
 pub(crate) mod network;
 
+pub(crate) mod dispatch;
+
+/// tls connector honoring custom certificates and pins
+pub(crate) mod tls;
+
+/// proxy tunnels
+pub(crate) mod proxy;
+
+/// underlying byte streams, including custom transports
+pub mod transport;
+
+/// protocol extensions, on top of codemp-proto
+pub mod protocol;
+
 /// Get the current version of the client
 pub fn version() -> &'static str {
 	env!("CARGO_PKG_VERSION")
@@ -0,0 +1,238 @@
+//! ### Loader
+//! Build a [`Config`] from a configuration file and `CODEMP_*` environment variables, so that
+//! every editor plugin on a machine can share the same settings.
+//!
+//! Values are merged in increasing order of precedence:
+//!  - the configuration file, see [`find`];
+//!  - environment variables like `CODEMP_HOST` or `CODEMP_TUNING_CONNECT_TIMEOUT_MS`, named after
+//!    [`Config`] fields (nested `tls_settings` and `tuning` fields are prefixed with their name);
+//!  - explicit values given by the caller, ignoring unset fields and empty credentials.
+//!
+//! Configuration files may be written in TOML (`config.toml`) or JSON (`config.json`), with the
+//! same structure as [`Config`]:
+//! ```toml
+//! username = "alice"
+//! endpoint = "https://code.example:50053"
+//!
+//! [tuning]
+//! connect_timeout_ms = 5000
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::{
+	api::Config,
+	errors::{ConnectionError, ConnectionResult},
+};
+
+/// Environment variable pointing to a configuration file, overriding the default locations.
+pub const CONFIG_ENV: &str = "CODEMP_CONFIG";
+
+/// Prefix of environment variables holding configuration values.
+const ENV_PREFIX: &str = "CODEMP_";
+
+/// Nested configuration sections, which environment variables address with a prefix.
+const SECTIONS: [&str; 2] = ["tls_settings", "tuning"];
+
+/// Locate the configuration file, looking in order at:
+///  - the path in `CODEMP_CONFIG`, even if missing;
+///  - `codemp/config.toml` and `codemp/config.json` in the user configuration directory
+///    (`$XDG_CONFIG_HOME`, defaulting to `~/.config`, or `%APPDATA%` on Windows);
+///  - the same files in every directory of `$XDG_CONFIG_DIRS`, defaulting to `/etc/xdg`.
+pub fn find() -> Option<PathBuf> {
+	if let Some(path) = std::env::var_os(CONFIG_ENV) {
+		return Some(path.into());
+	}
+	let home = std::env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.or_else(|| match cfg!(windows) {
+			true => std::env::var_os("APPDATA").map(PathBuf::from),
+			false => std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")),
+		});
+	let system = match std::env::var_os("XDG_CONFIG_DIRS") {
+		Some(dirs) => std::env::split_paths(&dirs).collect(),
+		None if cfg!(unix) => vec![PathBuf::from("/etc/xdg")],
+		None => Vec::new(),
+	};
+	home.into_iter()
+		.chain(system)
+		.flat_map(|dir| ["config.toml", "config.json"].map(|name| dir.join("codemp").join(name)))
+		.find(|path| path.is_file())
+}
+
+/// Load configuration from the file found with [`find`] (if any) and the environment, then
+/// apply given explicit values on top.
+///
+/// Pass [`Config::default`] when there are no explicit values.
+#[allow(clippy::result_large_err)] // same error connecting with it would give
+pub fn load(explicit: Config) -> ConnectionResult<Config> {
+	let file = find()
+		.map(|path| read(&path).map(|x| (path, x)))
+		.transpose()
+		.map_err(ConnectionError::InvalidConfig)?;
+	merge(file, std::env::vars(), explicit).map_err(ConnectionError::InvalidConfig)
+}
+
+/// Like [`load`], but reading given configuration file instead of looking for one.
+#[allow(clippy::result_large_err)] // same error connecting with it would give
+pub fn load_file(path: impl AsRef<Path>, explicit: Config) -> ConnectionResult<Config> {
+	let path = path.as_ref();
+	let file = read(path).map_err(ConnectionError::InvalidConfig)?;
+	merge(Some((path.to_path_buf(), file)), std::env::vars(), explicit)
+		.map_err(ConnectionError::InvalidConfig)
+}
+
+/// Parse a configuration file, choosing the format from its extension.
+fn read(path: &Path) -> Result<Value, String> {
+	let content = std::fs::read_to_string(path)
+		.map_err(|e| format!("could not read {}: {e}", path.display()))?;
+	let parsed = match path.extension().and_then(|x| x.to_str()) {
+		Some("json") => serde_json::from_str(&content).map_err(|e| e.to_string()),
+		Some("toml") => toml::from_str(&content).map_err(|e| e.to_string()),
+		_ => Err("only .toml and .json files are supported".to_string()),
+	};
+	parsed.map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Merge file, environment and explicit values, describing the first invalid one.
+fn merge(
+	file: Option<(PathBuf, Value)>,
+	env: impl IntoIterator<Item = (String, String)>,
+	explicit: Config,
+) -> Result<Config, String> {
+	let mut merged = match file {
+		None => Value::Object(Map::new()),
+		Some((_, value @ Value::Object(_))) => value,
+		Some((path, _)) => return Err(format!("{} must hold a table", path.display())),
+	};
+	overlay(&mut merged, from_env(env)?);
+
+	let mut explicit = serde_json::to_value(explicit).map_err(|e| e.to_string())?;
+	if let Value::Object(fields) = &mut explicit {
+		for credential in ["username", "password"] {
+			if fields.get(credential).is_some_and(|x| x == "") {
+				fields.remove(credential);
+			}
+		}
+	}
+	overlay(&mut merged, explicit);
+
+	serde_json::from_value(merged).map_err(|e| format!("invalid configuration: {e}"))
+}
+
+/// Collect configuration values from `CODEMP_*` variables among given ones.
+fn from_env(env: impl IntoIterator<Item = (String, String)>) -> Result<Value, String> {
+	let mut values = Map::new();
+	for (name, raw) in env {
+		let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+			continue;
+		};
+		if name == CONFIG_ENV {
+			continue;
+		}
+		let key = key.to_lowercase();
+		let (section, field) = SECTIONS
+			.iter()
+			.find_map(|section| {
+				let field = key.strip_prefix(section)?.strip_prefix('_')?;
+				Some((Some(*section), field.to_string()))
+			})
+			.unwrap_or((None, key));
+
+		let invalid = |kind: &str| format!("{name} must be {kind}, found '{raw}'");
+		let value = match (section, field.as_str()) {
+			(None, "port") | (Some("tuning"), _) if field != "callback_strategy" => raw
+				.trim()
+				.parse::<u32>()
+				.map(Value::from)
+				.map_err(|_| invalid("a number"))?,
+			(None, "tls") | (Some("tls_settings"), "native_roots") => {
+				match raw.trim().to_lowercase().as_str() {
+					"true" | "1" | "yes" => Value::Bool(true),
+					"false" | "0" | "no" => Value::Bool(false),
+					_ => return Err(invalid("a boolean")),
+				}
+			}
+			_ => Value::String(raw),
+		};
+
+		match section {
+			None => values.insert(field, value),
+			Some(section) => values
+				.entry(section)
+				.or_insert_with(|| Value::Object(Map::new()))
+				.as_object_mut()
+				.expect("sections are always tables")
+				.insert(field, value),
+		};
+	}
+	Ok(Value::Object(values))
+}
+
+/// Recursively copy every set value of `top` over `base`.
+fn overlay(base: &mut Value, top: Value) {
+	match (base, top) {
+		(_, Value::Null) => {}
+		(Value::Object(base), Value::Object(top)) => {
+			for (key, value) in top {
+				overlay(base.entry(key).or_insert(Value::Null), value);
+			}
+		}
+		(base, top) => *base = top,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::merge;
+
+	fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+		vars.iter()
+			.map(|(k, v)| (k.to_string(), v.to_string()))
+			.collect()
+	}
+
+	#[test]
+	fn explicit_values_win_over_environment_and_file() {
+		let file: serde_json::Value = toml::from_str(
+			"username = \"file\"\nhost = \"file.example\"\nport = 1\n[tuning]\nbuffer_queue = 8\n",
+		)
+		.unwrap();
+		let config = merge(
+			Some(("config.toml".into(), file)),
+			env(&[
+				("CODEMP_HOST", "env.example"),
+				("CODEMP_TLS", "false"),
+				("CODEMP_TUNING_CONNECT_TIMEOUT_MS", "500"),
+				("HOME", "/home/alice"),
+			]),
+			crate::api::Config {
+				port: Some(3),
+				..Default::default()
+			},
+		)
+		.unwrap();
+		assert_eq!(config.username, "file");
+		assert_eq!(config.host.as_deref(), Some("env.example"));
+		assert_eq!(config.port, Some(3));
+		assert_eq!(config.tls, Some(false));
+		let tuning = config.tuning.unwrap();
+		assert_eq!(tuning.buffer_queue, Some(8));
+		assert_eq!(tuning.connect_timeout_ms, Some(500));
+	}
+
+	#[test]
+	fn malformed_environment_is_rejected() {
+		let res = merge(
+			None,
+			env(&[("CODEMP_PORT", "http")]),
+			crate::api::Config::default(),
+		);
+		assert_eq!(
+			res.unwrap_err(),
+			"CODEMP_PORT must be a number, found 'http'"
+		);
+	}
+}
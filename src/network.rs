@@ -1,18 +1,39 @@
+use std::{
+	future::Future,
+	time::{Duration, SystemTime},
+};
+
 use codemp_proto::{
-	buffer::buffer_client::BufferClient, common::Token, cursor::cursor_client::CursorClient,
+	auth::{auth_client::AuthClient, LoginRequest},
+	buffer::buffer_client::BufferClient,
+	common::{Token, User},
+	cursor::cursor_client::CursorClient,
+	session::session_client::SessionClient,
 	workspace::workspace_client::WorkspaceClient,
 };
+use tokio::sync::watch;
 use tonic::{
 	service::{interceptor::InterceptedService, Interceptor},
-	transport::{Channel, Endpoint},
+	transport::{Channel, Endpoint, Uri},
 };
 
-use crate::errors::ConnectionResult;
+use crate::{
+	api::Config,
+	auth::{Credentials, DynProvider},
+	errors::{ConnectionError, ConnectionResult, RemoteError, RemoteResult},
+	ext::InternallyMutable,
+	protocol::{
+		annotations::annotations_client::AnnotationsClient, chat::chat_client::ChatClient,
+		decorations::decorations_client::DecorationsClient, members::members_client::MembersClient,
+		metadata::metadata_client::MetadataClient, presence::presence_client::PresenceClient,
+	},
+};
 
-type AuthedService = InterceptedService<Channel, WorkspaceInterceptor>;
+pub type AuthedService = InterceptedService<Channel, WorkspaceInterceptor>;
+pub type SessionService = InterceptedService<Channel, SessionInterceptor>;
 
 #[derive(Debug, Clone)]
-pub struct SessionInterceptor(pub tokio::sync::watch::Receiver<codemp_proto::common::Token>);
+pub struct SessionInterceptor(pub watch::Receiver<Token>);
 impl tonic::service::Interceptor for SessionInterceptor {
 	fn call(&mut self, mut request: tonic::Request<()>) -> tonic::Result<tonic::Request<()>> {
 		if let Ok(token) = self.0.borrow().token.parse() {
@@ -23,29 +44,367 @@ impl tonic::service::Interceptor for SessionInterceptor {
 	}
 }
 
+/// Connection towards a server, shared by every service of a session.
+#[derive(Debug, Clone)]
+pub struct Link {
+	pub channel: Channel,
+	/// Scheme, authority and path prefix every request should be sent to.
+	pub origin: Uri,
+}
+
+/// Open a new channel towards configured endpoint, retrying as specified by its tuning.
+///
+/// Streams are opened with given transport if any, otherwise as described by the config.
+pub async fn connect(
+	config: &Config,
+	transport: Option<crate::transport::DynTransport>,
+) -> ConnectionResult<Link> {
+	let tuning = config.tuning();
+	let connector = crate::transport::Connector::new(config, transport)
+		.map_err(crate::errors::ConnectionError::InvalidConfig)?;
+
+	// TLS, proxies and other transports are handled by our own connector: tonic must believe
+	// this is plaintext TCP, while requests should still carry the real origin
+	let authority = match (connector.is_tcp(), config.host()) {
+		(false, _) => "localhost".to_string(),
+		(true, host) if host.contains(':') => format!("[{host}]:{}", config.port()),
+		(true, host) => format!("{host}:{}", config.port()),
+	};
+	let scheme = if connector.is_tls() { "https" } else { "http" };
+	let origin: Uri = format!("{scheme}://{authority}{}", config.path_prefix())
+		.parse()
+		.map_err(|e| ConnectionError::InvalidConfig(format!("invalid endpoint: {e}")))?;
+	let mut endpoint = Endpoint::from_shared(format!("http://{authority}"))?.origin(origin.clone());
+	if let Some(timeout) = tuning.connect_timeout() {
+		endpoint = endpoint.connect_timeout(timeout);
+	}
+	if let Some(timeout) = tuning.request_timeout() {
+		endpoint = endpoint.timeout(timeout);
+	}
+	if let Some(interval) = tuning.http2_keepalive() {
+		endpoint = endpoint
+			.http2_keep_alive_interval(interval)
+			.keep_alive_while_idle(true);
+	}
+	if let Some(timeout) = tuning.http2_keepalive_timeout() {
+		endpoint = endpoint.keep_alive_timeout(timeout);
+	}
+
+	let mut backoff = tuning.retry_backoff();
+	let mut attempt = 0;
+	loop {
+		match endpoint.connect_with_connector(connector.clone()).await {
+			Ok(channel) => break Ok(Link { channel, origin }),
+			Err(e) if attempt < tuning.connect_retries() => {
+				attempt += 1;
+				tracing::warn!("connection attempt {attempt} failed, retrying in {backoff:?}: {e}");
+				tokio::time::sleep(backoff).await;
+				backoff *= 2;
+			}
+			Err(e) => break Err(e.into()),
+		}
+	}
+}
+
+/// Obtain credentials from given provider and exchange them for a session token.
+///
+/// The authenticated user is only known when logging in with a password.
+pub async fn authenticate(
+	auth: &AuthClient<Channel>,
+	provider: &DynProvider,
+) -> ConnectionResult<(Token, Option<User>)> {
+	match provider().await? {
+		Credentials::Token(token) => Ok((Token { token }, None)),
+		Credentials::Password { username, password } => {
+			let resp = auth
+				.clone()
+				.login(LoginRequest { username, password })
+				.await?
+				.into_inner();
+			Ok((resp.token, Some(resp.user)))
+		}
+	}
+}
+
+/// Session credentials and connection, shared between a [`crate::Client`] and its workspaces.
+pub struct Session {
+	link: Link,
+	auth: AuthClient<Channel>,
+	client: SessionClient<SessionService>,
+	members: MembersClient<SessionService>,
+	presence: PresenceClient<SessionService>,
+	claims: InternallyMutable<Token>,
+	provider: DynProvider,
+	refreshing: tokio::sync::Mutex<()>,
+}
+
+impl std::fmt::Debug for Session {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Session")
+			.field("link", &self.link)
+			.field("auth", &self.auth)
+			.field("client", &self.client)
+			.field("members", &self.members)
+			.field("presence", &self.presence)
+			.field("claims", &self.claims)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Session {
+	pub fn new(link: Link, token: Token, provider: DynProvider) -> Self {
+		let claims = InternallyMutable::new(token);
+		let service =
+			InterceptedService::new(link.channel.clone(), SessionInterceptor(claims.channel()));
+		Self {
+			auth: AuthClient::with_origin(link.channel.clone(), link.origin.clone()),
+			client: SessionClient::with_origin(service.clone(), link.origin.clone()),
+			members: MembersClient::with_origin(service.clone(), link.origin.clone()),
+			presence: PresenceClient::with_origin(service, link.origin.clone()),
+			link,
+			claims,
+			provider,
+			refreshing: tokio::sync::Mutex::new(()),
+		}
+	}
+
+	/// Underlying connection, which every service of this session should share.
+	pub fn link(&self) -> Link {
+		self.link.clone()
+	}
+
+	pub fn claims(&self) -> watch::Receiver<Token> {
+		self.claims.channel()
+	}
+
+	/// Exchange current session token for a new one.
+	pub async fn refresh(&self) -> RemoteResult<()> {
+		self.refresh_from(self.claims.get()).await
+	}
+
+	/// Exchange given session token for a new one, unless it was already replaced.
+	///
+	/// If the server refuses to refresh it, authenticate again from scratch.
+	async fn refresh_from(&self, stale: Token) -> RemoteResult<()> {
+		let _guard = self.refreshing.lock().await;
+		if self.claims.get() != stale {
+			return Ok(()); // someone else refreshed it while we were waiting
+		}
+		let token = match self.auth.clone().refresh(stale).await {
+			Ok(res) => res.into_inner(),
+			Err(status) if status.code() == tonic::Code::Unauthenticated => {
+				tracing::info!(
+					"session token can't be refreshed, authenticating again: {}",
+					status.message()
+				);
+				match authenticate(&self.auth, &self.provider).await {
+					Ok((token, _user)) => token,
+					Err(ConnectionError::Remote(e)) => return Err(e),
+					Err(e) => {
+						return Err(RemoteError::from(tonic::Status::unauthenticated(
+							e.to_string(),
+						)))
+					}
+				}
+			}
+			Err(status) => return Err(status.into()),
+		};
+		self.claims.set(token);
+		Ok(())
+	}
+
+	/// Run a session request, refreshing the token and trying again once if it was rejected.
+	pub async fn call<T, F, Fut>(&self, f: F) -> RemoteResult<T>
+	where
+		F: Fn(SessionClient<SessionService>) -> Fut,
+		Fut: Future<Output = tonic::Result<tonic::Response<T>>>,
+	{
+		self.retry(&self.client, f).await
+	}
+
+	/// Like [`Session::call`], but for the [`crate::protocol::members`] extension.
+	pub async fn call_members<T, F, Fut>(&self, f: F) -> RemoteResult<T>
+	where
+		F: Fn(MembersClient<SessionService>) -> Fut,
+		Fut: Future<Output = tonic::Result<tonic::Response<T>>>,
+	{
+		self.retry(&self.members, f).await
+	}
+
+	/// Like [`Session::call`], but for the [`crate::protocol::presence`] extension.
+	pub async fn call_presence<T, F, Fut>(&self, f: F) -> RemoteResult<T>
+	where
+		F: Fn(PresenceClient<SessionService>) -> Fut,
+		Fut: Future<Output = tonic::Result<tonic::Response<T>>>,
+	{
+		self.retry(&self.presence, f).await
+	}
+
+	async fn retry<C, T, F, Fut>(&self, client: &C, f: F) -> RemoteResult<T>
+	where
+		C: Clone,
+		F: Fn(C) -> Fut,
+		Fut: Future<Output = tonic::Result<tonic::Response<T>>>,
+	{
+		let token = self.claims.get();
+		match f(client.clone()).await {
+			Err(status) if status.code() == tonic::Code::Unauthenticated => {
+				tracing::info!(
+					"session token rejected, refreshing it: {}",
+					status.message()
+				);
+				self.refresh_from(token).await?;
+				Ok(f(client.clone()).await?.into_inner())
+			}
+			res => Ok(res?.into_inner()),
+		}
+	}
+
+	/// Keep the session token fresh in background, for as long as this session is alive.
+	pub fn keep_fresh(self: &std::sync::Arc<Self>, margin: Duration, backoff: Duration) {
+		let weak = std::sync::Arc::downgrade(self);
+		keep_fresh(self.claims(), margin, backoff, move |stale| {
+			let session = weak.upgrade()?;
+			Some(async move { session.refresh_from(stale).await })
+		});
+	}
+}
+
+/// Try to find out when given token expires.
+///
+/// Tokens are opaque to clients, but if they look like a JWT carrying an `exp` claim, that is
+/// used as expiration time.
+pub fn expiry(token: &Token) -> Option<SystemTime> {
+	let exp = claims(token)?.get("exp")?.as_u64()?;
+	Some(SystemTime::UNIX_EPOCH + Duration::from_secs(exp))
+}
+
+/// Try to find out which user given token belongs to.
+///
+/// Like [`expiry`], this only works for JWT-shaped tokens carrying an UUID as `sub` claim. The
+/// user name is taken from the `name` claim, if present.
+pub fn identity(token: &Token) -> Option<(uuid::Uuid, Option<String>)> {
+	let claims = claims(token)?;
+	let id = claims.get("sub")?.as_str()?.parse().ok()?;
+	let name = claims
+		.get("name")
+		.and_then(|x| x.as_str())
+		.map(str::to_string);
+	Some((id, name))
+}
+
+/// Decode the payload of given token, if it looks like a JWT.
+fn claims(token: &Token) -> Option<serde_json::Value> {
+	use base64::Engine;
+	let mut parts = token.token.split('.');
+	let (Some(_header), Some(payload), Some(_signature), None) =
+		(parts.next(), parts.next(), parts.next(), parts.next())
+	else {
+		return None;
+	};
+	let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+		.decode(payload.trim_end_matches('='))
+		.ok()?;
+	serde_json::from_slice(&payload).ok()
+}
+
+/// Longest delay between attempts to renew a token, see [`keep_fresh`].
+const MAX_RENEWAL_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Spawn a task renewing the token in given channel some time before it expires.
+///
+/// The task stops once the channel is closed or `renew` returns `None`. Tokens with unknown
+/// expiration (see [`expiry`]) are left alone until replaced. Failed renewals are retried after
+/// `backoff`, doubled after each consecutive failure up to [`MAX_RENEWAL_BACKOFF`].
+///
+/// After a successful renewal, the next one waits for at least half the remaining lifetime of the
+/// new token, and never less than `backoff`: tokens living less than `margin`, or renewed without
+/// a later expiration, would be renewed again right away otherwise.
+pub fn keep_fresh<F, Fut>(
+	mut claims: watch::Receiver<Token>,
+	margin: Duration,
+	backoff: Duration,
+	renew: F,
+) where
+	F: Fn(Token) -> Option<Fut> + Send + 'static,
+	Fut: Future<Output = RemoteResult<()>> + Send,
+{
+	tokio::spawn(async move {
+		let mut delay = backoff;
+		let mut renewed: Option<std::time::Instant> = None;
+		loop {
+			let token = claims.borrow_and_update().clone();
+			let wait = expiry(&token).map(|exp| {
+				let left = exp.duration_since(SystemTime::now()).unwrap_or_default();
+				let wait = left.saturating_sub(margin);
+				match renewed {
+					Some(at) => (at + (left / 2).max(backoff))
+						.saturating_duration_since(std::time::Instant::now())
+						.max(wait),
+					None => wait,
+				}
+			});
+			let due = async {
+				match wait {
+					Some(wait) => tokio::time::sleep(wait).await,
+					None => std::future::pending().await,
+				}
+			};
+			tokio::select! {
+				res = claims.changed() => match res {
+					Ok(()) => continue, // replaced, start over with the new one
+					Err(_) => break, // owner is gone
+				},
+				() = due => {},
+			}
+			let Some(renewal) = renew(token) else { break };
+			match renewal.await {
+				Ok(()) => {
+					delay = backoff;
+					renewed = Some(std::time::Instant::now());
+				}
+				Err(e) => {
+					tracing::warn!("could not renew token, retrying in {delay:?}: {e}");
+					tokio::time::sleep(delay).await;
+					delay = std::cmp::min(delay * 2, MAX_RENEWAL_BACKOFF.max(backoff));
+				}
+			}
+		}
+		tracing::debug!("token renewal worker stopping");
+	});
+}
+
 #[derive(Debug)]
 pub struct Services {
 	workspace: WorkspaceClient<AuthedService>,
 	buffer: BufferClient<AuthedService>,
 	cursor: CursorClient<AuthedService>,
+	chat: ChatClient<AuthedService>,
+	annotations: AnnotationsClient<AuthedService>,
+	decorations: DecorationsClient<AuthedService>,
+	metadata: MetadataClient<AuthedService>,
 }
 
 impl Services {
-	pub async fn try_new(
-		dest: &str,
-		session: tokio::sync::watch::Receiver<codemp_proto::common::Token>,
-		workspace: tokio::sync::watch::Receiver<codemp_proto::common::Token>,
-	) -> ConnectionResult<Self> {
-		// TRANSPORT ERROR
-		let channel = Endpoint::from_shared(dest.to_string())?.connect().await?;
+	/// Build workspace services on top of given connection, authenticating with given tokens.
+	pub fn new(
+		link: Link,
+		session: watch::Receiver<Token>,
+		workspace: watch::Receiver<Token>,
+	) -> Self {
 		let inter = WorkspaceInterceptor { session, workspace };
-		Ok(Self {
-			cursor: CursorClient::with_interceptor(channel.clone(), inter.clone()),
-			workspace: WorkspaceClient::with_interceptor(channel.clone(), inter.clone()),
+		let service = InterceptedService::new(link.channel, inter);
+		Self {
+			cursor: CursorClient::with_origin(service.clone(), link.origin.clone()),
+			workspace: WorkspaceClient::with_origin(service.clone(), link.origin.clone()),
+			chat: ChatClient::with_origin(service.clone(), link.origin.clone()),
+			annotations: AnnotationsClient::with_origin(service.clone(), link.origin.clone()),
+			decorations: DecorationsClient::with_origin(service.clone(), link.origin.clone()),
+			metadata: MetadataClient::with_origin(service.clone(), link.origin.clone()),
 			// TODO technically we could keep buffers on separate servers, and thus manage buffer
 			// connections separately, but for now it's more convenient to bundle them with workspace
-			buffer: BufferClient::with_interceptor(channel.clone(), inter.clone()),
-		})
+			buffer: BufferClient::with_origin(service, link.origin),
+		}
 	}
 
 	// TODO just make fields pub(crate) ?? idk
@@ -60,12 +419,28 @@ impl Services {
 	pub fn cur(&self) -> CursorClient<AuthedService> {
 		self.cursor.clone()
 	}
+
+	pub fn chat(&self) -> ChatClient<AuthedService> {
+		self.chat.clone()
+	}
+
+	pub fn annotations(&self) -> AnnotationsClient<AuthedService> {
+		self.annotations.clone()
+	}
+
+	pub fn decorations(&self) -> DecorationsClient<AuthedService> {
+		self.decorations.clone()
+	}
+
+	pub fn metadata(&self) -> MetadataClient<AuthedService> {
+		self.metadata.clone()
+	}
 }
 
 #[derive(Clone)]
 pub struct WorkspaceInterceptor {
-	session: tokio::sync::watch::Receiver<Token>,
-	workspace: tokio::sync::watch::Receiver<Token>,
+	session: watch::Receiver<Token>,
+	workspace: watch::Receiver<Token>,
 }
 
 impl Interceptor for WorkspaceInterceptor {
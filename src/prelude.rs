@@ -2,13 +2,21 @@
 //! All-in-one renamed imports with `use codemp::prelude::*`.
 
 pub use crate::api::{
-	AsyncReceiver as CodempAsyncReceiver, AsyncSender as CodempAsyncSender,
-	BufferUpdate as CodempBufferUpdate, Config as CodempConfig, Controller as CodempController,
-	Cursor as CodempCursor, Event as CodempEvent, Selection as CodempSelection,
-	TextChange as CodempTextChange, User as CodempUser,
+	Annotation as CodempAnnotation, AsyncReceiver as CodempAsyncReceiver,
+	AsyncSender as CodempAsyncSender, BufferMetadata as CodempBufferMetadata,
+	BufferUpdate as CodempBufferUpdate, CallbackStrategy as CodempCallbackStrategy,
+	Config as CodempConfig, Controller as CodempController, Cursor as CodempCursor,
+	Decoration as CodempDecoration, DecorationKind as CodempDecorationKind, Draft as CodempDraft,
+	Event as CodempEvent, Indentation as CodempIndentation, LineEnding as CodempLineEnding,
+	Member as CodempMember, Message as CodempMessage, Presence as CodempPresence,
+	Role as CodempRole, Selection as CodempSelection, Status as CodempStatus,
+	TextChange as CodempTextChange, Theme as CodempTheme, TlsSettings as CodempTlsSettings,
+	Tuning as CodempTuning, User as CodempUser,
 };
 
 pub use crate::{
-	buffer::Controller as CodempBufferController, client::Client as CodempClient,
-	cursor::Controller as CodempCursorController, workspace::Workspace as CodempWorkspace,
+	buffer::Controller as CodempBufferController, chat::Controller as CodempChatController,
+	client::Client as CodempClient, cursor::Controller as CodempCursorController,
+	decoration::Controller as CodempDecorationController,
+	workspace::Subscription as CodempSubscription, workspace::Workspace as CodempWorkspace,
 };
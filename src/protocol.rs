@@ -0,0 +1,90 @@
+//! ### Protocol
+//! Extensions to the codemp protocol which are not (yet) part of [`codemp_proto`], compiled from
+//! the definitions in `proto/`.
+//!
+//! Servers may not implement them: features built on top should degrade gracefully when they
+//! answer [`tonic::Code::Unimplemented`].
+
+/// workspace membership and roles
+pub mod members {
+	tonic::include_proto!("members");
+
+	impl Member {
+		pub fn uuid(&self) -> uuid::Uuid {
+			uuid::Uuid::from_u64_pair(self.id_hi, self.id_lo)
+		}
+	}
+}
+
+/// display names and avatars of users
+pub mod presence {
+	tonic::include_proto!("presence");
+
+	impl Profile {
+		pub fn uuid(&self) -> uuid::Uuid {
+			uuid::Uuid::from_u64_pair(self.id_hi, self.id_lo)
+		}
+	}
+}
+
+/// text messages between users of a workspace
+pub mod chat {
+	tonic::include_proto!("chat");
+
+	impl Message {
+		pub fn uuid(&self) -> uuid::Uuid {
+			uuid::Uuid::from_u64_pair(self.id_hi, self.id_lo)
+		}
+	}
+
+	impl From<crate::api::Selection> for Anchor {
+		fn from(value: crate::api::Selection) -> Self {
+			Self {
+				buffer: value.buffer,
+				start_row: value.start_row,
+				start_col: value.start_col,
+				end_row: value.end_row,
+				end_col: value.end_col,
+			}
+		}
+	}
+
+	impl From<Anchor> for crate::api::Selection {
+		fn from(value: Anchor) -> Self {
+			Self {
+				buffer: value.buffer,
+				start_row: value.start_row,
+				start_col: value.start_col,
+				end_row: value.end_row,
+				end_col: value.end_col,
+			}
+		}
+	}
+}
+
+/// review comments anchored to buffer ranges
+pub mod annotations {
+	tonic::include_proto!("annotations");
+
+	impl Annotation {
+		pub fn author(&self) -> uuid::Uuid {
+			uuid::Uuid::from_u64_pair(self.author_hi, self.author_lo)
+		}
+	}
+}
+
+/// short-lived marks on buffer ranges, such as diagnostics
+pub mod decorations {
+	tonic::include_proto!("decorations");
+
+	impl Decoration {
+		pub fn author(&self) -> uuid::Uuid {
+			uuid::Uuid::from_u64_pair(self.author_hi(), self.author_lo())
+		}
+	}
+}
+
+/// properties of buffers every editor should agree on, such as line endings
+pub mod metadata {
+	tonic::include_proto!("metadata");
+}
@@ -0,0 +1,200 @@
+//! ### TLS
+//! Connector establishing TLS sessions as described by [`crate::api::TlsSettings`].
+//!
+//! tonic's `ClientTlsConfig` only takes trusted authorities, a client identity and a domain: it
+//! builds its own rustls configuration with the standard WebPKI verifier and offers no way to
+//! plug in another [`ServerCertVerifier`]. Checking pinned fingerprints needs one, so the rustls
+//! configuration is built here, the handshake done by this connector and the resulting stream
+//! handed to tonic as if it was plaintext, see [`crate::transport`].
+//!
+//! Pins are checked on top of WebPKI verification, not instead of it: a pinned certificate must
+//! still chain to a trusted authority, either a system one or one from `ca_bundle`.
+
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::{
+	client::TlsStream,
+	rustls::{
+		self,
+		client::{
+			danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+			WebPkiServerVerifier,
+		},
+		pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+		ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+	},
+	TlsConnector,
+};
+
+use crate::api::TlsSettings;
+
+const ALPN_H2: &[u8] = b"h2";
+
+/// Performs TLS handshakes on top of established connections.
+#[derive(Clone)]
+pub struct Connector {
+	config: Arc<ClientConfig>,
+	domain: ServerName<'static>,
+}
+
+impl std::fmt::Debug for Connector {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Connector")
+			.field("domain", &self.domain)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Connector {
+	/// Load certificates described by given settings, describing the first one which can't be used.
+	pub fn new(settings: &TlsSettings, host: &str) -> Result<Self, String> {
+		let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+		let mut roots = RootCertStore::empty();
+		if settings.native_roots() {
+			let native = rustls_native_certs::load_native_certs();
+			if !native.errors.is_empty() {
+				tracing::debug!("errors loading system certificates: {:?}", native.errors);
+			}
+			roots.add_parsable_certificates(native.certs);
+		}
+		if let Some(path) = &settings.ca_bundle {
+			for cert in load_certs("ca_bundle", path)? {
+				roots
+					.add(cert)
+					.map_err(|e| format!("tls_settings.ca_bundle is unusable: {e}"))?;
+			}
+		}
+		if roots.is_empty() {
+			return Err("no trusted certificate authorities found".to_string());
+		}
+
+		let verifier =
+			WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+				.build()
+				.map_err(|e| format!("could not build certificate verifier: {e}"))?;
+
+		let builder = ClientConfig::builder_with_provider(provider.clone())
+			.with_safe_default_protocol_versions()
+			.map_err(|e| e.to_string())?;
+		let builder = match settings.pinned_sha256() {
+			None => builder.with_webpki_verifier(verifier),
+			Some(pin) => builder
+				.dangerous()
+				.with_custom_certificate_verifier(Arc::new(Pinned {
+					inner: verifier,
+					fingerprint: pin?,
+				})),
+		};
+		let mut config = match (&settings.client_cert, &settings.client_key) {
+			(Some(cert), Some(key)) => builder
+				.with_client_auth_cert(load_certs("client_cert", cert)?, load_key(key)?)
+				.map_err(|e| format!("tls_settings.client_cert is unusable: {e}"))?,
+			_ => builder.with_no_client_auth(),
+		};
+		config.alpn_protocols.push(ALPN_H2.to_vec());
+
+		let domain = settings.domain.as_deref().unwrap_or(host).to_string();
+		let domain =
+			ServerName::try_from(domain).map_err(|e| format!("invalid server name: {e}"))?;
+
+		Ok(Self {
+			config: Arc::new(config),
+			domain,
+		})
+	}
+}
+
+impl Connector {
+	/// Establish a TLS session over given stream, requiring the server to speak HTTP/2.
+	pub async fn handshake<S>(&self, io: S) -> std::io::Result<TlsStream<S>>
+	where
+		S: AsyncRead + AsyncWrite + Unpin,
+	{
+		let tls = TlsConnector::from(self.config.clone())
+			.connect(self.domain.clone(), io)
+			.await?;
+		if tls.get_ref().1.alpn_protocol() != Some(ALPN_H2) {
+			return Err(std::io::Error::other("server did not negotiate HTTP/2"));
+		}
+		Ok(tls)
+	}
+}
+
+/// Verifies certificates as usual, then also requires the leaf to match a fingerprint: a pinned
+/// certificate which doesn't chain to a trusted authority is still refused.
+#[derive(Debug)]
+struct Pinned {
+	inner: Arc<WebPkiServerVerifier>,
+	fingerprint: Vec<u8>,
+}
+
+impl ServerCertVerifier for Pinned {
+	fn verify_server_cert(
+		&self,
+		end_entity: &CertificateDer<'_>,
+		intermediates: &[CertificateDer<'_>],
+		server_name: &ServerName<'_>,
+		ocsp_response: &[u8],
+		now: UnixTime,
+	) -> Result<ServerCertVerified, rustls::Error> {
+		let verified = self.inner.verify_server_cert(
+			end_entity,
+			intermediates,
+			server_name,
+			ocsp_response,
+			now,
+		)?;
+		let digest = ring::digest::digest(&ring::digest::SHA256, end_entity.as_ref());
+		if digest.as_ref() != self.fingerprint.as_slice() {
+			tracing::warn!("server certificate does not match pinned fingerprint");
+			return Err(rustls::Error::InvalidCertificate(
+				rustls::CertificateError::ApplicationVerificationFailure,
+			));
+		}
+		Ok(verified)
+	}
+
+	fn verify_tls12_signature(
+		&self,
+		message: &[u8],
+		cert: &CertificateDer<'_>,
+		dss: &DigitallySignedStruct,
+	) -> Result<HandshakeSignatureValid, rustls::Error> {
+		self.inner.verify_tls12_signature(message, cert, dss)
+	}
+
+	fn verify_tls13_signature(
+		&self,
+		message: &[u8],
+		cert: &CertificateDer<'_>,
+		dss: &DigitallySignedStruct,
+	) -> Result<HandshakeSignatureValid, rustls::Error> {
+		self.inner.verify_tls13_signature(message, cert, dss)
+	}
+
+	fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+		self.inner.supported_verify_schemes()
+	}
+}
+
+fn load_certs(field: &str, path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+	let pem =
+		std::fs::read(path).map_err(|e| format!("could not read tls_settings.{field}: {e}"))?;
+	let certs = rustls_pemfile::certs(&mut pem.as_slice())
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|e| format!("tls_settings.{field} is not valid PEM: {e}"))?;
+	if certs.is_empty() {
+		return Err(format!("tls_settings.{field} holds no certificates"));
+	}
+	Ok(certs)
+}
+
+fn load_key(path: &str) -> Result<PrivateKeyDer<'static>, String> {
+	let pem =
+		std::fs::read(path).map_err(|e| format!("could not read tls_settings.client_key: {e}"))?;
+	rustls_pemfile::private_key(&mut pem.as_slice())
+		.map_err(|e| format!("tls_settings.client_key is not valid PEM: {e}"))?
+		.ok_or_else(|| "tls_settings.client_key holds no private key".to_string())
+}
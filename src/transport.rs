@@ -0,0 +1,177 @@
+//! ### Transport
+//! Connector opening the byte streams tonic speaks HTTP/2 over: TCP connections (possibly through
+//! a proxy), Unix domain sockets or any custom [`Transport`], optionally wrapped in TLS.
+
+use std::{
+	future::Future,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::Duration,
+};
+
+use hyper_util::rt::TokioIo;
+use tokio::{
+	io::{AsyncRead, AsyncWrite},
+	net::TcpStream,
+};
+use tonic::transport::Uri;
+
+use crate::{api::Config, proxy::Proxy};
+
+/// Any bidirectional byte stream.
+pub trait Io: AsyncRead + AsyncWrite + Send + Unpin {}
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Io for T {}
+
+/// Something able to open byte streams towards a codemp server, to connect over anything else
+/// than TCP or Unix sockets (for example an in-process [`tokio::io::DuplexStream`]).
+///
+/// Pass it to [`crate::Client::connect_with_transport`]. A new stream is opened every time the
+/// client (re)connects, and TLS is still layered on top if explicitly enabled in the config.
+pub trait Transport: Send + Sync {
+	/// Stream type produced by this transport.
+	type Stream: Io + 'static;
+
+	/// Open a new stream towards the server.
+	fn open(&self) -> impl Future<Output = std::io::Result<Self::Stream>> + Send;
+}
+
+/// Type-erased [`Transport`], shared by every connection attempt.
+pub(crate) type DynTransport = Arc<
+	dyn Fn() -> Pin<Box<dyn Future<Output = std::io::Result<Box<dyn Io>>> + Send>> + Send + Sync,
+>;
+
+pub(crate) fn erase<T: Transport + 'static>(transport: T) -> DynTransport {
+	let transport = Arc::new(transport);
+	Arc::new(move || {
+		let transport = transport.clone();
+		Box::pin(async move {
+			let stream: Box<dyn Io> = Box::new(transport.open().await?);
+			Ok(stream)
+		})
+	})
+}
+
+/// Where byte streams are opened towards.
+#[derive(Clone)]
+enum Dial {
+	Tcp {
+		proxy: Option<Proxy>,
+		keepalive: Option<Duration>,
+	},
+	#[cfg(unix)]
+	Unix(std::path::PathBuf),
+	Custom(DynTransport),
+}
+
+impl std::fmt::Debug for Dial {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Tcp { proxy, keepalive } => f
+				.debug_struct("Tcp")
+				.field("proxy", proxy)
+				.field("keepalive", keepalive)
+				.finish(),
+			#[cfg(unix)]
+			Self::Unix(path) => f.debug_tuple("Unix").field(path).finish(),
+			Self::Custom(_) => f.write_str("Custom"),
+		}
+	}
+}
+
+/// Opens connections towards given uris as described by a [`Config`].
+#[derive(Debug, Clone)]
+pub(crate) struct Connector {
+	tls: Option<crate::tls::Connector>,
+	dial: Dial,
+}
+
+impl Connector {
+	/// Prepare a connector for given configuration, describing the first problem found.
+	///
+	/// When a custom transport is given, TLS is only used if explicitly enabled.
+	pub fn new(config: &Config, custom: Option<DynTransport>) -> Result<Self, String> {
+		let tls = match custom.is_some() {
+			true => config.tls.unwrap_or(false),
+			false => config.tls(),
+		};
+		let dial = match (custom, config.unix_socket()) {
+			(Some(transport), _) => Dial::Custom(transport),
+			#[cfg(unix)]
+			(None, Some(path)) => Dial::Unix(path.into()),
+			#[cfg(not(unix))]
+			(None, Some(_)) => return Err("unix sockets are not supported on this platform".into()),
+			(None, None) => Dial::Tcp {
+				proxy: config.proxy.as_deref().map(Proxy::parse).transpose()?,
+				keepalive: config.tuning().tcp_keepalive(),
+			},
+		};
+		// without a host to verify, certificates must be issued for `tls_settings.domain`
+		let host = match dial {
+			Dial::Tcp { .. } => config.host(),
+			_ => "localhost",
+		};
+		let tls = match tls {
+			true => Some(crate::tls::Connector::new(&config.tls_settings(), host)?),
+			false => None,
+		};
+		Ok(Self { tls, dial })
+	}
+
+	/// Whether streams opened by this connector are wrapped in TLS.
+	pub fn is_tls(&self) -> bool {
+		self.tls.is_some()
+	}
+
+	/// Whether this connector reaches the server over TCP, addressed by host and port.
+	pub fn is_tcp(&self) -> bool {
+		matches!(self.dial, Dial::Tcp { .. })
+	}
+
+	async fn open(self, uri: Uri) -> std::io::Result<Box<dyn Io>> {
+		let stream: Box<dyn Io> = match &self.dial {
+			Dial::Tcp { proxy, keepalive } => {
+				let host = uri
+					.host()
+					.ok_or_else(|| std::io::Error::other("endpoint has no host"))?
+					.trim_start_matches('[')
+					.trim_end_matches(']');
+				let port = uri.port_u16().unwrap_or(80);
+
+				let tcp = match proxy {
+					Some(proxy) => proxy.tunnel(host, port).await?,
+					None => TcpStream::connect((host, port)).await?,
+				};
+				tcp.set_nodelay(true)?;
+				if let Some(idle) = keepalive {
+					socket2::SockRef::from(&tcp)
+						.set_tcp_keepalive(&socket2::TcpKeepalive::new().with_time(*idle))?;
+				}
+				Box::new(tcp)
+			}
+			#[cfg(unix)]
+			Dial::Unix(path) => Box::new(tokio::net::UnixStream::connect(path).await?),
+			Dial::Custom(transport) => transport().await?,
+		};
+
+		match &self.tls {
+			Some(tls) => Ok(Box::new(tls.handshake(stream).await?)),
+			None => Ok(stream),
+		}
+	}
+}
+
+impl tower_service::Service<Uri> for Connector {
+	type Response = TokioIo<Box<dyn Io>>;
+	type Error = std::io::Error;
+	type Future = Pin<Box<dyn Future<Output = std::io::Result<Self::Response>> + Send>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, uri: Uri) -> Self::Future {
+		let connector = self.clone();
+		Box::pin(async move { Ok(TokioIo::new(connector.open(uri).await?)) })
+	}
+}
@@ -6,18 +6,27 @@
 use crate::{
 	api::{
 		controller::{AsyncReceiver, ControllerCallback},
-		Event, User,
+		presence::Tracked,
+		BufferMetadata, Event, Member, Presence, Role, User,
+	},
+	buffer, chat, cursor, decoration,
+	dispatch::{Dispatcher, Strategy},
+	errors::{ConnectionError, ConnectionResult, ControllerError, ControllerResult, RemoteResult},
+	ext::{ControllerStream, InternallyMutable},
+	network::{self, AuthedService, Services},
+	protocol::{
+		members::MembersRequest,
+		metadata::{metadata_client::MetadataClient, BufferPath},
+		presence::ProfilesRequest,
 	},
-	buffer, cursor,
-	errors::{ConnectionResult, ControllerResult, RemoteResult},
-	ext::InternallyMutable,
-	network::Services,
 };
 
 use codemp_proto::{
 	common::{Empty, Token},
 	files::BufferNode,
+	session::WorkspaceRequest,
 	workspace::{
+		workspace_client::WorkspaceClient,
 		workspace_event::{
 			Event as WorkspaceEventInner, FileCreate, FileDelete, FileRename, UserJoin, UserLeave,
 		},
@@ -26,8 +35,15 @@ use codemp_proto::{
 };
 
 use dashmap::{DashMap, DashSet};
-use std::sync::Arc;
-use tokio::sync::{mpsc, mpsc::error::TryRecvError};
+use std::{
+	collections::VecDeque,
+	future::Future,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Weak,
+	},
+};
+use tokio::sync::{mpsc, watch, Notify};
 use tonic::Streaming;
 use uuid::Uuid;
 
@@ -39,7 +55,7 @@ use napi_derive::napi;
 /// Workspaces encapsulate a working environment: cursor positions, filetree, user list
 /// and more. Each holds a [cursor::Controller] and a map of [buffer::Controller]s.
 /// Using a workspace handle, it's possible to receive events (user join/leave, filetree updates)
-/// and create/delete/attach to new buffers.
+/// and create/delete/attach to new buffers. It also tracks the [Presence] of every user in it.
 #[derive(Debug, Clone)]
 #[cfg_attr(any(feature = "py", feature = "py-noabi"), pyo3::pyclass)]
 #[cfg_attr(feature = "js", napi)]
@@ -49,44 +65,41 @@ pub struct Workspace(Arc<WorkspaceInner>);
 struct WorkspaceInner {
 	name: String,
 	user: User, // TODO back-reference to global user id... needed for buffer controllers
+	config: crate::api::Config,
 	cursor: cursor::Controller,
 	buffers: DashMap<String, buffer::Controller>,
+	decorations: DashMap<String, decoration::Controller>,
+	chat: std::sync::Mutex<Option<chat::Controller>>,
+	/// Held while attaching the chat, so that concurrent attempts share the same controller.
+	chat_attaching: tokio::sync::Mutex<()>,
 	services: Services,
+	session: Arc<network::Session>,
+	claims: InternallyMutable<Token>,
+	role: InternallyMutable<Role>,
 	// TODO these two are Arced so that the inner worker can hold them without holding the
 	//      WorkspaceInner itself, otherwise its impossible to drop Workspace
 	filetree: DashSet<String>,
-	users: Arc<DashMap<Uuid, User>>,
-	// TODO can we drop the mutex?
-	events: tokio::sync::Mutex<mpsc::UnboundedReceiver<crate::api::Event>>,
-	callback: std::sync::Mutex<Option<ControllerCallback<Workspace>>>, // TODO lmao another one
+	users: Arc<DashMap<Uuid, Tracked>>,
+	hub: Arc<EventHub>,
+	events: Arc<EventQueue>,
+	callback: watch::Sender<Option<ControllerCallback<Workspace>>>,
 }
 
 impl AsyncReceiver<Event> for Workspace {
 	async fn try_recv(&self) -> ControllerResult<Option<Event>> {
-		match self.0.events.lock().await.try_recv() {
-			Ok(x) => Ok(Some(x)),
-			Err(TryRecvError::Empty) => Ok(None),
-			Err(TryRecvError::Disconnected) => Err(crate::errors::ControllerError::Stopped),
-		}
+		self.0.events.try_pop()
 	}
 
 	async fn poll(&self) -> ControllerResult<()> {
-		loop {
-			if !self.0.events.lock().await.is_empty() {
-				break Ok(());
-			}
-			// TODO disgusting, please send help
-			tokio::time::sleep(std::time::Duration::from_millis(200)).await;
-		}
+		self.0.events.wait().await
 	}
 
-	// TODO please send HELP ASAP this is hurting me emotionally
 	fn clear_callback(&self) {
-		*self.0.callback.lock().expect("mutex poisoned") = None;
+		self.0.callback.send_replace(None);
 	}
 
 	fn callback(&self, cb: impl Into<ControllerCallback<Self>>) {
-		*self.0.callback.lock().expect("mutex poisoned") = Some(cb.into());
+		self.0.callback.send_replace(Some(cb.into()));
 	}
 }
 
@@ -95,16 +108,16 @@ impl Workspace {
 		name: String,
 		user: User,
 		config: crate::api::Config,
-		token: Token,
-		claims: tokio::sync::watch::Receiver<codemp_proto::common::Token>, // TODO ughh receiving this
+		session: Arc<network::Session>,
 	) -> ConnectionResult<Self> {
-		let workspace_claim = InternallyMutable::new(token);
-		let services =
-			Services::try_new(&config.endpoint(), claims, workspace_claim.channel()).await?;
+		let claims = InternallyMutable::new(access(&session, &name).await?);
+		let services = Services::new(session.link(), session.claims(), claims.channel());
 		let ws_stream = services.ws().attach(Empty {}).await?.into_inner();
 
-		let (tx, rx) = mpsc::channel(128);
-		let (ev_tx, ev_rx) = mpsc::unbounded_channel();
+		let tuning = config.tuning();
+		let (tx, rx) = mpsc::channel(tuning.cursor_queue());
+		let hub = Arc::new(EventHub::new(tuning.callback_strategy()));
+		let events = hub.subscribe();
 		let cur_stream = services
 			.cur()
 			.attach(tokio_stream::wrappers::ReceiverStream::new(rx))
@@ -112,44 +125,149 @@ impl Workspace {
 			.into_inner();
 
 		let users = Arc::new(DashMap::default());
+		let (activity_tx, activity_rx) = mpsc::unbounded_channel();
 
-		let controller = cursor::Controller::spawn(users.clone(), tx, cur_stream);
+		let controller = cursor::Controller::spawn(
+			users.clone(),
+			activity_tx,
+			tuning.callback_strategy(),
+			tx,
+			cur_stream,
+		);
 
 		let ws = Self(Arc::new(WorkspaceInner {
 			name,
 			user,
+			config,
 			cursor: controller,
 			buffers: DashMap::default(),
+			decorations: DashMap::default(),
+			chat: std::sync::Mutex::default(),
+			chat_attaching: tokio::sync::Mutex::default(),
 			filetree: DashSet::default(),
 			users,
-			events: tokio::sync::Mutex::new(ev_rx),
+			hub,
+			events,
 			services,
-			callback: std::sync::Mutex::new(None),
+			session,
+			claims,
+			role: InternallyMutable::default(),
+			callback: watch::channel(None).0,
 		}));
 
+		ws.fetch_role().await?;
 		ws.fetch_users().await?;
+		ws.fetch_profiles().await?;
 		ws.fetch_buffers().await?;
-		ws.run_actor(ws_stream, ev_tx);
+		ws.run_actor(ws_stream, activity_rx);
+		ws.keep_fresh();
 
 		Ok(ws)
 	}
 
+	/// Create a new independent [`Subscription`] to this workspace's events.
+	///
+	/// Every subscription receives all events happening after its creation, regardless of
+	/// other subscribers and of events consumed through the [`Workspace`] itself.
+	///
+	/// This may be called from any thread, even outside of an async runtime.
+	pub fn subscribe(&self) -> Subscription {
+		Subscription(self.0.hub.subscribe())
+	}
+
+	/// Turn this workspace handle into a [`tokio_stream::Stream`] of [`Event`]s.
+	///
+	/// Events are consumed from the same queue as [`AsyncReceiver::recv`] on this handle; use
+	/// [`Subscription::into_stream`] to get an independent stream. The stream ends once the
+	/// workspace worker stops, see [`ControllerStream`].
+	pub fn into_stream(self) -> ControllerStream<Event> {
+		ControllerStream::new(move || {
+			let workspace = self.clone();
+			Box::pin(async move { workspace.recv().await })
+		})
+	}
+
 	/// drop arc, return true if was last
 	pub(crate) fn consume(self) -> bool {
 		Arc::into_inner(self.0).is_some()
 	}
 
+	/// Run a workspace request, renewing the access token and trying again once if it was rejected.
+	async fn call<T, F, Fut>(&self, f: F) -> RemoteResult<T>
+	where
+		F: Fn(WorkspaceClient<AuthedService>) -> Fut,
+		Fut: Future<Output = tonic::Result<tonic::Response<T>>>,
+	{
+		match f(self.0.services.ws()).await {
+			Err(status) if status.code() == tonic::Code::Unauthenticated => {
+				tracing::info!(
+					"workspace token rejected, renewing it: {}",
+					status.message()
+				);
+				self.renew().await?;
+				Ok(f(self.0.services.ws()).await?.into_inner())
+			}
+			res => Ok(res?.into_inner()),
+		}
+	}
+
+	/// Like [`Workspace::call`], but for the [`crate::protocol::metadata`] extension.
+	async fn call_metadata<T, F, Fut>(&self, f: F) -> RemoteResult<T>
+	where
+		F: Fn(MetadataClient<AuthedService>) -> Fut,
+		Fut: Future<Output = tonic::Result<tonic::Response<T>>>,
+	{
+		match f(self.0.services.metadata()).await {
+			Err(status) if status.code() == tonic::Code::Unauthenticated => {
+				tracing::info!(
+					"workspace token rejected, renewing it: {}",
+					status.message()
+				);
+				self.renew().await?;
+				Ok(f(self.0.services.metadata()).await?.into_inner())
+			}
+			res => Ok(res?.into_inner()),
+		}
+	}
+
+	/// Request a new access token for this workspace.
+	async fn renew(&self) -> RemoteResult<()> {
+		let token = access(&self.0.session, &self.0.name).await?;
+		self.0.claims.set(token);
+		Ok(())
+	}
+
+	/// Keep the workspace access token fresh in background, for as long as the workspace is alive.
+	fn keep_fresh(&self) {
+		let tuning = self.0.config.tuning();
+		let weak = Arc::downgrade(&self.0);
+		network::keep_fresh(
+			self.0.claims.channel(),
+			tuning.refresh_margin(),
+			tuning.retry_backoff(),
+			move |stale| {
+				let ws = Workspace(weak.upgrade()?);
+				Some(async move {
+					if ws.0.claims.get() != stale {
+						return Ok(()); // already renewed
+					}
+					ws.renew().await
+				})
+			},
+		);
+	}
+
 	/// Create a new buffer in the current workspace.
 	pub async fn create_buffer(&self, path: &str) -> RemoteResult<()> {
-		let mut workspace_client = self.0.services.ws();
-		workspace_client
-			.create_buffer(tonic::Request::new(BufferNode {
-				path: path.to_string(),
-			}))
-			.await?;
+		let path = path.to_string();
+		self.call(|mut ws| {
+			let path = path.clone();
+			async move { ws.create_buffer(BufferNode { path }).await }
+		})
+		.await?;
 
 		// add to filetree
-		self.0.filetree.insert(path.to_string());
+		self.0.filetree.insert(path);
 
 		// fetch buffers
 		self.fetch_buffers().await?;
@@ -158,32 +276,213 @@ impl Workspace {
 	}
 
 	/// Attach to a buffer and return a handle to it.
+	///
+	/// A new buffer access token is requested on every attach, renewing the workspace access token
+	/// once if the server rejects it.
 	pub async fn attach_buffer(&self, path: &str) -> ConnectionResult<buffer::Controller> {
-		let mut worskspace_client = self.0.services.ws();
-		let request = tonic::Request::new(BufferNode {
-			path: path.to_string(),
-		});
-		let credentials = worskspace_client.access_buffer(request).await?.into_inner();
-
-		let (tx, rx) = mpsc::channel(256);
-		let mut req = tonic::Request::new(tokio_stream::wrappers::ReceiverStream::new(rx));
-		req.metadata_mut().insert(
-			"buffer",
-			tonic::metadata::MetadataValue::try_from(credentials.token).map_err(|e| {
-				tonic::Status::internal(format!("failed representing token to string: {e}"))
-			})?,
-		);
-		let stream = self.0.services.buf().attach(req).await?.into_inner();
+		self.attach(path, false).await
+	}
+
+	/// Attach to a buffer as a spectator and return a handle to it.
+	///
+	/// The returned controller follows the buffer like any other, but never edits it: no CRDT
+	/// agent is allocated for the current user and [`buffer::Controller::send`] always fails with
+	/// [`crate::errors::ControllerError::ReadOnly`], regardless of the user's [`Role`].
+	pub async fn attach_buffer_read_only(
+		&self,
+		path: &str,
+	) -> ConnectionResult<buffer::Controller> {
+		self.attach(path, true).await
+	}
+
+	async fn attach(&self, path: &str, read_only: bool) -> ConnectionResult<buffer::Controller> {
+		let mut renewed = false;
+		let (tx, stream, token) = loop {
+			let credentials = self
+				.call(|mut ws| {
+					let path = path.to_string();
+					async move { ws.access_buffer(BufferNode { path }).await }
+				})
+				.await?;
+
+			let token =
+				tonic::metadata::MetadataValue::try_from(credentials.token).map_err(|e| {
+					tonic::Status::internal(format!("failed representing token to string: {e}"))
+				})?;
+			let (tx, rx) = mpsc::channel(self.0.config.tuning().buffer_queue());
+			let mut req = tonic::Request::new(tokio_stream::wrappers::ReceiverStream::new(rx));
+			req.metadata_mut().insert("buffer", token.clone());
+			match self.0.services.buf().attach(req).await {
+				Err(status) if status.code() == tonic::Code::Unauthenticated && !renewed => {
+					tracing::info!("buffer access rejected, renewing: {}", status.message());
+					self.renew().await?;
+					renewed = true;
+				}
+				res => break (tx, res?.into_inner(), token),
+			}
+		};
+
+		// annotations are typed by hand, a short queue is plenty
+		let (annotation_tx, annotation_rx) = mpsc::channel(16);
+		let mut req =
+			tonic::Request::new(tokio_stream::wrappers::ReceiverStream::new(annotation_rx));
+		req.metadata_mut().insert("buffer", token.clone());
+		let annotations = match self.0.services.annotations().attach(req).await {
+			Err(status) if status.code() == tonic::Code::Unimplemented => {
+				tracing::info!("server does not support annotations on buffer {path}");
+				None
+			}
+			res => Some(buffer::worker::AnnotationLink {
+				users: self.0.users.clone(),
+				tx: annotation_tx,
+				rx: res?.into_inner(),
+			}),
+		};
+
+		let mut req = tonic::Request::new(crate::protocol::metadata::Empty {});
+		req.metadata_mut().insert("buffer", token);
+		let metadata = match self.0.services.metadata().attach(req).await {
+			Err(status) if status.code() == tonic::Code::Unimplemented => {
+				tracing::info!("server does not support metadata of buffer {path}");
+				None
+			}
+			res => Some(res?.into_inner()),
+		};
 
-		let controller = buffer::Controller::spawn(self.0.user.id, path, tx, stream);
+		let controller = buffer::Controller::spawn(
+			(!read_only).then_some(self.0.user.id),
+			path,
+			self.0.config.tuning().hash_period(),
+			self.0.config.tuning().callback_strategy(),
+			self.0.role.channel(),
+			tx,
+			stream,
+			annotations,
+			metadata,
+		);
 		self.0.buffers.insert(path.to_string(), controller.clone());
 
 		Ok(controller)
 	}
 
+	/// Attach to the chat of this workspace and return a handle to it.
+	///
+	/// The chat is only attached once: later calls return the same [`chat::Controller`], unless
+	/// its worker stopped because the server closed the chat, in which case it's attached again.
+	/// Servers without chat support reject the attempt with [`tonic::Code::Unimplemented`].
+	pub async fn attach_chat(&self) -> ConnectionResult<chat::Controller> {
+		let _attaching = self.0.chat_attaching.lock().await;
+		if let Some(controller) = self.get_chat() {
+			return Ok(controller);
+		}
+		let mut renewed = false;
+		let (tx, stream) = loop {
+			// messages are typed by hand, a short queue is plenty
+			let (tx, rx) = mpsc::channel(16);
+			let req = tokio_stream::wrappers::ReceiverStream::new(rx);
+			match self.0.services.chat().attach(req).await {
+				Err(status) if status.code() == tonic::Code::Unauthenticated && !renewed => {
+					tracing::info!("chat access rejected, renewing: {}", status.message());
+					self.renew().await?;
+					renewed = true;
+				}
+				res => break (tx, res?.into_inner()),
+			}
+		};
+		let tuning = self.0.config.tuning();
+		let controller = chat::Controller::spawn(
+			self.0.users.clone(),
+			tuning.chat_history(),
+			tuning.callback_strategy(),
+			tx,
+			stream,
+		);
+		*self.0.chat.lock().expect("mutex poisoned") = Some(controller.clone());
+		Ok(controller)
+	}
+
+	/// Return a handle to the [`chat::Controller`], if the chat was attached and is still running.
+	pub fn get_chat(&self) -> Option<chat::Controller> {
+		self.0
+			.chat
+			.lock()
+			.expect("mutex poisoned")
+			.clone()
+			.filter(|controller| !controller.is_stopped())
+	}
+
+	/// Attach to the decorations of a buffer and return a handle to them.
+	///
+	/// The buffer is attached first if needed, since decorations are anchored to its content.
+	/// Decorations are only attached once per buffer: later calls return the same
+	/// [`decoration::Controller`] until the buffer is detached. Servers without decoration
+	/// support reject the attempt with [`tonic::Code::Unimplemented`].
+	pub async fn attach_decorations(&self, path: &str) -> ConnectionResult<decoration::Controller> {
+		if let Some(controller) = self.get_decorations(path) {
+			return Ok(controller);
+		}
+		let buffer = match self.get_buffer(path) {
+			Some(buffer) => buffer,
+			None => self.attach_buffer(path).await?,
+		};
+
+		let mut renewed = false;
+		let (tx, stream) = loop {
+			let credentials = self
+				.call(|mut ws| {
+					let path = path.to_string();
+					async move { ws.access_buffer(BufferNode { path }).await }
+				})
+				.await?;
+
+			let token =
+				tonic::metadata::MetadataValue::try_from(credentials.token).map_err(|e| {
+					tonic::Status::internal(format!("failed representing token to string: {e}"))
+				})?;
+			// decorations come in bursts, e.g. a round of diagnostics
+			let (tx, rx) = mpsc::channel(64);
+			let mut req = tonic::Request::new(tokio_stream::wrappers::ReceiverStream::new(rx));
+			req.metadata_mut().insert("buffer", token);
+			match self.0.services.decorations().attach(req).await {
+				Err(status) if status.code() == tonic::Code::Unauthenticated && !renewed => {
+					tracing::info!(
+						"decorations access rejected, renewing: {}",
+						status.message()
+					);
+					self.renew().await?;
+					renewed = true;
+				}
+				res => break (tx, res?.into_inner()),
+			}
+		};
+
+		let controller = self
+			.0
+			.decorations
+			.entry(path.to_string())
+			.or_insert_with(|| {
+				decoration::Controller::spawn(
+					buffer,
+					self.0.users.clone(),
+					self.0.config.tuning().callback_strategy(),
+					tx,
+					stream,
+				)
+			})
+			.clone();
+
+		Ok(controller)
+	}
+
+	/// Return a handle to the [`decoration::Controller`] of a buffer, if attached.
+	pub fn get_decorations(&self, path: &str) -> Option<decoration::Controller> {
+		self.0.decorations.get(path).map(|x| x.clone())
+	}
+
 	/// Detach from an active buffer.
 	///
-	/// This will stop and drop its [`buffer::Controller`].
+	/// This will stop and drop its [`buffer::Controller`], along with its
+	/// [`decoration::Controller`] if any.
 	///
 	/// Returns `true` if connectly dropped or wasn't present, `false` if dropped but wasn't last ref
 	///
@@ -191,6 +490,7 @@ impl Workspace {
 	/// collection or maybe preventing the controller from being dropped completely
 	#[allow(clippy::redundant_pattern_matching)] // all cases are clearer this way
 	pub fn detach_buffer(&self, path: &str) -> bool {
+		let _ = self.0.decorations.remove(path);
 		match self.0.buffers.remove(path) {
 			None => true, // noop: we werent attached in the first place
 			Some((_name, controller)) => match Arc::into_inner(controller.0) {
@@ -202,11 +502,9 @@ impl Workspace {
 
 	/// Re-fetch the list of available buffers in the workspace.
 	pub async fn fetch_buffers(&self) -> RemoteResult<Vec<String>> {
-		let mut workspace_client = self.0.services.ws();
-		let resp = workspace_client
-			.list_buffers(tonic::Request::new(Empty {}))
-			.await?
-			.into_inner();
+		let resp = self
+			.call(|mut ws| async move { ws.list_buffers(Empty {}).await })
+			.await?;
 
 		let mut out = Vec::new();
 
@@ -221,35 +519,71 @@ impl Workspace {
 
 	/// Re-fetch the list of all users in the workspace.
 	pub async fn fetch_users(&self) -> RemoteResult<Vec<User>> {
-		let mut workspace_client = self.0.services.ws();
-		let users = workspace_client
-			.list_users(tonic::Request::new(Empty {}))
+		let users = self
+			.call(|mut ws| async move { ws.list_users(Empty {}).await })
 			.await?
-			.into_inner()
 			.users
 			.into_iter()
 			.map(User::from);
 
 		let mut result = Vec::new();
 
-		self.0.users.clear();
 		for u in users {
-			self.0.users.insert(u.id, u.clone());
+			self.0
+				.users
+				.entry(u.id)
+				.and_modify(|tracked| tracked.user = u.clone())
+				.or_insert_with(|| Tracked::new(u.clone()));
 			result.push(u);
 		}
+		// keep what we know about users still here, forget the others
+		self.0
+			.users
+			.retain(|id, _| result.iter().any(|u| u.id == *id));
 
 		Ok(result)
 	}
 
+	/// Re-fetch display names and avatars of users in the workspace.
+	///
+	/// Servers without profile support leave them all unset.
+	async fn fetch_profiles(&self) -> RemoteResult<()> {
+		let res = self
+			.0
+			.session
+			.call_presence(|mut presence| {
+				let workspace = self.0.name.clone();
+				async move { presence.list_profiles(ProfilesRequest { workspace }).await }
+			})
+			.await;
+		let profiles = match res {
+			Ok(list) => list.profiles,
+			Err(e) if e.code() == tonic::Code::Unimplemented => return Ok(()),
+			Err(e) => return Err(e),
+		};
+		for profile in profiles {
+			if let Some(mut tracked) = self.0.users.get_mut(&profile.uuid()) {
+				tracked.set_profile(profile);
+			}
+		}
+		Ok(())
+	}
+
+	/// Re-fetch users in the workspace and their profiles, returning their updated [`Presence`].
+	pub async fn fetch_presence(&self) -> RemoteResult<Vec<Presence>> {
+		self.fetch_users().await?;
+		self.fetch_profiles().await?;
+		Ok(self.presence())
+	}
+
 	/// Fetch a list of the [User]s attached to a specific buffer.
 	pub async fn fetch_buffer_users(&self, path: &str) -> RemoteResult<Vec<User>> {
-		let mut workspace_client = self.0.services.ws();
-		let buffer_users = workspace_client
-			.list_buffer_users(tonic::Request::new(BufferNode {
-				path: path.to_string(),
-			}))
+		let buffer_users = self
+			.call(|mut ws| {
+				let path = path.to_string();
+				async move { ws.list_buffer_users(BufferNode { path }).await }
+			})
 			.await?
-			.into_inner()
 			.users
 			.into_iter()
 			.map(|id| id.into())
@@ -258,16 +592,80 @@ impl Workspace {
 		Ok(buffer_users)
 	}
 
+	/// Fetch all members of this workspace with their roles, including pending invites.
+	pub async fn fetch_members(&self) -> RemoteResult<Vec<Member>> {
+		let members = self
+			.0
+			.session
+			.call_members(|mut members| {
+				let workspace = self.0.name.clone();
+				async move { members.list_members(MembersRequest { workspace }).await }
+			})
+			.await?;
+		Ok(members.members.into_iter().map(Member::from).collect())
+	}
+
+	/// Re-fetch the [`Role`] of the current user in this workspace, which decides whether
+	/// attached buffers accept changes.
+	///
+	/// Servers without membership support grant everyone [`Role::ReadWrite`].
+	pub async fn fetch_role(&self) -> RemoteResult<Role> {
+		let res = self
+			.0
+			.session
+			.call_members(|mut members| {
+				let workspace = self.0.name.clone();
+				async move { members.get_membership(MembersRequest { workspace }).await }
+			})
+			.await;
+		let role = match res {
+			Ok(membership) => Role::from(membership.role()),
+			Err(e) if e.code() == tonic::Code::Unimplemented => Role::ReadWrite,
+			Err(e) => return Err(e),
+		};
+		self.0.role.set(role);
+		Ok(role)
+	}
+
+	/// Fetch the [`BufferMetadata`] of a buffer, with every field unset if it was never set.
+	///
+	/// Servers without metadata support reject the request with [`tonic::Code::Unimplemented`].
+	pub async fn fetch_buffer_metadata(&self, path: &str) -> RemoteResult<BufferMetadata> {
+		let metadata = self
+			.call_metadata(|mut metadata| {
+				let path = path.to_string();
+				async move { metadata.get(BufferPath { path }).await }
+			})
+			.await?;
+		Ok(BufferMetadata::from(metadata))
+	}
+
+	/// Replace the [`BufferMetadata`] of a buffer, announcing it to every user attached to it.
+	///
+	/// Unset fields are cleared, so fetch the current metadata first to change only some.
+	/// Servers without metadata support reject the request with [`tonic::Code::Unimplemented`].
+	pub async fn set_buffer_metadata(
+		&self,
+		path: &str,
+		metadata: BufferMetadata,
+	) -> RemoteResult<()> {
+		self.call_metadata(|mut client| {
+			let metadata = metadata.clone().into_proto(path);
+			async move { client.set(metadata).await }
+		})
+		.await?;
+		Ok(())
+	}
+
 	/// Delete a buffer.
 	pub async fn delete_buffer(&self, path: &str) -> RemoteResult<()> {
 		self.detach_buffer(path); // just in case
 
-		let mut workspace_client = self.0.services.ws();
-		workspace_client
-			.delete_buffer(tonic::Request::new(BufferNode {
-				path: path.to_string(),
-			}))
-			.await?;
+		self.call(|mut ws| {
+			let path = path.to_string();
+			async move { ws.delete_buffer(BufferNode { path }).await }
+		})
+		.await?;
 
 		self.0.filetree.remove(path);
 
@@ -280,6 +678,11 @@ impl Workspace {
 		self.0.name.clone()
 	}
 
+	/// Get the [`Role`] of the current user in this workspace, as last fetched.
+	pub fn role(&self) -> Role {
+		self.0.role.get()
+	}
+
 	/// Return a handle to the [`cursor::Controller`].
 	// #[cfg_attr(feature = "js", napi)] // https://github.com/napi-rs/napi-rs/issues/1120
 	pub fn cursor(&self) -> cursor::Controller {
@@ -307,10 +710,30 @@ impl Workspace {
 		self.0
 			.users
 			.iter()
-			.map(|elem| elem.value().clone())
+			.map(|elem| elem.value().user.clone())
 			.collect()
 	}
 
+	/// Get the [`Presence`] of all users currently in this workspace, as last known.
+	///
+	/// Buffers and statuses follow the cursor movements of each user, while display names and
+	/// avatars are only updated by [`Workspace::fetch_presence`]. Changes are notified with
+	/// [`Event::PresenceChanged`].
+	pub fn presence(&self) -> Vec<Presence> {
+		let tuning = self.0.config.tuning();
+		let (idle, away) = (tuning.idle_after(), tuning.away_after());
+		self.0
+			.users
+			.iter()
+			.map(|elem| elem.value().presence(idle, away))
+			.collect()
+	}
+
+	/// Get the [`Presence`] of the user with given name, if they are in this workspace.
+	pub fn user_presence(&self, name: &str) -> Option<Presence> {
+		self.presence().into_iter().find(|p| p.user.name == name)
+	}
+
 	/// Get the filetree as it is currently cached.
 	/// A filter may be applied, and it may be strict (equality check) or not (starts_with check).
 	// #[cfg_attr(feature = "js", napi)] // https://github.com/napi-rs/napi-rs/issues/1120
@@ -329,24 +752,51 @@ impl Workspace {
 	pub(crate) fn run_actor(
 		&self,
 		mut stream: Streaming<WorkspaceEvent>,
-		tx: mpsc::UnboundedSender<crate::api::Event>,
+		mut activity: mpsc::UnboundedReceiver<Uuid>,
 	) {
 		// TODO for buffer and cursor controller we invoke the tokio::spawn outside, but here inside..?
 		let weak = Arc::downgrade(&self.0);
+		let hub = self.0.hub.clone();
+		let callback = Dispatcher::new(
+			self.0.config.tuning().callback_strategy(),
+			self.0.callback.subscribe(),
+		);
 		let name = self.id();
+		let tuning = self.0.config.tuning();
+		let tick = tuning.worker_tick();
+		let (idle, away) = (tuning.idle_after(), tuning.away_after());
 		tokio::spawn(async move {
 			tracing::debug!("workspace worker starting");
+			let presence_changed = |inner: &Arc<WorkspaceInner>, name: String| {
+				hub.dispatch(Event::PresenceChanged { name });
+				callback.dispatch(Workspace(inner.clone()));
+			};
 			loop {
 				// TODO can we stop responsively rather than poll for Arc being dropped?
-				if weak.upgrade().is_none() {
-					break;
-				};
-				let Some(res) = tokio::select!(
-					x = stream.message() => Some(x),
-					_ = tokio::time::sleep(std::time::Duration::from_secs(5)) => None,
-				) else {
-					continue;
-				};
+				let Some(inner) = weak.upgrade() else { break };
+				// report users who became idle or away since last time we checked
+				let faded = inner
+					.users
+					.iter_mut()
+					.filter_map(|mut t| t.refresh(idle, away).then(|| t.user.name.clone()))
+					.collect::<Vec<_>>();
+				for name in faded {
+					presence_changed(&inner, name);
+				}
+				drop(inner); // don't keep the workspace alive while waiting
+
+				let res = tokio::select!(
+					x = stream.message() => x,
+					Some(id) = activity.recv() => {
+						let Some(inner) = weak.upgrade() else { break };
+						let name = inner.users.get(&id).map(|t| t.user.name.clone());
+						if let Some(name) = name {
+							presence_changed(&inner, name);
+						}
+						continue;
+					},
+					_ = tokio::time::sleep(tick) => continue,
+				);
 				match res {
 					Err(e) => break tracing::error!("workspace '{}' stream closed: {}", name, e),
 					Ok(None) => break tracing::info!("leaving workspace {}", name),
@@ -359,7 +809,8 @@ impl Workspace {
 						match ev {
 							// user
 							WorkspaceEventInner::Join(UserJoin { user }) => {
-								inner.users.insert(user.id.uuid(), user.into());
+								let user = User::from(user);
+								inner.users.insert(user.id, Tracked::new(user));
 							}
 							WorkspaceEventInner::Leave(UserLeave { user }) => {
 								inner.users.remove(&user.id.uuid());
@@ -374,16 +825,233 @@ impl Workspace {
 							}
 							WorkspaceEventInner::Delete(FileDelete { path }) => {
 								inner.filetree.remove(&path);
+								let _ = inner.decorations.remove(&path);
 								let _ = inner.buffers.remove(&path);
 							}
 						}
-						if tx.send(update).is_err() {
-							tracing::warn!("no active controller to receive workspace event");
-						}
+						hub.dispatch(update);
+						callback.dispatch(Workspace(inner.clone()));
 					}
 				}
 			}
+			hub.close();
 			tracing::debug!("workspace worker stopping");
 		});
 	}
 }
+
+/// Request an access token for the workspace with given name.
+async fn access(session: &network::Session, name: &str) -> RemoteResult<Token> {
+	session
+		.call(|mut session| {
+			let workspace = name.to_string();
+			async move {
+				session
+					.access_workspace(WorkspaceRequest { workspace })
+					.await
+			}
+		})
+		.await
+}
+
+/// An independent stream of [`Event`]s happening in a [`Workspace`].
+///
+/// Obtained with [`Workspace::subscribe`]; each subscription holds its own queue, so events
+/// consumed here won't be missed by other subscribers. Once the workspace worker stops, pending
+/// events can still be received, after which [`ControllerError::Stopped`] is returned.
+#[derive(Debug, Clone)]
+pub struct Subscription(Arc<EventQueue>);
+
+impl Subscription {
+	/// Turn this subscription into a [`tokio_stream::Stream`] of [`Event`]s.
+	///
+	/// The stream ends once the workspace worker stops, see [`ControllerStream`].
+	pub fn into_stream(self) -> ControllerStream<Event> {
+		ControllerStream::new(move || {
+			let subscription = self.clone();
+			Box::pin(async move { subscription.recv().await })
+		})
+	}
+}
+
+impl AsyncReceiver<Event> for Subscription {
+	async fn try_recv(&self) -> ControllerResult<Option<Event>> {
+		self.0.try_pop()
+	}
+
+	async fn poll(&self) -> ControllerResult<()> {
+		self.0.wait().await
+	}
+
+	fn clear_callback(&self) {
+		self.0.callback.send_replace(None);
+	}
+
+	fn callback(&self, cb: impl Into<ControllerCallback<Self>>) {
+		self.0.callback.send_replace(Some(cb.into()));
+	}
+}
+
+/// Fans out workspace events to every live [`EventQueue`].
+#[derive(Debug)]
+struct EventHub {
+	subscribers: std::sync::Mutex<Vec<Weak<EventQueue>>>,
+	closed: AtomicBool,
+	callbacks: Strategy,
+	/// Runtime the hub was created on, running the callbacks of every subscriber.
+	runtime: tokio::runtime::Handle,
+}
+
+impl EventHub {
+	/// Create a new hub, must be called from within a tokio runtime.
+	fn new(callbacks: Strategy) -> Self {
+		Self {
+			subscribers: std::sync::Mutex::default(),
+			closed: AtomicBool::new(false),
+			callbacks,
+			runtime: tokio::runtime::Handle::current(),
+		}
+	}
+
+	fn subscribe(&self) -> Arc<EventQueue> {
+		// subscribers may come from any thread, but dispatchers spawn their tasks on the runtime
+		let _runtime = self.runtime.enter();
+		let queue = Arc::new(EventQueue::new(self.callbacks));
+		let mut subscribers = self.subscribers.lock().expect("mutex poisoned");
+		if self.closed.load(Ordering::Acquire) {
+			queue.close();
+		} else {
+			subscribers.push(Arc::downgrade(&queue));
+		}
+		queue
+	}
+
+	fn dispatch(&self, event: Event) {
+		let mut queues = Vec::new();
+		self.subscribers
+			.lock()
+			.expect("mutex poisoned")
+			.retain(|sub| match sub.upgrade() {
+				Some(queue) => {
+					queues.push(queue);
+					true
+				}
+				None => false, // subscription was dropped
+			});
+		// callbacks may subscribe again, so don't run them while holding the lock
+		for queue in queues {
+			queue.push(event.clone());
+			queue.dispatcher.dispatch(Subscription(queue.clone()));
+		}
+	}
+
+	fn close(&self) {
+		let mut subscribers = self.subscribers.lock().expect("mutex poisoned");
+		self.closed.store(true, Ordering::Release);
+		for queue in subscribers.drain(..).filter_map(|sub| sub.upgrade()) {
+			queue.close();
+		}
+	}
+}
+
+/// A single subscriber's event queue, woken as soon as something is pushed.
+#[derive(Debug)]
+struct EventQueue {
+	events: std::sync::Mutex<VecDeque<Event>>,
+	notify: Notify,
+	closed: AtomicBool,
+	callback: watch::Sender<Option<ControllerCallback<Subscription>>>,
+	dispatcher: Dispatcher<Subscription>,
+}
+
+impl EventQueue {
+	fn new(callbacks: Strategy) -> Self {
+		let (callback, rx) = watch::channel(None);
+		Self {
+			events: std::sync::Mutex::default(),
+			notify: Notify::new(),
+			closed: AtomicBool::new(false),
+			callback,
+			dispatcher: Dispatcher::new(callbacks, rx),
+		}
+	}
+
+	fn push(&self, event: Event) {
+		self.events.lock().expect("mutex poisoned").push_back(event);
+		self.notify.notify_waiters();
+	}
+
+	fn close(&self) {
+		self.closed.store(true, Ordering::Release);
+		self.notify.notify_waiters();
+	}
+
+	fn try_pop(&self) -> ControllerResult<Option<Event>> {
+		match self.events.lock().expect("mutex poisoned").pop_front() {
+			Some(event) => Ok(Some(event)),
+			None if self.closed.load(Ordering::Acquire) => Err(ControllerError::Stopped),
+			None => Ok(None),
+		}
+	}
+
+	async fn wait(&self) -> ControllerResult<()> {
+		loop {
+			// register interest before checking, so that a push in between can't be missed
+			let notified = self.notify.notified();
+			tokio::pin!(notified);
+			notified.as_mut().enable();
+			if !self.events.lock().expect("mutex poisoned").is_empty() {
+				return Ok(());
+			}
+			if self.closed.load(Ordering::Acquire) {
+				return Err(ControllerError::Stopped);
+			}
+			notified.await;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{EventHub, Strategy, Subscription};
+	use crate::api::{controller::AsyncReceiver, Event};
+
+	#[tokio::test]
+	async fn every_subscriber_receives_all_events() {
+		let hub = EventHub::new(Strategy::Inline);
+		let first = Subscription(hub.subscribe());
+		let second = Subscription(hub.subscribe());
+		hub.dispatch(Event::FileTreeUpdated {
+			path: "a.txt".into(),
+		});
+		hub.dispatch(Event::UserJoin {
+			name: "alice".into(),
+		});
+		for sub in [&first, &second] {
+			assert!(matches!(
+				sub.try_recv().await,
+				Ok(Some(Event::FileTreeUpdated { .. }))
+			));
+			assert!(matches!(
+				sub.try_recv().await,
+				Ok(Some(Event::UserJoin { .. }))
+			));
+			assert!(matches!(sub.try_recv().await, Ok(None)));
+		}
+	}
+
+	#[tokio::test]
+	async fn poll_wakes_on_dispatch_and_stops_on_close() {
+		let hub = std::sync::Arc::new(EventHub::new(Strategy::Inline));
+		let sub = Subscription(hub.subscribe());
+		let _hub = hub.clone();
+		let task = tokio::spawn(async move {
+			_hub.dispatch(Event::UserLeave { name: "bob".into() });
+			_hub.close();
+		});
+		assert!(matches!(sub.recv().await, Ok(Event::UserLeave { .. })));
+		task.await.expect("dispatcher task panicked");
+		assert!(sub.poll().await.is_err());
+		assert!(Subscription(hub.subscribe()).try_recv().await.is_err());
+	}
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use std::time::Duration;
+
+use codemp::api::{
+	controller::{AsyncReceiver, AsyncSender},
+	Annotation, Event, TextChange,
+};
+use common::{attach, fired, notifier, MockServer, TIMEOUT};
+
+async fn annotations(buffer: &codemp::buffer::Controller) -> Vec<Annotation> {
+	buffer
+		.annotations()
+		.await
+		.expect("could not list annotations")
+}
+
+#[tokio::test]
+async fn annotations_follow_edits() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let buffer = workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("could not attach to buffer");
+	let (cb, mut rx) = notifier();
+	buffer.annotation_callback(cb);
+
+	buffer
+		.send(TextChange {
+			start_idx: 0,
+			end_idx: 0,
+			content: "hello world".into(),
+		})
+		.expect("could not send change");
+	let id = buffer
+		.annotate(6, 11, "rename this")
+		.expect("could not annotate");
+	fired(&mut rx, "annotation change").await;
+	assert_eq!(
+		annotations(&buffer).await,
+		[Annotation {
+			id: id.clone(),
+			author: "alice".into(),
+			text: "rename this".into(),
+			start_idx: 6,
+			end_idx: 11,
+			resolved: false,
+		}]
+	);
+
+	// text inserted right before the range stays outside of it
+	buffer
+		.send(TextChange {
+			start_idx: 6,
+			end_idx: 6,
+			content: "big ".into(),
+		})
+		.expect("could not send change");
+	let moved = annotations(&buffer).await;
+	assert_eq!((moved[0].start_idx, moved[0].end_idx), (10, 15));
+
+	buffer
+		.send(TextChange {
+			start_idx: 0,
+			end_idx: 6,
+			content: String::new(),
+		})
+		.expect("could not send change");
+	let moved = annotations(&buffer).await;
+	assert_eq!((moved[0].start_idx, moved[0].end_idx), (4, 9));
+
+	buffer.resolve_annotation(&id).expect("could not resolve");
+	fired(&mut rx, "annotation change").await;
+	assert!(annotations(&buffer).await[0].resolved);
+
+	buffer.delete_annotation(&id).expect("could not delete");
+	fired(&mut rx, "annotation change").await;
+	assert!(annotations(&buffer).await.is_empty());
+}
+
+#[tokio::test]
+async fn annotations_of_others_are_delivered() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	server.join("bob").await;
+	let joined = tokio::time::timeout(TIMEOUT, workspace.recv()).await;
+	assert!(matches!(joined, Ok(Ok(Event::UserJoin { .. }))));
+
+	// known annotations are delivered upon attaching
+	server.annotate("bob", "main.rs", "first", "welcome").await;
+	let buffer = workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("could not attach to buffer");
+	let known = tokio::time::timeout(TIMEOUT, async {
+		loop {
+			let known = annotations(&buffer).await;
+			if !known.is_empty() {
+				break known;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("timed out waiting for known annotations");
+	assert_eq!(known[0].id, "first");
+	assert_eq!(known[0].author, "bob");
+
+	let (cb, mut rx) = notifier();
+	buffer.annotation_callback(cb);
+	server
+		.annotate("bob", "main.rs", "second", "looks good")
+		.await;
+	server.annotate("bob", "lib.rs", "other", "not here").await;
+	fired(&mut rx, "annotation change").await;
+	let known = annotations(&buffer).await;
+	assert_eq!(known.len(), 2);
+	assert_eq!(known[1].text, "looks good");
+	assert_eq!((known[1].start_idx, known[1].end_idx), (0, 0));
+
+	buffer.clear_annotation_callback();
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use codemp::api::{
+	controller::{AsyncReceiver, AsyncSender},
+	Draft, Event, Message, Selection,
+};
+use common::{attach, attach_as, MockServer, TIMEOUT};
+
+async fn next(chat: &codemp::chat::Controller) -> Message {
+	tokio::time::timeout(TIMEOUT, chat.recv())
+		.await
+		.expect("timed out waiting for message")
+		.expect("chat stopped")
+}
+
+#[tokio::test]
+async fn messages_are_relayed_with_their_threads() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	assert!(workspace.get_chat().is_none());
+
+	let chat = workspace
+		.attach_chat()
+		.await
+		.expect("could not attach chat");
+	assert!(workspace.get_chat().is_some());
+
+	chat.send(Draft::new("hello"))
+		.expect("could not send message");
+	let hello = next(&chat).await;
+	assert_eq!(hello.user, "alice");
+	assert_eq!(hello.text, "hello");
+	assert!(hello.thread.is_none());
+	assert!(hello.timestamp > 0);
+
+	let range = Selection {
+		buffer: "main.rs".into(),
+		start_row: 2,
+		start_col: 0,
+		end_row: 4,
+		end_col: 1,
+	};
+	chat.send(Draft::in_thread("why this?", range))
+		.expect("could not send message");
+	let question = next(&chat).await;
+	let thread = question.thread.expect("message lost its thread");
+	assert_eq!(thread.buffer, "main.rs");
+	assert_eq!((thread.start_row, thread.end_row), (2, 4));
+
+	server.join("bob").await;
+	let joined = tokio::time::timeout(TIMEOUT, workspace.recv()).await;
+	assert!(matches!(joined, Ok(Ok(Event::UserJoin { .. }))));
+	server.say("bob", "because", Some("main.rs")).await;
+	let answer = next(&chat).await;
+	assert_eq!(answer.user, "bob");
+	assert!(answer.is_about("main.rs"));
+
+	assert!(matches!(chat.try_recv().await, Ok(None)));
+	let texts = |messages: Vec<Message>| messages.into_iter().map(|m| m.text).collect::<Vec<_>>();
+	assert_eq!(texts(chat.history()), ["hello", "why this?", "because"]);
+	assert_eq!(
+		texts(chat.thread_history("main.rs")),
+		["why this?", "because"]
+	);
+	assert!(chat.thread_history("lib.rs").is_empty());
+}
+
+#[tokio::test]
+async fn history_only_keeps_latest_messages() {
+	let server = MockServer::start().await;
+	let mut config = server.config();
+	config.tuning = Some(codemp::api::Tuning {
+		chat_history: Some(2),
+		..Default::default()
+	});
+	let (_client, workspace) = attach_as(&server, config).await;
+	let chat = workspace
+		.attach_chat()
+		.await
+		.expect("could not attach chat");
+
+	for text in ["one", "two", "three"] {
+		server.say("alice", text, None).await;
+		assert_eq!(next(&chat).await.text, text);
+	}
+	let history = chat.history();
+	assert_eq!(history.len(), 2);
+	assert_eq!(history[0].text, "two");
+	assert_eq!(history[1].text, "three");
+}
+
+#[tokio::test]
+async fn stream_ends_when_server_closes_chat() {
+	use tokio_stream::StreamExt;
+
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let chat = workspace
+		.attach_chat()
+		.await
+		.expect("could not attach chat");
+
+	server.say("bob", "bye", None).await;
+	assert_eq!(next(&chat).await.text, "bye");
+	server.close_chats();
+
+	let rest: Vec<_> = tokio::time::timeout(TIMEOUT, chat.into_stream().collect::<Vec<_>>())
+		.await
+		.expect("stream did not end");
+	assert!(rest.iter().all(Result::is_err));
+
+	assert!(workspace.get_chat().is_none());
+	let chat = workspace
+		.attach_chat()
+		.await
+		.expect("could not attach chat again");
+	server.say("bob", "back", None).await;
+	assert_eq!(next(&chat).await.text, "back");
+}
@@ -0,0 +1,1372 @@
+//! A minimal in-process stand-in for a codemp server, good enough to drive a [`codemp::Client`]
+//! through login and workspace attachment while letting tests script server-side events.
+
+#![allow(dead_code)] // not every test binary uses every helper
+
+use std::{
+	net::SocketAddr,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant, SystemTime},
+};
+
+use codemp::protocol::{
+	annotations::{
+		annotations_server::{Annotations, AnnotationsServer},
+		change::Change as AnnotationChange,
+		event::Event as AnnotationEvent,
+		Anchor as AnnotationAnchor, Annotation, Change, Event as AnnotationUpdate, RemoteVersion,
+	},
+	chat::{
+		chat_server::{Chat, ChatServer},
+		Anchor, Draft, Message,
+	},
+	decorations::{
+		decorations_server::{Decorations, DecorationsServer},
+		Decoration, Kind,
+	},
+	members::{
+		members_server::{Members, MembersServer},
+		Empty as Done, Member, MemberList, MemberRequest, MembersRequest, Role, RoleRequest,
+	},
+	metadata::{
+		metadata_server::{Metadata, MetadataServer},
+		BufferMetadata, BufferPath, Empty as NoMetadata,
+	},
+	presence::{
+		presence_server::{Presence, PresenceServer},
+		Empty as Updated, Profile, ProfileList, ProfileUpdate, ProfilesRequest,
+	},
+};
+use codemp_proto::{
+	auth::{
+		auth_server::{Auth, AuthServer},
+		LoginRequest, LoginResponse,
+	},
+	buffer::{
+		buffer_server::{Buffer, BufferServer},
+		BufferEvent, Operation,
+	},
+	common::{Empty, Identity, Token, User},
+	cursor::{
+		cursor_server::{Cursor, CursorServer},
+		CursorEvent, CursorPosition, RowCol,
+	},
+	files::{BufferNode, BufferTree},
+	session::{
+		session_server::{Session, SessionServer},
+		InviteRequest, WorkspaceList, WorkspaceRequest,
+	},
+	workspace::{
+		workspace_event::{Event, FileCreate, FileDelete, FileRename, UserJoin, UserLeave},
+		workspace_server::{Workspace, WorkspaceServer},
+		UserList, WorkspaceEvent,
+	},
+};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Handle to a running stand-in server.
+pub struct MockServer {
+	host: String,
+	port: Option<u16>,
+	state: Arc<State>,
+	shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+#[derive(Default)]
+struct State {
+	/// Senders for every workspace event stream currently attached.
+	attached: Mutex<Vec<mpsc::Sender<Result<WorkspaceEvent, Status>>>>,
+	/// Notified whenever a client attaches to the workspace event stream.
+	attach_notify: tokio::sync::Notify,
+	/// Session token of every authenticated request received, in order.
+	seen_tokens: Mutex<Vec<String>>,
+	/// If set, issued tokens are JWT-shaped and expire after this long.
+	token_lifetime: Mutex<Option<Duration>>,
+	/// How many tokens were issued so far, used to make each one unique.
+	issued: AtomicUsize,
+	/// Last session token issued upon login.
+	login_token: Mutex<Option<String>>,
+	/// How many times a session token was refreshed.
+	refreshes: AtomicUsize,
+	/// When every request to refresh a session token was received, even refused ones.
+	refresh_attempts: Mutex<Vec<Instant>>,
+	/// How many workspace access tokens were granted.
+	accesses: AtomicUsize,
+	/// How many connections were accepted.
+	connections: AtomicUsize,
+	/// Reject this many upcoming authenticated requests as unauthenticated.
+	reject: AtomicUsize,
+	/// Refuse to refresh session tokens, as if they were revoked.
+	refuse_refresh: AtomicBool,
+	/// How many times a user logged in.
+	logins: AtomicUsize,
+	/// Role and pending invite status of every workspace member, by name.
+	members: Mutex<std::collections::BTreeMap<String, (Role, bool)>>,
+	/// Senders for every cursor stream currently attached.
+	cursors: Mutex<Vec<mpsc::Sender<Result<CursorEvent, Status>>>>,
+	/// Display name and avatar of every user who set them, by name.
+	profiles: Mutex<std::collections::BTreeMap<String, ProfileUpdate>>,
+	/// Senders for every chat stream currently attached.
+	chats: Mutex<Vec<mpsc::Sender<Result<Message, Status>>>>,
+	/// Buffer token and sender for every annotation stream currently attached.
+	annotation_streams: Mutex<Vec<(String, mpsc::Sender<Result<AnnotationUpdate, Status>>)>>,
+	/// Buffer token and content of every annotation, in order of creation.
+	annotations: Mutex<Vec<(String, Annotation)>>,
+	/// Buffer token and sender for every decoration stream currently attached.
+	decoration_streams: Mutex<Vec<(String, mpsc::Sender<Result<Decoration, Status>>)>>,
+	/// Buffer token and content of every decoration received, in order.
+	decorations: Mutex<Vec<(String, Decoration)>>,
+	/// Buffer token and sender for every metadata stream currently attached.
+	metadata_streams: Mutex<Vec<(String, mpsc::Sender<Result<BufferMetadata, Status>>)>>,
+	/// Metadata of every buffer it was set for, by path.
+	metadata: Mutex<std::collections::BTreeMap<String, BufferMetadata>>,
+	/// Buffer token and sender for every buffer stream currently attached.
+	buffer_streams: Mutex<Vec<(String, mpsc::Sender<Result<BufferEvent, Status>>)>>,
+}
+
+impl State {
+	fn issue(&self, kind: &str) -> Token {
+		use base64::Engine;
+		let n = self.issued.fetch_add(1, Ordering::SeqCst);
+		let token = match *self.token_lifetime.lock().unwrap() {
+			None => format!("{kind}-{n}"),
+			Some(lifetime) => {
+				let exp = (SystemTime::now() + lifetime)
+					.duration_since(SystemTime::UNIX_EPOCH)
+					.unwrap()
+					.as_secs();
+				let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+				let header = b64.encode(r#"{"alg":"none"}"#);
+				let payload = b64.encode(format!(r#"{{"sub":"{kind}-{n}","exp":{exp}}}"#));
+				format!("{header}.{payload}.")
+			}
+		};
+		Token { token }
+	}
+}
+
+impl MockServer {
+	/// Start a stand-in server on a random local port.
+	pub async fn start() -> Self {
+		Self::start_with(tonic::transport::Server::builder(), None).await
+	}
+
+	/// Start a stand-in server on a random local port, serving under given path prefix as if
+	/// behind a reverse proxy.
+	pub async fn start_under(prefix: &'static str) -> Self {
+		Self::start_with(tonic::transport::Server::builder(), Some(prefix)).await
+	}
+
+	/// Start a stand-in server on a random local port, only accepting TLS connections.
+	pub async fn start_tls(tls: tonic::transport::ServerTlsConfig) -> Self {
+		let builder = tonic::transport::Server::builder()
+			.tls_config(tls)
+			.expect("invalid server tls configuration");
+		Self::start_with(builder, None).await
+	}
+
+	/// Start a stand-in server listening on a Unix domain socket at given path.
+	#[cfg(unix)]
+	pub async fn start_unix(path: &std::path::Path) -> Self {
+		let listener = tokio::net::UnixListener::bind(path).expect("could not bind unix socket");
+		let incoming = tokio_stream::wrappers::UnixListenerStream::new(listener);
+		Self::serve(
+			tonic::transport::Server::builder(),
+			None,
+			incoming,
+			format!("unix://{}", path.display()),
+			None,
+		)
+	}
+
+	/// Start a stand-in server only reachable in-process, through the returned transport.
+	pub fn start_in_process() -> (Self, InProcess) {
+		let (tx, rx) = mpsc::channel(16);
+		let incoming = ReceiverStream::new(rx).map(Ok::<_, std::io::Error>);
+		let server = Self::serve(
+			tonic::transport::Server::builder(),
+			None,
+			incoming,
+			"in-process".to_string(),
+			None,
+		);
+		(server, InProcess(tx))
+	}
+
+	async fn start_with(builder: tonic::transport::Server, prefix: Option<&'static str>) -> Self {
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+			.await
+			.expect("could not bind local listener");
+		let addr = listener.local_addr().expect("listener has no address");
+		let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener);
+		Self::serve(
+			builder,
+			prefix,
+			incoming,
+			addr.ip().to_string(),
+			Some(addr.port()),
+		)
+	}
+
+	fn serve<IO>(
+		builder: tonic::transport::Server,
+		prefix: Option<&'static str>,
+		incoming: impl Stream<Item = std::io::Result<IO>> + Send + 'static,
+		host: String,
+		port: Option<u16>,
+	) -> Self
+	where
+		IO: tokio::io::AsyncRead
+			+ tokio::io::AsyncWrite
+			+ tonic::transport::server::Connected
+			+ Unpin
+			+ Send
+			+ 'static,
+		IO::ConnectInfo: Clone + Send + Sync + 'static,
+	{
+		let state = Arc::new(State::default());
+		let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+		let service = Service(state.clone());
+		let counted = state.clone();
+		let mut builder = builder.layer(StripPrefix(prefix));
+		tokio::spawn(async move {
+			builder
+				.add_service(AuthServer::new(service.clone()))
+				.add_service(SessionServer::new(service.clone()))
+				.add_service(WorkspaceServer::new(service.clone()))
+				.add_service(CursorServer::new(service.clone()))
+				.add_service(BufferServer::new(service.clone()))
+				.add_service(MembersServer::new(service.clone()))
+				.add_service(PresenceServer::new(service.clone()))
+				.add_service(ChatServer::new(service.clone()))
+				.add_service(AnnotationsServer::new(service.clone()))
+				.add_service(DecorationsServer::new(service.clone()))
+				.add_service(MetadataServer::new(service))
+				.serve_with_incoming_shutdown(
+					incoming.map(move |conn| {
+						counted.connections.fetch_add(1, Ordering::SeqCst);
+						conn
+					}),
+					async {
+						rx.await.ok();
+					},
+				)
+				.await
+				.expect("stand-in server failed");
+		});
+		Self {
+			host,
+			port,
+			state,
+			shutdown: Some(tx),
+		}
+	}
+
+	/// A client configuration pointing to this server.
+	pub fn config(&self) -> codemp::api::Config {
+		codemp::api::Config {
+			host: Some(self.host.clone()),
+			port: self.port,
+			tls: Some(false),
+			..codemp::api::Config::new("alice", "password")
+		}
+	}
+
+	/// Wait until at least one client is attached to the workspace event stream.
+	pub async fn attached(&self) {
+		loop {
+			let notified = self.state.attach_notify.notified();
+			if !self.state.attached.lock().unwrap().is_empty() {
+				return;
+			}
+			notified.await;
+		}
+	}
+
+	/// Send a raw workspace event to every attached client.
+	pub async fn send_event(&self, event: Event) {
+		let attached = self.state.attached.lock().unwrap().clone();
+		for tx in attached {
+			let _ = tx
+				.send(Ok(WorkspaceEvent {
+					event: Some(event.clone()),
+				}))
+				.await;
+		}
+	}
+
+	/// Drop every workspace event stream, as if the server closed them.
+	pub fn close_events(&self) {
+		self.state.attached.lock().unwrap().clear();
+	}
+
+	pub async fn join(&self, name: &str) {
+		self.send_event(Event::Join(UserJoin { user: user(name) }))
+			.await
+	}
+
+	pub async fn leave(&self, name: &str) {
+		self.send_event(Event::Leave(UserLeave { user: user(name) }))
+			.await
+	}
+
+	pub async fn create(&self, path: &str) {
+		self.send_event(Event::Create(FileCreate {
+			path: path.to_string(),
+		}))
+		.await
+	}
+
+	pub async fn rename(&self, before: &str, after: &str) {
+		self.send_event(Event::Rename(FileRename {
+			before: before.to_string(),
+			after: after.to_string(),
+		}))
+		.await
+	}
+
+	pub async fn delete(&self, path: &str) {
+		self.send_event(Event::Delete(FileDelete {
+			path: path.to_string(),
+		}))
+		.await
+	}
+
+	/// Make given user a member of the workspace with given role, as if they joined.
+	pub fn set_role(&self, name: &str, role: codemp::api::Role) {
+		let role = match role {
+			codemp::api::Role::ReadWrite => Role::ReadWrite,
+			codemp::api::Role::ReadOnly => Role::ReadOnly,
+			codemp::api::Role::Owner => Role::Owner,
+		};
+		self.state
+			.members
+			.lock()
+			.unwrap()
+			.insert(name.to_string(), (role, false));
+	}
+
+	/// Send a cursor movement of given user to every attached client.
+	pub async fn move_cursor(&self, name: &str, buffer: &str) {
+		let attached = self.state.cursors.lock().unwrap().clone();
+		let position = CursorPosition {
+			buffer: BufferNode {
+				path: buffer.to_string(),
+			},
+			start: RowCol { row: 0, col: 0 },
+			end: RowCol { row: 0, col: 0 },
+		};
+		for tx in attached {
+			let _ = tx
+				.send(Ok(CursorEvent {
+					user: user(name).id,
+					position: position.clone(),
+				}))
+				.await;
+		}
+	}
+
+	/// Drop every cursor stream, as if the server closed them.
+	pub fn close_cursors(&self) {
+		self.state.cursors.lock().unwrap().clear();
+	}
+
+	/// Set the profile of given user, as if they updated it themselves.
+	pub fn set_profile(&self, name: &str, display_name: Option<&str>, avatar: Option<&str>) {
+		self.state.profiles.lock().unwrap().insert(
+			name.to_string(),
+			ProfileUpdate {
+				display_name: display_name.map(str::to_string),
+				avatar: avatar.map(str::to_string),
+			},
+		);
+	}
+
+	/// Relay a chat message from given user to every attached client, optionally in the thread
+	/// about the start of given buffer.
+	pub async fn say(&self, name: &str, text: &str, buffer: Option<&str>) {
+		let anchor = buffer.map(|path| Anchor {
+			buffer: path.to_string(),
+			start_row: 0,
+			start_col: 0,
+			end_row: 0,
+			end_col: 0,
+		});
+		broadcast(&self.state, name, text.to_string(), anchor).await;
+	}
+
+	/// Drop every chat stream, as if the server closed them.
+	pub fn close_chats(&self) {
+		self.state.chats.lock().unwrap().clear();
+	}
+
+	/// Annotate the initial, empty state of given buffer as given user, relaying the annotation to
+	/// every attached client.
+	pub async fn annotate(&self, name: &str, buffer: &str, id: &str, text: &str) {
+		let (author_hi, author_lo) = uuid::Uuid::from(user(name).id).as_u64_pair();
+		let annotation = Annotation {
+			id: id.to_string(),
+			author_hi,
+			author_lo,
+			text: text.to_string(),
+			resolved: false,
+			anchor: AnnotationAnchor {
+				version: Vec::new(),
+				start: 0,
+				end: 0,
+			},
+		};
+		let token = format!("buffer-token-{buffer}");
+		self.state
+			.annotations
+			.lock()
+			.unwrap()
+			.push((token.clone(), annotation.clone()));
+		relay_annotation(&self.state, &token, AnnotationEvent::Update(annotation)).await;
+	}
+
+	/// Decorate the initial, empty state of given buffer as given user, relaying the decoration
+	/// to every attached client.
+	pub async fn decorate(&self, name: &str, buffer: &str, message: &str, ttl_ms: u32) {
+		self.decorate_at(name, buffer, message, ttl_ms, Vec::new())
+			.await;
+	}
+
+	/// Decorate given buffer as given user, on a change no client ever received.
+	pub async fn decorate_unseen(&self, name: &str, buffer: &str, message: &str, ttl_ms: u32) {
+		let version = vec![RemoteVersion {
+			agent: "unseen".to_string(),
+			seq: 0,
+		}];
+		self.decorate_at(name, buffer, message, ttl_ms, version)
+			.await;
+	}
+
+	async fn decorate_at(
+		&self,
+		name: &str,
+		buffer: &str,
+		message: &str,
+		ttl_ms: u32,
+		version: Vec<RemoteVersion>,
+	) {
+		let mut decoration = Decoration {
+			author_hi: None,
+			author_lo: None,
+			kind: 0,
+			message: message.to_string(),
+			anchor: AnnotationAnchor {
+				version,
+				start: 0,
+				end: 0,
+			},
+			ttl_ms,
+		};
+		decoration.set_kind(Kind::Highlight);
+		let token = format!("buffer-token-{buffer}");
+		relay_decoration(&self.state, &token, name, decoration, None).await;
+	}
+
+	/// Relay every decoration received so far back to every attached client, as if given user
+	/// sent them.
+	pub async fn echo_decorations(&self, name: &str) {
+		let received = self.state.decorations.lock().unwrap().clone();
+		for (token, decoration) in received {
+			relay_decoration(&self.state, &token, name, decoration, None).await;
+		}
+	}
+
+	/// How many decorations were received so far.
+	pub fn decorations(&self) -> usize {
+		self.state.decorations.lock().unwrap().len()
+	}
+
+	/// Session tokens the server has seen so far.
+	pub fn seen_tokens(&self) -> Vec<String> {
+		self.state.seen_tokens.lock().unwrap().clone()
+	}
+
+	/// Issue JWT-shaped tokens expiring after given time from now on.
+	pub fn expire_tokens_after(&self, lifetime: Duration) {
+		*self.state.token_lifetime.lock().unwrap() = Some(lifetime);
+	}
+
+	/// Reject the next `n` authenticated requests as if their tokens expired.
+	pub fn reject_next(&self, n: usize) {
+		self.state.reject.store(n, Ordering::SeqCst);
+	}
+
+	/// Refuse to refresh session tokens from now on.
+	pub fn refuse_refresh(&self) {
+		self.state.refuse_refresh.store(true, Ordering::SeqCst);
+	}
+
+	/// How many times a user logged in.
+	pub fn logins(&self) -> usize {
+		self.state.logins.load(Ordering::SeqCst)
+	}
+
+	/// Last session token issued upon login.
+	pub fn login_token(&self) -> Option<String> {
+		self.state.login_token.lock().unwrap().clone()
+	}
+
+	/// How many times a session token was refreshed.
+	pub fn refreshes(&self) -> usize {
+		self.state.refreshes.load(Ordering::SeqCst)
+	}
+
+	/// When every request to refresh a session token was received, even refused ones.
+	pub fn refresh_attempts(&self) -> Vec<Instant> {
+		self.state.refresh_attempts.lock().unwrap().clone()
+	}
+
+	/// How many workspace access tokens were granted.
+	pub fn accesses(&self) -> usize {
+		self.state.accesses.load(Ordering::SeqCst)
+	}
+
+	/// How many connections were accepted so far.
+	pub fn connections(&self) -> usize {
+		self.state.connections.load(Ordering::SeqCst)
+	}
+}
+
+impl Drop for MockServer {
+	fn drop(&mut self) {
+		if let Some(tx) = self.shutdown.take() {
+			let _ = tx.send(());
+		}
+	}
+}
+
+/// How long tests wait for anything the stand-in server is expected to deliver.
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connect as the default user and attach to the stand-in workspace.
+pub async fn attach(server: &MockServer) -> (codemp::Client, codemp::Workspace) {
+	attach_as(server, server.config()).await
+}
+
+/// Connect with given config and attach to the stand-in workspace, waiting until the server
+/// has seen the attachment.
+pub async fn attach_as(
+	server: &MockServer,
+	config: codemp::api::Config,
+) -> (codemp::Client, codemp::Workspace) {
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect to stand-in server");
+	let workspace = client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace");
+	server.attached().await;
+	(client, workspace)
+}
+
+/// A buffer callback paired with the channel it notifies every time it runs.
+pub fn notifier() -> (
+	impl Fn(codemp::buffer::Controller) + Send + Sync + 'static,
+	mpsc::UnboundedReceiver<()>,
+) {
+	let (tx, rx) = mpsc::unbounded_channel();
+	let cb = move |_| {
+		let _ = tx.send(());
+	};
+	(cb, rx)
+}
+
+/// Wait for the next notification from a [`notifier`], failing with given description.
+pub async fn fired(rx: &mut mpsc::UnboundedReceiver<()>, what: &str) {
+	tokio::time::timeout(TIMEOUT, rx.recv())
+		.await
+		.unwrap_or_else(|_| panic!("timed out waiting for {what}"))
+		.expect("buffer worker stopped");
+}
+
+/// Build a stable user for the given name.
+pub fn user(name: &str) -> User {
+	let id = uuid::Uuid::from_u64_pair(codemp::ext::hash(name) as u64, 0);
+	User {
+		id: Identity::from(id),
+		name: name.to_string(),
+	}
+}
+
+/// A pre-issued, JWT-shaped token carrying the identity of given user.
+pub fn user_token(name: &str) -> String {
+	use base64::Engine;
+	let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+	let id = uuid::Uuid::from_u64_pair(codemp::ext::hash(name) as u64, 0);
+	let header = b64.encode(r#"{"alg":"none"}"#);
+	let payload = b64.encode(format!(r#"{{"sub":"{id}","name":"{name}"}}"#));
+	format!("{header}.{payload}.")
+}
+
+#[derive(Clone)]
+struct Service(Arc<State>);
+
+impl Service {
+	/// Record the session token of given request, rejecting it if requested.
+	#[allow(clippy::result_large_err)] // same error type as handlers
+	fn record<T>(&self, req: &Request<T>) -> Result<(), Status> {
+		if let Some(token) = req.metadata().get("session") {
+			let token = token.to_str().unwrap_or_default().to_string();
+			self.0.seen_tokens.lock().unwrap().push(token);
+		}
+		let rejected = self
+			.0
+			.reject
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+		match rejected {
+			Ok(_) => Err(Status::unauthenticated("token expired")),
+			Err(_) => Ok(()),
+		}
+	}
+}
+
+#[tonic::async_trait]
+impl Auth for Service {
+	async fn login(&self, req: Request<LoginRequest>) -> Result<Response<LoginResponse>, Status> {
+		let req = req.into_inner();
+		if req.password != "password" {
+			return Err(Status::unauthenticated("wrong password"));
+		}
+		self.0.logins.fetch_add(1, Ordering::SeqCst);
+		let token = self.0.issue("session");
+		*self.0.login_token.lock().unwrap() = Some(token.token.clone());
+		Ok(Response::new(LoginResponse {
+			token,
+			user: user(&req.username),
+		}))
+	}
+
+	async fn refresh(&self, _req: Request<Token>) -> Result<Response<Token>, Status> {
+		self.0.refresh_attempts.lock().unwrap().push(Instant::now());
+		if self.0.refuse_refresh.load(Ordering::SeqCst) {
+			return Err(Status::unauthenticated("token revoked"));
+		}
+		self.0.refreshes.fetch_add(1, Ordering::SeqCst);
+		Ok(Response::new(self.0.issue("session")))
+	}
+}
+
+#[tonic::async_trait]
+impl Session for Service {
+	async fn access_workspace(
+		&self,
+		req: Request<WorkspaceRequest>,
+	) -> Result<Response<Token>, Status> {
+		self.record(&req)?;
+		self.0.accesses.fetch_add(1, Ordering::SeqCst);
+		Ok(Response::new(self.0.issue("workspace")))
+	}
+
+	async fn create_workspace(
+		&self,
+		req: Request<WorkspaceRequest>,
+	) -> Result<Response<Empty>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(Empty {}))
+	}
+
+	async fn delete_workspace(
+		&self,
+		req: Request<WorkspaceRequest>,
+	) -> Result<Response<Empty>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(Empty {}))
+	}
+
+	async fn list_workspaces(
+		&self,
+		req: Request<Empty>,
+	) -> Result<Response<WorkspaceList>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(WorkspaceList {
+			owned: vec!["workspace".to_string()],
+			invited: Vec::new(),
+		}))
+	}
+
+	async fn invite_to_workspace(
+		&self,
+		req: Request<InviteRequest>,
+	) -> Result<Response<Empty>, Status> {
+		self.record(&req)?;
+		self.0
+			.members
+			.lock()
+			.unwrap()
+			.entry(req.into_inner().user)
+			.or_insert((Role::ReadWrite, true));
+		Ok(Response::new(Empty {}))
+	}
+}
+
+/// Describe a workspace member, as the server would.
+fn member(name: &str, role: Role, pending: bool) -> Member {
+	let id = user(name).id;
+	let mut member = Member {
+		id_hi: id.hi,
+		id_lo: id.lo,
+		name: name.to_string(),
+		role: 0,
+		pending,
+	};
+	member.set_role(role);
+	member
+}
+
+#[tonic::async_trait]
+impl Members for Service {
+	async fn list_members(
+		&self,
+		req: Request<MembersRequest>,
+	) -> Result<Response<MemberList>, Status> {
+		self.record(&req)?;
+		let members = self.0.members.lock().unwrap();
+		Ok(Response::new(MemberList {
+			members: members
+				.iter()
+				.map(|(name, (role, pending))| member(name, *role, *pending))
+				.collect(),
+		}))
+	}
+
+	async fn get_membership(
+		&self,
+		req: Request<MembersRequest>,
+	) -> Result<Response<Member>, Status> {
+		self.record(&req)?;
+		let members = self.0.members.lock().unwrap();
+		let (role, pending) = members
+			.get("alice")
+			.copied()
+			.unwrap_or((Role::ReadWrite, false));
+		Ok(Response::new(member("alice", role, pending)))
+	}
+
+	async fn remove_member(&self, req: Request<MemberRequest>) -> Result<Response<Done>, Status> {
+		self.record(&req)?;
+		let req = req.into_inner();
+		match self.0.members.lock().unwrap().remove(&req.user) {
+			Some(_) => Ok(Response::new(Done {})),
+			None => Err(Status::not_found(format!("{} is not a member", req.user))),
+		}
+	}
+
+	async fn set_role(&self, req: Request<RoleRequest>) -> Result<Response<Done>, Status> {
+		self.record(&req)?;
+		let req = req.into_inner();
+		let role = req.role();
+		match self.0.members.lock().unwrap().get_mut(&req.user) {
+			Some(member) => {
+				member.0 = role;
+				Ok(Response::new(Done {}))
+			}
+			None => Err(Status::not_found(format!("{} is not a member", req.user))),
+		}
+	}
+}
+
+#[tonic::async_trait]
+impl Presence for Service {
+	async fn list_profiles(
+		&self,
+		req: Request<ProfilesRequest>,
+	) -> Result<Response<ProfileList>, Status> {
+		self.record(&req)?;
+		let profiles = self
+			.0
+			.profiles
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|(name, update)| {
+				let (id_hi, id_lo) = uuid::Uuid::from(user(name).id).as_u64_pair();
+				Profile {
+					id_hi,
+					id_lo,
+					display_name: update.display_name.clone(),
+					avatar: update.avatar.clone(),
+				}
+			})
+			.collect();
+		Ok(Response::new(ProfileList { profiles }))
+	}
+
+	async fn set_profile(&self, req: Request<ProfileUpdate>) -> Result<Response<Updated>, Status> {
+		self.record(&req)?;
+		self.0
+			.profiles
+			.lock()
+			.unwrap()
+			.insert("alice".to_string(), req.into_inner());
+		Ok(Response::new(Updated {}))
+	}
+}
+
+/// Relay a chat message to every attached client, as the server would.
+async fn broadcast(state: &State, name: &str, text: String, anchor: Option<Anchor>) {
+	let (id_hi, id_lo) = uuid::Uuid::from(user(name).id).as_u64_pair();
+	let timestamp = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_millis() as u64;
+	let message = Message {
+		id_hi,
+		id_lo,
+		text,
+		timestamp,
+		anchor,
+	};
+	let attached = state.chats.lock().unwrap().clone();
+	for tx in attached {
+		let _ = tx.send(Ok(message.clone())).await;
+	}
+}
+
+#[tonic::async_trait]
+impl Chat for Service {
+	type AttachStream = ResponseStream<Message>;
+
+	async fn attach(
+		&self,
+		req: Request<Streaming<Draft>>,
+	) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let mut incoming = req.into_inner();
+		let (tx, rx) = mpsc::channel(16);
+		self.0.chats.lock().unwrap().push(tx.clone());
+		let state = self.0.clone();
+		tokio::spawn(async move {
+			while let Ok(Some(draft)) = incoming.message().await {
+				broadcast(&state, "alice", draft.text, draft.anchor).await;
+			}
+		});
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+}
+
+/// Relay a change to the annotations of a buffer to every client attached to them.
+async fn relay_annotation(state: &State, token: &str, event: AnnotationEvent) {
+	let attached = state
+		.annotation_streams
+		.lock()
+		.unwrap()
+		.iter()
+		.filter(|(t, _)| t == token)
+		.map(|(_, tx)| tx.clone())
+		.collect::<Vec<_>>();
+	for tx in attached {
+		let _ = tx
+			.send(Ok(AnnotationUpdate {
+				event: Some(event.clone()),
+			}))
+			.await;
+	}
+}
+
+/// Apply a change to the annotations of a buffer requested by alice, returning what to relay.
+fn apply_annotation(
+	state: &State,
+	token: &str,
+	change: AnnotationChange,
+) -> Option<AnnotationEvent> {
+	let mut annotations = state.annotations.lock().unwrap();
+	match change {
+		AnnotationChange::Create(note) => {
+			let (author_hi, author_lo) = uuid::Uuid::from(user("alice").id).as_u64_pair();
+			let annotation = Annotation {
+				id: note.id,
+				author_hi,
+				author_lo,
+				text: note.text,
+				resolved: false,
+				anchor: note.anchor,
+			};
+			annotations.push((token.to_string(), annotation.clone()));
+			Some(AnnotationEvent::Update(annotation))
+		}
+		AnnotationChange::Resolve(id) => {
+			let (_, annotation) = annotations
+				.iter_mut()
+				.find(|(t, a)| t == token && a.id == id)?;
+			annotation.resolved = true;
+			Some(AnnotationEvent::Update(annotation.clone()))
+		}
+		AnnotationChange::Delete(id) => {
+			annotations.retain(|(t, a)| t != token || a.id != id);
+			Some(AnnotationEvent::Delete(id))
+		}
+	}
+}
+
+#[tonic::async_trait]
+impl Annotations for Service {
+	type AttachStream = ResponseStream<AnnotationUpdate>;
+
+	async fn attach(
+		&self,
+		req: Request<Streaming<Change>>,
+	) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let token = req
+			.metadata()
+			.get("buffer")
+			.and_then(|t| t.to_str().ok())
+			.ok_or_else(|| Status::unauthenticated("missing buffer token"))?
+			.to_string();
+		let mut incoming = req.into_inner();
+		let (tx, rx) = mpsc::channel(16);
+		let known = self
+			.0
+			.annotations
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|(t, _)| *t == token)
+			.map(|(_, a)| a.clone())
+			.collect::<Vec<_>>();
+		for annotation in known {
+			let _ = tx
+				.send(Ok(AnnotationUpdate {
+					event: Some(AnnotationEvent::Update(annotation)),
+				}))
+				.await;
+		}
+		self.0
+			.annotation_streams
+			.lock()
+			.unwrap()
+			.push((token.clone(), tx.clone()));
+		let state = self.0.clone();
+		tokio::spawn(async move {
+			while let Ok(Some(change)) = incoming.message().await {
+				let Some(change) = change.change else {
+					continue;
+				};
+				if let Some(event) = apply_annotation(&state, &token, change) {
+					relay_annotation(&state, &token, event).await;
+				}
+			}
+			drop(tx);
+		});
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+}
+
+/// Relay a decoration of a buffer as sent by given user to every client attached to them, except
+/// the sender itself.
+async fn relay_decoration(
+	state: &State,
+	token: &str,
+	name: &str,
+	mut decoration: Decoration,
+	sender: Option<&mpsc::Sender<Result<Decoration, Status>>>,
+) {
+	let (author_hi, author_lo) = uuid::Uuid::from(user(name).id).as_u64_pair();
+	decoration.author_hi = Some(author_hi);
+	decoration.author_lo = Some(author_lo);
+	let attached = state
+		.decoration_streams
+		.lock()
+		.unwrap()
+		.iter()
+		.filter(|(t, tx)| t == token && !sender.is_some_and(|s| s.same_channel(tx)))
+		.map(|(_, tx)| tx.clone())
+		.collect::<Vec<_>>();
+	for tx in attached {
+		let _ = tx.send(Ok(decoration.clone())).await;
+	}
+}
+
+#[tonic::async_trait]
+impl Decorations for Service {
+	type AttachStream = ResponseStream<Decoration>;
+
+	async fn attach(
+		&self,
+		req: Request<Streaming<Decoration>>,
+	) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let token = req
+			.metadata()
+			.get("buffer")
+			.and_then(|t| t.to_str().ok())
+			.ok_or_else(|| Status::unauthenticated("missing buffer token"))?
+			.to_string();
+		let mut incoming = req.into_inner();
+		let (tx, rx) = mpsc::channel(16);
+		self.0
+			.decoration_streams
+			.lock()
+			.unwrap()
+			.push((token.clone(), tx.clone()));
+		let state = self.0.clone();
+		tokio::spawn(async move {
+			while let Ok(Some(decoration)) = incoming.message().await {
+				state
+					.decorations
+					.lock()
+					.unwrap()
+					.push((token.clone(), decoration.clone()));
+				relay_decoration(&state, &token, "alice", decoration, Some(&tx)).await;
+			}
+			drop(tx);
+		});
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+}
+
+#[tonic::async_trait]
+impl Metadata for Service {
+	type AttachStream = ResponseStream<BufferMetadata>;
+
+	async fn get(&self, req: Request<BufferPath>) -> Result<Response<BufferMetadata>, Status> {
+		self.record(&req)?;
+		let path = req.into_inner().path;
+		let metadata = self.0.metadata.lock().unwrap().get(&path).cloned();
+		Ok(Response::new(metadata.unwrap_or(BufferMetadata {
+			path,
+			..Default::default()
+		})))
+	}
+
+	async fn set(&self, req: Request<BufferMetadata>) -> Result<Response<NoMetadata>, Status> {
+		self.record(&req)?;
+		let metadata = req.into_inner();
+		let token = format!("buffer-token-{}", metadata.path);
+		self.0
+			.metadata
+			.lock()
+			.unwrap()
+			.insert(metadata.path.clone(), metadata.clone());
+		let attached = self
+			.0
+			.metadata_streams
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|(t, _)| *t == token)
+			.map(|(_, tx)| tx.clone())
+			.collect::<Vec<_>>();
+		for tx in attached {
+			let _ = tx.send(Ok(metadata.clone())).await;
+		}
+		Ok(Response::new(NoMetadata {}))
+	}
+
+	async fn attach(
+		&self,
+		req: Request<NoMetadata>,
+	) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let token = req
+			.metadata()
+			.get("buffer")
+			.and_then(|t| t.to_str().ok())
+			.ok_or_else(|| Status::unauthenticated("missing buffer token"))?
+			.to_string();
+		let (tx, rx) = mpsc::channel(16);
+		let path = token.trim_start_matches("buffer-token-");
+		let current = self.0.metadata.lock().unwrap().get(path).cloned();
+		if let Some(metadata) = current {
+			let _ = tx.send(Ok(metadata)).await;
+		}
+		self.0.metadata_streams.lock().unwrap().push((token, tx));
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+}
+
+#[tonic::async_trait]
+impl Workspace for Service {
+	type AttachStream = ResponseStream<WorkspaceEvent>;
+
+	async fn attach(&self, req: Request<Empty>) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let (tx, rx) = mpsc::channel(16);
+		self.0.attached.lock().unwrap().push(tx);
+		self.0.attach_notify.notify_waiters();
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+
+	async fn create_buffer(&self, req: Request<BufferNode>) -> Result<Response<Empty>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(Empty {}))
+	}
+
+	async fn access_buffer(&self, req: Request<BufferNode>) -> Result<Response<Token>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(Token {
+			token: format!("buffer-token-{}", req.into_inner().path),
+		}))
+	}
+
+	async fn delete_buffer(&self, req: Request<BufferNode>) -> Result<Response<Empty>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(Empty {}))
+	}
+
+	async fn list_buffers(&self, req: Request<Empty>) -> Result<Response<BufferTree>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(BufferTree {
+			buffers: Vec::new(),
+		}))
+	}
+
+	async fn list_users(&self, req: Request<Empty>) -> Result<Response<UserList>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(UserList {
+			users: vec![user("alice")],
+		}))
+	}
+
+	async fn list_buffer_users(
+		&self,
+		req: Request<BufferNode>,
+	) -> Result<Response<UserList>, Status> {
+		self.record(&req)?;
+		Ok(Response::new(UserList { users: Vec::new() }))
+	}
+}
+
+#[tonic::async_trait]
+impl Cursor for Service {
+	type AttachStream = ResponseStream<CursorEvent>;
+
+	async fn attach(
+		&self,
+		req: Request<Streaming<CursorPosition>>,
+	) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let mut incoming = req.into_inner();
+		let (tx, rx) = mpsc::channel(16);
+		self.0.cursors.lock().unwrap().push(tx);
+		tokio::spawn(async move {
+			// the response stream stays open until closed with `close_cursors`
+			while let Ok(Some(_)) = incoming.message().await {}
+		});
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+}
+
+#[tonic::async_trait]
+impl Buffer for Service {
+	type AttachStream = ResponseStream<BufferEvent>;
+
+	async fn attach(
+		&self,
+		req: Request<Streaming<Operation>>,
+	) -> Result<Response<Self::AttachStream>, Status> {
+		self.record(&req)?;
+		let token = req
+			.metadata()
+			.get("buffer")
+			.and_then(|t| t.to_str().ok())
+			.ok_or_else(|| Status::unauthenticated("missing buffer token"))?
+			.to_string();
+		let mut incoming = req.into_inner();
+		let (tx, rx) = mpsc::channel::<Result<BufferEvent, Status>>(16);
+		self.0
+			.buffer_streams
+			.lock()
+			.unwrap()
+			.push((token.clone(), tx.clone()));
+		let state = self.0.clone();
+		tokio::spawn(async move {
+			// relay operations to every other client attached to the same buffer
+			while let Ok(Some(op)) = incoming.message().await {
+				let attached = state
+					.buffer_streams
+					.lock()
+					.unwrap()
+					.iter()
+					.filter(|(t, other)| *t == token && !other.same_channel(&tx))
+					.map(|(_, other)| other.clone())
+					.collect::<Vec<_>>();
+				for other in attached {
+					let event = BufferEvent {
+						op: op.clone(),
+						// streams aren't tied to users here, and clients only apply the operation
+						user: user("alice").id,
+					};
+					let _ = other.send(Ok(event)).await;
+				}
+			}
+			state
+				.buffer_streams
+				.lock()
+				.unwrap()
+				.retain(|(_, other)| !other.same_channel(&tx));
+			drop(tx);
+		});
+		Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+	}
+}
+
+/// Layer removing a path prefix from every request, like a reverse proxy would.
+#[derive(Clone, Copy)]
+struct StripPrefix(Option<&'static str>);
+
+impl<S> tower_layer::Layer<S> for StripPrefix {
+	type Service = Stripped<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		Stripped(self.0, inner)
+	}
+}
+
+#[derive(Clone)]
+struct Stripped<S>(Option<&'static str>, S);
+
+impl<S, B> tower_service::Service<http::Request<B>> for Stripped<S>
+where
+	S: tower_service::Service<http::Request<B>>,
+{
+	type Response = S::Response;
+	type Error = S::Error;
+	type Future = S::Future;
+
+	fn poll_ready(
+		&mut self,
+		cx: &mut std::task::Context<'_>,
+	) -> std::task::Poll<Result<(), Self::Error>> {
+		self.1.poll_ready(cx)
+	}
+
+	fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+		if let Some(prefix) = self.0 {
+			// requests outside the prefix are sent nowhere, and won't match any service
+			let path = req.uri().path().strip_prefix(prefix).unwrap_or("/");
+			let mut parts = req.uri().clone().into_parts();
+			parts.path_and_query = path.parse().ok();
+			*req.uri_mut() = http::Uri::from_parts(parts).expect("invalid stripped uri");
+		}
+		self.1.call(req)
+	}
+}
+
+/// Transport reaching a server started with [`MockServer::start_in_process`] over duplex pipes.
+pub struct InProcess(mpsc::Sender<tokio::io::DuplexStream>);
+
+impl codemp::transport::Transport for InProcess {
+	type Stream = tokio::io::DuplexStream;
+
+	async fn open(&self) -> std::io::Result<Self::Stream> {
+		let (client, server) = tokio::io::duplex(64 * 1024);
+		self.0
+			.send(server)
+			.await
+			.map_err(|_| std::io::Error::other("stand-in server is gone"))?;
+		Ok(client)
+	}
+}
+
+/// A minimal forwarding proxy, counting how many tunnels it opened.
+pub struct MockProxy {
+	pub addr: SocketAddr,
+	tunnels: Arc<AtomicUsize>,
+}
+
+impl MockProxy {
+	/// Start an HTTP proxy only supporting the CONNECT method.
+	pub async fn http() -> Self {
+		Self::start(|mut conn| async move {
+			use tokio::io::{AsyncReadExt, AsyncWriteExt};
+			let mut head = Vec::new();
+			while !head.ends_with(b"\r\n\r\n") {
+				head.push(conn.read_u8().await.ok()?);
+			}
+			let head = String::from_utf8(head).ok()?;
+			let target = head.strip_prefix("CONNECT ")?.split_whitespace().next()?;
+			let upstream = tokio::net::TcpStream::connect(target).await.ok()?;
+			conn.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+				.await
+				.ok()?;
+			Some((conn, upstream))
+		})
+		.await
+	}
+
+	/// Start a SOCKS5 proxy without authentication, only accepting IPv4 destinations.
+	pub async fn socks5() -> Self {
+		Self::start(|mut conn| async move {
+			use tokio::io::{AsyncReadExt, AsyncWriteExt};
+			let mut greeting = [0u8; 2];
+			conn.read_exact(&mut greeting).await.ok()?;
+			let mut methods = vec![0u8; greeting[1] as usize];
+			conn.read_exact(&mut methods).await.ok()?;
+			conn.write_all(&[5, 0]).await.ok()?;
+			let mut request = [0u8; 10];
+			conn.read_exact(&mut request).await.ok()?;
+			if request[3] != 1 {
+				return None;
+			}
+			let ip = std::net::Ipv4Addr::new(request[4], request[5], request[6], request[7]);
+			let port = u16::from_be_bytes([request[8], request[9]]);
+			let upstream = tokio::net::TcpStream::connect((ip, port)).await.ok()?;
+			conn.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await.ok()?;
+			Some((conn, upstream))
+		})
+		.await
+	}
+
+	async fn start<F, Fut>(handshake: F) -> Self
+	where
+		F: Fn(tokio::net::TcpStream) -> Fut + Send + 'static,
+		Fut: std::future::Future<Output = Option<(tokio::net::TcpStream, tokio::net::TcpStream)>>
+			+ Send
+			+ 'static,
+	{
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+			.await
+			.expect("could not bind local listener");
+		let addr = listener.local_addr().expect("listener has no address");
+		let tunnels = Arc::new(AtomicUsize::new(0));
+		let counter = tunnels.clone();
+		tokio::spawn(async move {
+			while let Ok((conn, _)) = listener.accept().await {
+				let tunnel = handshake(conn);
+				let counter = counter.clone();
+				tokio::spawn(async move {
+					if let Some((mut conn, mut upstream)) = tunnel.await {
+						counter.fetch_add(1, Ordering::SeqCst);
+						let _ = tokio::io::copy_bidirectional(&mut conn, &mut upstream).await;
+					}
+				});
+			}
+		});
+		Self { addr, tunnels }
+	}
+
+	/// How many tunnels were opened so far.
+	pub fn tunnels(&self) -> usize {
+		self.tunnels.load(Ordering::SeqCst)
+	}
+}
+
+/// Accept connections without ever answering, keeping them open.
+pub async fn black_hole() -> SocketAddr {
+	let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+		.await
+		.expect("could not bind local listener");
+	let addr = listener.local_addr().expect("listener has no address");
+	tokio::spawn(async move {
+		let mut held = Vec::new();
+		while let Ok((conn, _)) = listener.accept().await {
+			held.push(conn);
+		}
+	});
+	addr
+}
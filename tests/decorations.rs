@@ -0,0 +1,128 @@
+mod common;
+
+use std::time::Duration;
+
+use codemp::api::{
+	controller::{AsyncReceiver, AsyncSender},
+	Decoration, DecorationKind, Event, TextChange,
+};
+use common::{attach, MockServer, TIMEOUT};
+
+#[tokio::test]
+async fn decorations_follow_edits() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	server.join("bob").await;
+	let joined = tokio::time::timeout(TIMEOUT, workspace.recv()).await;
+	assert!(matches!(joined, Ok(Ok(Event::UserJoin { .. }))));
+
+	let decorations = workspace
+		.attach_decorations("main.rs")
+		.await
+		.expect("could not attach to decorations");
+	let buffer = workspace
+		.get_buffer("main.rs")
+		.expect("buffer was not attached along with decorations");
+	assert_eq!(decorations.path(), "main.rs");
+
+	buffer
+		.send(TextChange {
+			start_idx: 0,
+			end_idx: 0,
+			content: "hello world".into(),
+		})
+		.expect("could not send change");
+	decorations
+		.send(Decoration::new(DecorationKind::Error, 6, 11, "typo"))
+		.expect("could not send decoration");
+	tokio::time::timeout(TIMEOUT, async {
+		while server.decorations() == 0 {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("timed out waiting for decoration to reach the server");
+
+	// the decoration refers to the buffer before this insertion, and moves past it
+	buffer
+		.send(TextChange {
+			start_idx: 6,
+			end_idx: 6,
+			content: "big ".into(),
+		})
+		.expect("could not send change");
+	server.echo_decorations("bob").await;
+
+	let received = tokio::time::timeout(TIMEOUT, decorations.recv())
+		.await
+		.expect("timed out waiting for decoration")
+		.expect("decoration worker stopped");
+	assert_eq!(
+		received,
+		Decoration {
+			kind: DecorationKind::Error,
+			message: "typo".into(),
+			author: "bob".into(),
+			start_idx: 10,
+			end_idx: 15,
+			ttl_ms: Decoration::DEFAULT_TTL_MS,
+		}
+	);
+	assert_eq!(decorations.active().await.unwrap(), [received]);
+}
+
+#[tokio::test]
+async fn decorations_expire() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let decorations = workspace
+		.attach_decorations("main.rs")
+		.await
+		.expect("could not attach to decorations");
+
+	server.decorate("bob", "main.rs", "found", 100).await;
+	let active = tokio::time::timeout(TIMEOUT, async {
+		loop {
+			let active = decorations
+				.active()
+				.await
+				.expect("could not list decorations");
+			if !active.is_empty() {
+				break active;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("timed out waiting for decoration");
+	assert_eq!(active[0].message, "found");
+	assert_eq!(active[0].kind, DecorationKind::Highlight);
+
+	tokio::time::sleep(Duration::from_millis(200)).await;
+	assert!(decorations.active().await.unwrap().is_empty());
+	assert_eq!(decorations.try_recv().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn undeliverable_decorations_dont_hold_back_later_ones() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let decorations = workspace
+		.attach_decorations("main.rs")
+		.await
+		.expect("could not attach to decorations");
+
+	server
+		.decorate_unseen("bob", "main.rs", "pending", 60_000)
+		.await;
+	server.decorate("bob", "main.rs", "found", 60_000).await;
+	let received = tokio::time::timeout(TIMEOUT, decorations.recv())
+		.await
+		.expect("timed out waiting for decoration")
+		.expect("decoration worker stopped");
+	assert_eq!(received.message, "found");
+
+	// the first one stays queued until the editor receives its change
+	assert_eq!(decorations.try_recv().await.unwrap(), None);
+	assert_eq!(decorations.active().await.unwrap(), [received]);
+}
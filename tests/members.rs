@@ -0,0 +1,133 @@
+mod common;
+
+use codemp::{
+	api::{
+		controller::{AsyncReceiver, AsyncSender},
+		Role, TextChange,
+	},
+	errors::{ControllerError, ErrorKind},
+};
+use common::MockServer;
+
+async fn connect(server: &MockServer) -> codemp::Client {
+	codemp::Client::connect(server.config())
+		.await
+		.expect("could not connect to stand-in server")
+}
+
+#[tokio::test]
+async fn members_can_be_listed_changed_and_removed() {
+	let server = MockServer::start().await;
+	let client = connect(&server).await;
+	server.set_role("alice", Role::Owner);
+
+	client
+		.invite_to_workspace("workspace", "bob")
+		.await
+		.expect("could not invite");
+	let members = client
+		.fetch_workspace_members("workspace")
+		.await
+		.expect("could not list members");
+	let summary: Vec<_> = members
+		.iter()
+		.map(|m| (m.user.name.as_str(), m.role, m.pending))
+		.collect();
+	assert_eq!(
+		summary,
+		vec![
+			("alice", Role::Owner, false),
+			("bob", Role::ReadWrite, true)
+		]
+	);
+
+	client
+		.set_workspace_role("workspace", "bob", Role::ReadOnly)
+		.await
+		.expect("could not change role");
+	let workspace = client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace");
+	let bob = workspace
+		.fetch_members()
+		.await
+		.expect("could not list members")
+		.into_iter()
+		.find(|m| m.user.name == "bob")
+		.expect("bob is not a member");
+	assert_eq!(bob.role, Role::ReadOnly);
+
+	client
+		.remove_from_workspace("workspace", "bob")
+		.await
+		.expect("could not remove member");
+	let err = client
+		.remove_from_workspace("workspace", "bob")
+		.await
+		.expect_err("removed a user twice");
+	assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn read_only_members_cannot_send_changes() {
+	let server = MockServer::start().await;
+	server.set_role("alice", Role::ReadOnly);
+	let client = connect(&server).await;
+	let workspace = client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace");
+	assert_eq!(workspace.role(), Role::ReadOnly);
+
+	let buffer = workspace
+		.attach_buffer("file.txt")
+		.await
+		.expect("could not attach to buffer");
+	assert!(buffer.is_read_only());
+	let change = TextChange {
+		start_idx: 0,
+		end_idx: 0,
+		content: "hello".into(),
+	};
+	let err = buffer
+		.send(change.clone())
+		.expect_err("read-only buffer accepted a change");
+	assert!(matches!(err, ControllerError::ReadOnly));
+	assert_eq!(err.kind(), ErrorKind::Forbidden);
+
+	server.set_role("alice", Role::ReadWrite);
+	assert_eq!(
+		workspace.fetch_role().await.expect("could not fetch role"),
+		Role::ReadWrite
+	);
+	assert!(!buffer.is_read_only());
+	buffer.send(change).expect("change was refused");
+}
+
+#[tokio::test]
+async fn spectated_buffers_refuse_changes_but_follow_content() {
+	let server = MockServer::start().await;
+	let client = connect(&server).await;
+	let workspace = client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace");
+	assert_eq!(workspace.role(), Role::ReadWrite);
+
+	let buffer = workspace
+		.attach_buffer_read_only("file.txt")
+		.await
+		.expect("could not spectate buffer");
+	assert!(buffer.is_read_only());
+	let err = buffer
+		.send(TextChange {
+			start_idx: 0,
+			end_idx: 0,
+			content: "hello".into(),
+		})
+		.expect_err("spectated buffer accepted a change");
+	assert!(matches!(err, ControllerError::ReadOnly));
+	assert_eq!(buffer.content().await.expect("could not read content"), "");
+	assert!(buffer.try_recv().await.expect("could not poll").is_none());
+}
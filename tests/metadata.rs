@@ -0,0 +1,196 @@
+mod common;
+
+use codemp::api::{
+	controller::{AsyncReceiver, AsyncSender},
+	BufferMetadata, Indentation, LineEnding, TextChange,
+};
+use common::{attach, attach_as, fired, notifier, MockServer, TIMEOUT};
+
+#[tokio::test]
+async fn metadata_is_set_and_announced() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let buffer = workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("could not attach to buffer");
+	let (cb, mut rx) = notifier();
+	buffer.metadata_callback(cb);
+	assert_eq!(buffer.metadata(), BufferMetadata::default());
+	assert_eq!(
+		workspace.fetch_buffer_metadata("main.rs").await.unwrap(),
+		BufferMetadata::default()
+	);
+
+	let metadata = BufferMetadata {
+		language: Some("rust".into()),
+		line_ending: Some(LineEnding::Crlf),
+		indentation: Some(Indentation {
+			tabs: true,
+			width: 4,
+		}),
+		encoding: Some("utf-8".into()),
+	};
+	workspace
+		.set_buffer_metadata("main.rs", metadata.clone())
+		.await
+		.expect("could not set metadata");
+	fired(&mut rx, "metadata").await;
+	assert_eq!(buffer.metadata(), metadata);
+	assert_eq!(
+		workspace.fetch_buffer_metadata("main.rs").await.unwrap(),
+		metadata
+	);
+
+	// buffers attached later get the current metadata right away
+	workspace.detach_buffer("main.rs");
+	let buffer = workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("could not attach to buffer again");
+	let (cb, mut rx) = notifier();
+	buffer.metadata_callback(cb);
+	if buffer.metadata() != metadata {
+		fired(&mut rx, "metadata").await;
+	}
+	assert_eq!(buffer.metadata(), metadata);
+	buffer.clear_metadata_callback();
+}
+
+#[tokio::test]
+async fn sent_line_endings_are_normalized() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let buffer = workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("could not attach to buffer");
+	let (cb, mut rx) = notifier();
+	buffer.metadata_callback(cb);
+	workspace
+		.set_buffer_metadata(
+			"main.rs",
+			BufferMetadata {
+				line_ending: Some(LineEnding::Lf),
+				..Default::default()
+			},
+		)
+		.await
+		.expect("could not set metadata");
+	fired(&mut rx, "metadata").await;
+
+	assert!(!buffer.is_normalizing_line_endings());
+	buffer.normalize_line_endings(true);
+	assert!(buffer.is_normalizing_line_endings());
+
+	let mut editor = "a\r\nb".to_string();
+	buffer
+		.send(TextChange {
+			start_idx: 0,
+			end_idx: 0,
+			content: editor.clone(),
+		})
+		.expect("could not send change");
+
+	// the editor receives the replaced line endings like any other change
+	tokio::time::timeout(TIMEOUT, async {
+		while editor != "a\nb" {
+			let update = buffer.recv().await.expect("buffer worker stopped");
+			editor = update.change.apply(&editor);
+		}
+	})
+	.await
+	.expect("timed out waiting for normalized line endings");
+	assert_eq!(buffer.content().await.unwrap(), "a\nb");
+}
+
+/// Apply changes received on given buffer to the editor content until it's the expected one,
+/// checking every hash along the way.
+async fn receive(buffer: &codemp::buffer::Controller, editor: &mut String, expected: &str) {
+	tokio::time::timeout(TIMEOUT, async {
+		while editor.as_str() != expected {
+			let update = buffer.recv().await.expect("buffer worker stopped");
+			*editor = update.change.apply(editor);
+			if let Some(hash) = update.hash {
+				assert_eq!(hash, codemp::ext::hash(editor.as_str()));
+			}
+		}
+	})
+	.await
+	.unwrap_or_else(|_| panic!("timed out waiting for {expected:?}, editor has {editor:?}"));
+}
+
+#[tokio::test]
+async fn line_endings_are_translated_for_the_editor() {
+	let server = MockServer::start().await;
+	let (_alice, workspace) = attach(&server).await;
+	let bob_config = codemp::api::Config {
+		username: "bob".into(),
+		..server.config()
+	};
+	let (_bob, bob_workspace) = attach_as(&server, bob_config).await;
+	let buffer = workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("could not attach to buffer");
+	let bob_buffer = bob_workspace
+		.attach_buffer("main.rs")
+		.await
+		.expect("bob could not attach to buffer");
+	let (cb, mut rx) = notifier();
+	buffer.metadata_callback(cb);
+	workspace
+		.set_buffer_metadata(
+			"main.rs",
+			BufferMetadata {
+				line_ending: Some(LineEnding::Lf),
+				..Default::default()
+			},
+		)
+		.await
+		.expect("could not set metadata");
+	fired(&mut rx, "metadata").await;
+
+	assert_eq!(buffer.local_line_ending(), None);
+	buffer.translate_line_endings(Some(LineEnding::Crlf));
+	assert_eq!(buffer.local_line_ending(), Some(LineEnding::Crlf));
+
+	// sent line endings reach others as the canonical ones
+	let mut editor = "a\r\nb".to_string();
+	buffer
+		.send(TextChange {
+			start_idx: 0,
+			end_idx: 0,
+			content: editor.clone(),
+		})
+		.expect("could not send change");
+	let mut bob_editor = String::new();
+	receive(&bob_buffer, &mut bob_editor, "a\nb").await;
+
+	// received changes are moved past local line endings, and carry them
+	bob_buffer
+		.send(TextChange {
+			start_idx: 2,
+			end_idx: 2,
+			content: "c\n".into(),
+		})
+		.expect("bob could not send change");
+	receive(&buffer, &mut editor, "a\r\nc\r\nb").await;
+	assert_eq!(buffer.content().await.unwrap(), "a\r\nc\r\nb");
+	assert!(buffer.verify(codemp::ext::hash(&editor)).await.unwrap());
+
+	// removing a local line ending removes the canonical one
+	buffer
+		.send(TextChange {
+			start_idx: 4,
+			end_idx: 6,
+			content: "".into(),
+		})
+		.expect("could not send change");
+	bob_editor = "a\nc\nb".to_string();
+	receive(&bob_buffer, &mut bob_editor, "a\ncb").await;
+	assert_eq!(bob_buffer.content().await.unwrap(), "a\ncb");
+
+	buffer.translate_line_endings(None);
+	assert_eq!(buffer.content().await.unwrap(), "a\ncb");
+}
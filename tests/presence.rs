@@ -0,0 +1,99 @@
+mod common;
+
+use codemp::api::{controller::AsyncReceiver, Event, Status};
+use common::{attach, attach_as, MockServer, TIMEOUT};
+
+/// Receive events until the presence of given user changes.
+async fn presence_changed(workspace: &codemp::Workspace, user: &str) {
+	tokio::time::timeout(TIMEOUT, async {
+		loop {
+			match workspace.recv().await.expect("workspace stopped") {
+				Event::PresenceChanged { name } if name == user => break,
+				_ => continue,
+			}
+		}
+	})
+	.await
+	.expect("timed out waiting for presence change");
+}
+
+#[tokio::test]
+async fn presence_follows_cursors_and_profiles() {
+	let server = MockServer::start().await;
+	let (client, workspace) = attach(&server).await;
+
+	let alice = workspace
+		.user_presence("alice")
+		.expect("current user has no presence");
+	assert_eq!(alice.status, Status::Active);
+	assert_eq!(alice.buffer, None);
+	assert_eq!(alice.label(), "alice");
+	assert_eq!(
+		alice.color,
+		workspace.user_presence("alice").unwrap().color,
+		"colors should be stable"
+	);
+
+	server.join("bob").await;
+	server.move_cursor("bob", "main.rs").await;
+	presence_changed(&workspace, "bob").await;
+	let bob = workspace.user_presence("bob").expect("bob has no presence");
+	assert_eq!(bob.buffer.as_deref(), Some("main.rs"));
+	assert_ne!(bob.color, alice.color);
+
+	// moving within the same buffer changes nothing visible
+	server.move_cursor("bob", "main.rs").await;
+	server.move_cursor("bob", "lib.rs").await;
+	presence_changed(&workspace, "bob").await;
+	assert_eq!(
+		workspace.user_presence("bob").unwrap().buffer.as_deref(),
+		Some("lib.rs")
+	);
+
+	client
+		.set_profile(
+			Some("Alice A.".into()),
+			Some("https://code.mp/alice.png".into()),
+		)
+		.await
+		.expect("could not set profile");
+	let presence = workspace
+		.fetch_presence()
+		.await
+		.expect("could not fetch presence");
+	let alice = presence
+		.iter()
+		.find(|p| p.user.name == "alice")
+		.expect("current user has no presence");
+	assert_eq!(alice.label(), "Alice A.");
+	assert_eq!(alice.avatar.as_deref(), Some("https://code.mp/alice.png"));
+}
+
+#[tokio::test]
+async fn inactive_users_become_idle_then_away() {
+	let server = MockServer::start().await;
+	let mut config = server.config();
+	config.tuning = Some(codemp::api::Tuning {
+		worker_tick_ms: Some(50),
+		idle_after_ms: Some(200),
+		away_after_ms: Some(600),
+		..Default::default()
+	});
+	let (_client, workspace) = attach_as(&server, config).await;
+
+	server.join("bob").await;
+	server.move_cursor("bob", "main.rs").await;
+	presence_changed(&workspace, "bob").await;
+
+	presence_changed(&workspace, "bob").await;
+	assert_eq!(workspace.user_presence("bob").unwrap().status, Status::Idle);
+	presence_changed(&workspace, "bob").await;
+	assert_eq!(workspace.user_presence("bob").unwrap().status, Status::Away);
+
+	server.move_cursor("bob", "main.rs").await;
+	presence_changed(&workspace, "bob").await;
+	assert_eq!(
+		workspace.user_presence("bob").unwrap().status,
+		Status::Active
+	);
+}
@@ -0,0 +1,283 @@
+mod common;
+
+use std::time::Duration;
+
+use codemp::{
+	auth::{AuthProvider, Credentials},
+	errors::ErrorKind,
+};
+use common::{MockServer, TIMEOUT};
+
+#[tokio::test]
+async fn session_token_is_refreshed_before_expiring() {
+	let server = MockServer::start().await;
+	server.expire_tokens_after(Duration::from_secs(3));
+	let mut config = server.config();
+	config.tuning = Some(codemp::api::Tuning {
+		refresh_margin_ms: Some(2500),
+		..Default::default()
+	});
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect to stand-in server");
+
+	tokio::time::timeout(TIMEOUT, async {
+		while server.refreshes() == 0 {
+			tokio::time::sleep(Duration::from_millis(50)).await;
+		}
+	})
+	.await
+	.expect("session token was never refreshed");
+
+	client
+		.fetch_owned_workspaces()
+		.await
+		.expect("could not list workspaces");
+	assert_ne!(
+		server.seen_tokens().last(),
+		server.login_token().as_ref(),
+		"requests should use the refreshed token"
+	);
+}
+
+#[tokio::test]
+async fn rejected_session_requests_are_retried_after_refreshing() {
+	let server = MockServer::start().await;
+	let client = codemp::Client::connect(server.config())
+		.await
+		.expect("could not connect to stand-in server");
+
+	server.reject_next(1);
+	client
+		.create_workspace("workspace")
+		.await
+		.expect("request was not retried");
+	assert_eq!(server.refreshes(), 1);
+
+	let seen = server.seen_tokens();
+	assert_eq!(seen.len(), 2);
+	assert_ne!(seen[0], seen[1], "retry should use the refreshed token");
+
+	server.reject_next(2);
+	assert!(client.create_workspace("workspace").await.is_err());
+	assert_eq!(server.refreshes(), 2, "requests are only retried once");
+}
+
+#[tokio::test]
+async fn rejected_workspace_requests_renew_workspace_token() {
+	let server = MockServer::start().await;
+	let client = codemp::Client::connect(server.config())
+		.await
+		.expect("could not connect to stand-in server");
+	let workspace = client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace");
+	assert_eq!(server.accesses(), 1);
+
+	server.reject_next(1);
+	workspace
+		.fetch_buffers()
+		.await
+		.expect("request was not retried");
+	assert_eq!(server.accesses(), 2);
+	assert_eq!(server.refreshes(), 0);
+}
+
+#[tokio::test]
+async fn workspace_token_is_renewed_before_expiring() {
+	let server = MockServer::start().await;
+	server.expire_tokens_after(Duration::from_secs(3));
+	let mut config = server.config();
+	config.tuning = Some(codemp::api::Tuning {
+		refresh_margin_ms: Some(2500),
+		..Default::default()
+	});
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect to stand-in server");
+	let _workspace = client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace");
+
+	tokio::time::timeout(TIMEOUT, async {
+		while server.accesses() < 2 {
+			tokio::time::sleep(Duration::from_millis(50)).await;
+		}
+	})
+	.await
+	.expect("workspace token was never renewed");
+}
+
+#[tokio::test]
+async fn pre_issued_token_skips_login() {
+	let server = MockServer::start().await;
+	let mut config = server.config();
+	config.token = Some(common::user_token("bob"));
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect to stand-in server");
+
+	client
+		.fetch_owned_workspaces()
+		.await
+		.expect("could not list workspaces");
+	assert_eq!(server.logins(), 0);
+	assert_eq!(server.seen_tokens(), vec![common::user_token("bob")]);
+	assert_eq!(client.current_user().name, "bob");
+	assert_eq!(
+		client.current_user().id,
+		uuid::Uuid::from(common::user("bob").id)
+	);
+}
+
+#[tokio::test]
+async fn pre_issued_token_without_identity_is_refused() {
+	let server = MockServer::start().await;
+	let mut config = server.config();
+	config.token = Some("pre-issued".into());
+	let err = codemp::Client::connect(config)
+		.await
+		.expect_err("token of unknown user was accepted");
+	assert_eq!(err.kind(), ErrorKind::Unauthorized);
+	assert_eq!(server.logins(), 0);
+}
+
+/// Hands out the password only once, counting how many times it was asked.
+struct Once(std::sync::atomic::AtomicUsize);
+
+impl AuthProvider for Once {
+	async fn credentials(&self) -> codemp::errors::ConnectionResult<Credentials> {
+		match self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+			0 => Ok(Credentials::Password {
+				username: "alice".into(),
+				password: "password".into(),
+			}),
+			_ => Ok(Credentials::Token("from-provider".into())),
+		}
+	}
+}
+
+#[tokio::test]
+async fn provider_is_asked_again_when_refresh_is_refused() {
+	let server = MockServer::start().await;
+	let client = codemp::Client::connect_with(server.config(), Once(Default::default()))
+		.await
+		.expect("could not connect to stand-in server");
+	assert_eq!(server.logins(), 1);
+
+	server.refuse_refresh();
+	server.reject_next(1);
+	client
+		.create_workspace("workspace")
+		.await
+		.expect("request was not retried");
+	assert_eq!(
+		server.seen_tokens().last().map(String::as_str),
+		Some("from-provider")
+	);
+}
+
+#[tokio::test]
+async fn short_lived_tokens_are_not_renewed_over_and_over() {
+	let server = MockServer::start().await;
+	// shorter than the default refresh margin, so every token is due as soon as it's issued
+	server.expire_tokens_after(Duration::from_secs(2));
+	let _client = codemp::Client::connect(server.config())
+		.await
+		.expect("could not connect to stand-in server");
+
+	tokio::time::sleep(Duration::from_secs(2)).await;
+	let refreshes = server.refreshes();
+	assert!(refreshes > 0, "short-lived token was never renewed");
+	assert!(refreshes <= 10, "token was renewed {refreshes} times in 2s");
+}
+
+/// Hands out the password only once, failing afterwards.
+struct Revoked(std::sync::atomic::AtomicBool);
+
+impl AuthProvider for Revoked {
+	async fn credentials(&self) -> codemp::errors::ConnectionResult<Credentials> {
+		if self.0.swap(true, std::sync::atomic::Ordering::SeqCst) {
+			return Err(codemp::errors::ConnectionError::Auth("revoked".into()));
+		}
+		Ok(Credentials::Password {
+			username: "alice".into(),
+			password: "password".into(),
+		})
+	}
+}
+
+#[tokio::test]
+async fn failed_renewals_are_retried_less_and_less_often() {
+	let server = MockServer::start().await;
+	server.expire_tokens_after(Duration::from_secs(3));
+	server.refuse_refresh();
+	let mut config = server.config();
+	config.tuning = Some(codemp::api::Tuning {
+		refresh_margin_ms: Some(2900),
+		retry_backoff_ms: Some(100),
+		..Default::default()
+	});
+	let _client = codemp::Client::connect_with(config, Revoked(Default::default()))
+		.await
+		.expect("could not connect to stand-in server");
+
+	tokio::time::timeout(TIMEOUT, async {
+		while server.refresh_attempts().len() < 4 {
+			tokio::time::sleep(Duration::from_millis(20)).await;
+		}
+	})
+	.await
+	.expect("session token renewal was not retried");
+
+	let attempts = server.refresh_attempts();
+	let intervals: Vec<_> = attempts.windows(2).map(|w| w[1] - w[0]).collect();
+	assert!(intervals[0] >= Duration::from_millis(100));
+	for pair in intervals.windows(2) {
+		assert!(
+			pair[1] >= pair[0] + Duration::from_millis(50),
+			"retry interval did not grow: {intervals:?}"
+		);
+	}
+}
+
+#[tokio::test]
+async fn workspaces_share_the_client_connection() {
+	let server = MockServer::start().await;
+	let client = codemp::Client::connect(server.config())
+		.await
+		.expect("could not connect to stand-in server");
+	for name in ["first", "second", "third"] {
+		client
+			.attach_workspace(name)
+			.await
+			.expect("could not attach to workspace");
+	}
+	assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn connection_errors_are_classified() {
+	let server = MockServer::start().await;
+	let config = codemp::api::Config {
+		password: "wrong".into(),
+		..server.config()
+	};
+	let err = codemp::Client::connect(config)
+		.await
+		.expect_err("wrong password was accepted");
+	assert_eq!(err.kind(), ErrorKind::Unauthorized);
+	assert!(!err.is_retryable());
+
+	let config = codemp::api::Config {
+		port: Some(1),
+		..server.config()
+	};
+	let err = codemp::Client::connect(config)
+		.await
+		.expect_err("connected to a closed port");
+	assert_eq!(err.kind(), ErrorKind::Unavailable);
+	assert!(err.is_retryable());
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use codemp::api::TlsSettings;
+use common::MockServer;
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+/// SHA-256 fingerprint of `certs/server.pem`.
+const SERVER_FINGERPRINT: &str = "da6eb4920cec509807f7304bf24d5c17737f9b216525519eadd285ea08530a60";
+
+fn cert(name: &str) -> String {
+	format!("{}/tests/certs/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+fn read(name: &str) -> String {
+	std::fs::read_to_string(cert(name)).expect("missing test certificate")
+}
+
+fn server_identity() -> ServerTlsConfig {
+	ServerTlsConfig::new().identity(Identity::from_pem(read("server.pem"), read("server.key")))
+}
+
+fn config(server: &MockServer, tls: TlsSettings) -> codemp::api::Config {
+	codemp::api::Config {
+		tls: Some(true),
+		tls_settings: Some(tls),
+		..server.config()
+	}
+}
+
+fn private_ca() -> TlsSettings {
+	TlsSettings {
+		ca_bundle: Some(cert("ca.pem")),
+		native_roots: Some(false),
+		..Default::default()
+	}
+}
+
+#[tokio::test]
+async fn private_ca_is_trusted() {
+	let server = MockServer::start_tls(server_identity()).await;
+	let client = codemp::Client::connect(config(&server, private_ca()))
+		.await
+		.expect("could not connect trusting private ca");
+	client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace over tls");
+}
+
+#[tokio::test]
+async fn unknown_ca_is_rejected() {
+	let server = MockServer::start_tls(server_identity()).await;
+	let res = codemp::Client::connect(config(&server, TlsSettings::default())).await;
+	assert!(res.is_err(), "server certificate should not be trusted");
+}
+
+#[tokio::test]
+async fn client_certificate_is_presented() {
+	let server = MockServer::start_tls(
+		server_identity().client_ca_root(Certificate::from_pem(read("ca.pem"))),
+	)
+	.await;
+
+	assert!(
+		codemp::Client::connect(config(&server, private_ca()))
+			.await
+			.is_err(),
+		"server should require a client certificate"
+	);
+
+	let tls = TlsSettings {
+		client_cert: Some(cert("client.pem")),
+		client_key: Some(cert("client.key")),
+		..private_ca()
+	};
+	codemp::Client::connect(config(&server, tls))
+		.await
+		.expect("could not connect presenting client certificate");
+}
+
+#[tokio::test]
+async fn server_certificate_must_match_pin() {
+	let server = MockServer::start_tls(server_identity()).await;
+
+	let pinned = TlsSettings {
+		pinned_sha256: Some(SERVER_FINGERPRINT.to_uppercase()),
+		..private_ca()
+	};
+	codemp::Client::connect(config(&server, pinned))
+		.await
+		.expect("could not connect with matching pin");
+
+	let mismatched = TlsSettings {
+		pinned_sha256: Some("00".repeat(32)),
+		..private_ca()
+	};
+	assert!(codemp::Client::connect(config(&server, mismatched))
+		.await
+		.is_err());
+}
+
+#[tokio::test]
+async fn unreadable_certificates_are_reported() {
+	let server = MockServer::start_tls(server_identity()).await;
+	let tls = TlsSettings {
+		ca_bundle: Some(cert("missing.pem")),
+		..private_ca()
+	};
+	assert!(matches!(
+		codemp::Client::connect(config(&server, tls)).await,
+		Err(codemp::errors::ConnectionError::InvalidConfig(_))
+	));
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use common::{MockProxy, MockServer, TIMEOUT};
+
+#[tokio::test]
+async fn connects_through_http_proxy() {
+	let server = MockServer::start().await;
+	let proxy = MockProxy::http().await;
+	let config = codemp::api::Config {
+		proxy: Some(format!("http://{}", proxy.addr)),
+		..server.config()
+	};
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect through proxy");
+	client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace through proxy");
+	assert_eq!(proxy.tunnels(), 1);
+}
+
+#[tokio::test]
+async fn connects_through_socks5_proxy() {
+	let server = MockServer::start().await;
+	let proxy = MockProxy::socks5().await;
+	let config = codemp::api::Config {
+		proxy: Some(format!("socks5://{}", proxy.addr)),
+		..server.config()
+	};
+	codemp::Client::connect(config)
+		.await
+		.expect("could not connect through proxy");
+	assert_eq!(proxy.tunnels(), 1);
+}
+
+#[tokio::test]
+async fn connect_timeout_covers_unresponsive_proxies() {
+	let server = MockServer::start().await;
+	let config = codemp::api::Config {
+		proxy: Some(format!("http://{}", common::black_hole().await)),
+		tuning: Some(codemp::api::Tuning {
+			connect_timeout_ms: Some(200),
+			..Default::default()
+		}),
+		..server.config()
+	};
+	let res = tokio::time::timeout(TIMEOUT, codemp::Client::connect(config))
+		.await
+		.expect("connection attempt did not time out");
+	assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn invalid_proxies_are_rejected() {
+	let server = MockServer::start().await;
+	let config = codemp::api::Config {
+		proxy: Some("gopher://proxy.example".into()),
+		..server.config()
+	};
+	assert!(matches!(
+		codemp::Client::connect(config).await,
+		Err(codemp::errors::ConnectionError::InvalidConfig(_))
+	));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn connects_over_unix_socket() {
+	let path = std::env::temp_dir().join(format!("codemp-test-{}.sock", std::process::id()));
+	let _ = std::fs::remove_file(&path);
+	let server = MockServer::start_unix(&path).await;
+	let config = server.config();
+	assert_eq!(
+		config.host.as_deref(),
+		Some(format!("unix://{}", path.display()).as_str())
+	);
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect over unix socket");
+	client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace over unix socket");
+	assert_eq!(server.connections(), 1);
+	let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn connects_over_custom_transport() {
+	let (server, transport) = MockServer::start_in_process();
+	let config = codemp::api::Config {
+		tls: None, // custom transports default to plaintext
+		..server.config()
+	};
+	let client = codemp::Client::connect_with_transport(config, transport)
+		.await
+		.expect("could not connect in-process");
+	client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace in-process");
+	assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn connects_to_endpoint_with_path_prefix() {
+	let server = MockServer::start_under("/codemp").await;
+	let base = server.config();
+	let url = format!(
+		"http://{}:{}/codemp/",
+		base.host.as_deref().unwrap(),
+		base.port.unwrap()
+	);
+	let config =
+		codemp::api::Config::from_url(url, "alice", "password").expect("endpoint should be valid");
+	let client = codemp::Client::connect(config)
+		.await
+		.expect("could not connect under path prefix");
+	client
+		.attach_workspace("workspace")
+		.await
+		.expect("could not attach to workspace under path prefix");
+}
+
+#[tokio::test]
+async fn invalid_endpoints_are_rejected() {
+	assert!(matches!(
+		codemp::api::Config::from_url("localhost:50053", "alice", "password"),
+		Err(codemp::errors::ConnectionError::InvalidConfig(_))
+	));
+	let config = codemp::api::Config {
+		endpoint: Some("http://::1:50053".into()),
+		..codemp::api::Config::new("alice", "password")
+	};
+	assert!(matches!(
+		codemp::Client::connect(config).await,
+		Err(codemp::errors::ConnectionError::InvalidConfig(_))
+	));
+}
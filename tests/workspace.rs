@@ -0,0 +1,201 @@
+mod common;
+
+use std::time::Duration;
+
+use codemp::api::{controller::AsyncReceiver, Event};
+use common::{attach, MockServer, TIMEOUT};
+
+async fn next<T>(rx: &mut tokio::sync::mpsc::UnboundedReceiver<T>) -> T {
+	tokio::time::timeout(TIMEOUT, rx.recv())
+		.await
+		.expect("timed out waiting for callback")
+		.expect("callback channel closed")
+}
+
+#[tokio::test]
+async fn workspace_callback_runs_on_every_event() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+
+	let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+	workspace.callback(move |ws: codemp::Workspace| {
+		tx.send(ws.id()).expect("test stopped listening");
+	});
+
+	server.join("bob").await;
+	assert_eq!(next(&mut rx).await, "workspace");
+	assert!(workspace.user_list().iter().any(|u| u.name == "bob"));
+
+	server.leave("bob").await;
+	assert_eq!(next(&mut rx).await, "workspace");
+	assert!(!workspace.user_list().iter().any(|u| u.name == "bob"));
+
+	server.create("a.txt").await;
+	next(&mut rx).await;
+	server.rename("a.txt", "b.txt").await;
+	next(&mut rx).await;
+	assert_eq!(workspace.search_buffers(None), vec!["b.txt".to_string()]);
+	server.delete("b.txt").await;
+	next(&mut rx).await;
+	assert!(workspace.search_buffers(None).is_empty());
+
+	// every event is still available to be received, in order
+	let mut events = Vec::new();
+	while let Some(event) = workspace.try_recv().await.expect("workspace stopped") {
+		events.push(event);
+	}
+	assert!(matches!(
+		events.as_slice(),
+		[
+			Event::UserJoin { .. },
+			Event::UserLeave { .. },
+			Event::FileTreeUpdated { .. },
+			Event::FileTreeUpdated { .. },
+			Event::FileTreeUpdated { .. },
+		]
+	));
+}
+
+#[tokio::test]
+async fn cleared_workspace_callback_is_not_invoked() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+
+	let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+	workspace.callback(move |_: codemp::Workspace| {
+		tx.send(()).expect("test stopped listening");
+	});
+	workspace.clear_callback();
+
+	server.create("a.txt").await;
+	tokio::time::timeout(TIMEOUT, workspace.poll())
+		.await
+		.expect("timed out waiting for event")
+		.expect("workspace stopped");
+	assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn subscriptions_receive_events_independently() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+
+	let files = workspace.subscribe();
+	let users = workspace.subscribe();
+	let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+	users.callback(move |_: codemp::workspace::Subscription| {
+		tx.send(()).expect("test stopped listening");
+	});
+
+	server.join("bob").await;
+	next(&mut rx).await;
+
+	let recv = |sub: codemp::workspace::Subscription| async move {
+		tokio::time::timeout(TIMEOUT, sub.recv())
+			.await
+			.expect("timed out waiting for event")
+	};
+	assert!(matches!(
+		recv(files.clone()).await,
+		Ok(Event::UserJoin { .. })
+	));
+	assert!(matches!(
+		recv(users.clone()).await,
+		Ok(Event::UserJoin { .. })
+	));
+
+	server.close_events();
+	assert!(recv(files).await.is_err());
+}
+
+#[tokio::test]
+async fn subscriptions_can_be_created_outside_the_runtime() {
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let handle = workspace.clone();
+	let sub = std::thread::spawn(move || handle.subscribe())
+		.join()
+		.expect("subscribing outside the runtime panicked");
+
+	server.create("a.txt").await;
+	assert!(matches!(
+		tokio::time::timeout(TIMEOUT, sub.recv()).await,
+		Ok(Ok(Event::FileTreeUpdated { .. }))
+	));
+}
+
+#[tokio::test]
+async fn subscription_stream_ends_when_worker_stops() {
+	use tokio_stream::StreamExt;
+
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let mut stream = workspace.subscribe().into_stream();
+
+	server.create("a.txt").await;
+	server.join("bob").await;
+	server.close_events();
+
+	let events: Vec<_> = tokio::time::timeout(TIMEOUT, (&mut stream).collect::<Vec<_>>())
+		.await
+		.expect("stream did not end");
+	assert!(matches!(
+		events.as_slice(),
+		[
+			Ok(Event::FileTreeUpdated { .. }),
+			Ok(Event::UserJoin { .. })
+		]
+	));
+	assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn cursor_stream_ends_when_server_closes_it() {
+	use tokio_stream::StreamExt;
+
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let cursor = workspace.cursor();
+	server.move_cursor("bob", "main.rs").await;
+	let moved = tokio::time::timeout(TIMEOUT, cursor.recv())
+		.await
+		.expect("timed out waiting for cursor");
+	assert_eq!(moved.expect("cursor stopped").sel.buffer, "main.rs");
+
+	server.close_cursors();
+	let mut stream = cursor.into_stream();
+	let rest: Vec<_> = tokio::time::timeout(TIMEOUT, (&mut stream).collect::<Vec<_>>())
+		.await
+		.expect("stream did not end");
+	assert!(rest.iter().all(Result::is_err));
+	assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn select_reports_which_controller_is_ready() {
+	use codemp::ext::{select, Selectable};
+
+	let server = MockServer::start().await;
+	let (_client, workspace) = attach(&server).await;
+	let controllers: Vec<Selectable> =
+		vec![workspace.cursor().into(), workspace.subscribe().into()];
+
+	let short = Some(Duration::from_millis(50));
+	assert!(select(&controllers, short).await.is_none());
+
+	server.join("bob").await;
+	match select(&controllers, Some(TIMEOUT)).await {
+		Some((1, Ok(()))) => {}
+		other => panic!("expected subscription to be ready, got {other:?}"),
+	}
+
+	server.close_events();
+	let Selectable::Subscription(sub) = &controllers[1] else {
+		unreachable!()
+	};
+	sub.try_recv().await.expect("pending event was lost");
+	match select(&controllers, Some(TIMEOUT)).await {
+		Some((1, Err(_))) => {}
+		other => panic!("expected subscription to be stopped, got {other:?}"),
+	}
+}